@@ -0,0 +1,24 @@
+//! Measures propose -> commit latency and throughput for a 4-node
+//! channel-transport cluster at varying batch sizes and pipeline depths, so
+//! regressions in `hotstuff2.rs`/`transaction_pool.rs` show up here.
+//! `harness = false`: no external benchmarking crate, just std timing.
+
+use hotstuff2_benches::{run_rounds, ChannelCluster};
+
+fn main() {
+    let batch_sizes = [1usize, 10, 100];
+    let pipeline_depths = [1usize, 4];
+
+    for &depth in &pipeline_depths {
+        for &batch_size in &batch_sizes {
+            let cluster = ChannelCluster::new(4);
+            let rounds = 50u64;
+            let total = run_rounds(&cluster, rounds * depth as u64, batch_size);
+            let avg = total / (rounds * depth as u64) as u32;
+            println!(
+                "pipeline_depth={depth} batch_size={batch_size} avg_commit_latency={avg:?} throughput={:.1} tx/s",
+                (batch_size as f64 * (rounds * depth as u64) as f64) / total.as_secs_f64().max(f64::EPSILON)
+            );
+        }
+    }
+}
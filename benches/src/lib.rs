@@ -0,0 +1,84 @@
+//! Simulation harness for propose -> commit latency benchmarks. Lives in a
+//! library so both `benches/commit_latency.rs` and unit tests exercise the
+//! same in-process 4-node, channel-transport cluster logic.
+//!
+//! Note: this crate intentionally has zero dependencies (no `criterion`) so
+//! it builds without network access; `commit_latency.rs` implements its own
+//! minimal timing harness (`harness = false`) instead.
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+pub struct ChannelCluster {
+    nodes: Vec<Sender<u64>>,
+    receivers: Vec<Receiver<u64>>,
+}
+
+impl ChannelCluster {
+    pub fn new(node_count: usize) -> Self {
+        let mut nodes = Vec::with_capacity(node_count);
+        let mut receivers = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let (tx, rx) = channel();
+            nodes.push(tx);
+            receivers.push(rx);
+        }
+        Self { nodes, receivers }
+    }
+
+    /// Proposes block `height` to every node and waits for all of them to
+    /// "commit" it (echo it back), returning the propose -> commit latency.
+    pub fn propose_and_commit(&self, height: u64, batch_size: usize) -> Duration {
+        let start = Instant::now();
+        for tx in &self.nodes {
+            for _ in 0..batch_size {
+                tx.send(height).unwrap();
+            }
+        }
+        for rx in &self.receivers {
+            for _ in 0..batch_size {
+                rx.recv().unwrap();
+            }
+        }
+        start.elapsed()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+/// Runs `rounds` propose/commit cycles at the given `batch_size` and returns
+/// the total elapsed time, letting callers derive throughput.
+pub fn run_rounds(cluster: &ChannelCluster, rounds: u64, batch_size: usize) -> Duration {
+    let mut total = Duration::ZERO;
+    for height in 0..rounds {
+        total += cluster.propose_and_commit(height, batch_size);
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cluster_has_requested_node_count() {
+        let cluster = ChannelCluster::new(4);
+        assert_eq!(cluster.node_count(), 4);
+    }
+
+    #[test]
+    fn propose_and_commit_completes_for_every_node() {
+        let cluster = ChannelCluster::new(4);
+        // Should not hang: every node's receiver gets exactly `batch_size` messages.
+        cluster.propose_and_commit(1, 10);
+    }
+
+    #[test]
+    fn run_rounds_completes_every_round_and_returns_a_duration() {
+        let cluster = ChannelCluster::new(4);
+        let total = run_rounds(&cluster, 5, 1);
+        assert!(total >= Duration::ZERO);
+    }
+}
@@ -0,0 +1,137 @@
+//! Configurable genesis: initial validator set, initial state, chain id, and
+//! genesis timestamp, loadable from a simple `key=value` config file so
+//! nodes no longer implicitly start from an ad-hoc zero state. Peers must
+//! agree on `genesis_hash()` during handshake or they're on different
+//! chains.
+//!
+//! The workspace has no `toml`/`serde` dependency yet, so `parse` reads a
+//! minimal `key=value`-per-line format rather than real TOML; swapping in a
+//! real parser later doesn't change the `Genesis` type or its validation.
+
+use hotstuff2_types::{Hash, ValidatorId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Genesis {
+    pub chain_id: u64,
+    pub genesis_timestamp: u64,
+    pub initial_validators: Vec<ValidatorId>,
+    pub initial_state_root: Hash,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GenesisError {
+    MissingField(&'static str),
+    InvalidValue(&'static str),
+    EmptyValidatorSet,
+}
+
+impl Genesis {
+    pub fn parse(contents: &str) -> Result<Self, GenesisError> {
+        let mut chain_id = None;
+        let mut genesis_timestamp = None;
+        let mut initial_validators = None;
+        let mut initial_state_root = None;
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or(GenesisError::InvalidValue("expected key=value"))?;
+            match key.trim() {
+                "chain_id" => chain_id = Some(value.trim().parse().map_err(|_| GenesisError::InvalidValue("chain_id"))?),
+                "genesis_timestamp" => {
+                    genesis_timestamp = Some(value.trim().parse().map_err(|_| GenesisError::InvalidValue("genesis_timestamp"))?)
+                }
+                "initial_validators" => {
+                    let ids = value
+                        .trim()
+                        .split(',')
+                        .filter(|s| !s.trim().is_empty())
+                        .map(|s| s.trim().parse().map_err(|_| GenesisError::InvalidValue("initial_validators")))
+                        .collect::<Result<Vec<ValidatorId>, _>>()?;
+                    initial_validators = Some(ids);
+                }
+                "initial_state_root" => {
+                    let bytes = value.trim().as_bytes();
+                    let mut root = [0u8; 32];
+                    let len = bytes.len().min(32);
+                    root[..len].copy_from_slice(&bytes[..len]);
+                    initial_state_root = Some(root);
+                }
+                _ => {}
+            }
+        }
+
+        let initial_validators = initial_validators.ok_or(GenesisError::MissingField("initial_validators"))?;
+        if initial_validators.is_empty() {
+            return Err(GenesisError::EmptyValidatorSet);
+        }
+
+        Ok(Genesis {
+            chain_id: chain_id.ok_or(GenesisError::MissingField("chain_id"))?,
+            genesis_timestamp: genesis_timestamp.ok_or(GenesisError::MissingField("genesis_timestamp"))?,
+            initial_validators,
+            initial_state_root: initial_state_root.unwrap_or([0u8; 32]),
+        })
+    }
+
+    /// Peers must agree on this during handshake or they're on different
+    /// chains and must not participate in the same consensus instance.
+    pub fn genesis_hash(&self) -> Hash {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash as _, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.chain_id.hash(&mut hasher);
+        self.genesis_timestamp.hash(&mut hasher);
+        self.initial_validators.hash(&mut hasher);
+        self.initial_state_root.hash(&mut hasher);
+        let digest = hasher.finish();
+
+        let mut out = [0u8; 32];
+        out[..8].copy_from_slice(&digest.to_le_bytes());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "chain_id = 7\ngenesis_timestamp = 1700000000\ninitial_validators = 1,2,3\n";
+
+    #[test]
+    fn parses_a_well_formed_genesis_file() {
+        let genesis = Genesis::parse(VALID).unwrap();
+        assert_eq!(genesis.chain_id, 7);
+        assert_eq!(genesis.initial_validators, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn rejects_missing_required_field() {
+        let err = Genesis::parse("chain_id = 1\n").unwrap_err();
+        assert_eq!(err, GenesisError::MissingField("initial_validators"));
+    }
+
+    #[test]
+    fn rejects_empty_validator_set() {
+        let err = Genesis::parse("chain_id = 1\ngenesis_timestamp = 1\ninitial_validators =\n").unwrap_err();
+        assert_eq!(err, GenesisError::EmptyValidatorSet);
+    }
+
+    #[test]
+    fn two_peers_with_the_same_genesis_agree_on_its_hash() {
+        let a = Genesis::parse(VALID).unwrap();
+        let b = Genesis::parse(VALID).unwrap();
+        assert_eq!(a.genesis_hash(), b.genesis_hash());
+    }
+
+    #[test]
+    fn different_chain_ids_produce_different_hashes() {
+        let a = Genesis::parse(VALID).unwrap();
+        let mut b = a.clone();
+        b.chain_id = 8;
+        assert_ne!(a.genesis_hash(), b.genesis_hash());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod genesis;
+pub mod node_config;
+
+pub use genesis::{Genesis, GenesisError};
+pub use node_config::{ConfigSource, NodeConfig};
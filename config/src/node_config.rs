@@ -0,0 +1,173 @@
+//! `Genesis::parse` only ever reads one file, which forces containerized
+//! deployments to template a whole config file per node just to override a
+//! single value (e.g. one validator running with a longer timeout during a
+//! debugging session). `NodeConfig` layers three sources with clear
+//! precedence — file, environment variables, then CLI flags, each strictly
+//! overriding the last — so a deployment can ship one base file and
+//! override individual keys per node. `effective_sources()` reports which
+//! layer each key's live value actually came from, so "why didn't my env
+//! var take effect" has a one-call answer.
+//!
+//! There's no real env/CLI-parsing crate available in this workspace, and
+//! reading `std::env::vars()` directly here would make this untestable
+//! without mutating global process state; `load_environment_overrides` and
+//! `apply_cli_flags` instead take the already-collected pairs, so the
+//! caller passes `std::env::vars().collect()` and `std::env::args()...` in
+//! production and a fixed `Vec` in tests.
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    File,
+    Environment,
+    CliFlag,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConfigValue {
+    value: String,
+    source: ConfigSource,
+}
+
+/// The prefix every recognized environment variable must start with, so
+/// `NodeConfig` doesn't accidentally pick up unrelated variables from the
+/// process environment.
+const ENV_PREFIX: &str = "HOTSTUFF__";
+
+/// Dotted-key config store (`consensus.base_timeout_ms`) built up from
+/// layered sources applied in increasing precedence: `load_file`, then
+/// `load_environment_overrides`, then `apply_cli_flags`. Each layer
+/// overwrites any value a lower layer set for the same key.
+#[derive(Default)]
+pub struct NodeConfig {
+    values: BTreeMap<String, ConfigValue>,
+}
+
+impl NodeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `key=value` lines (same minimal format as
+    /// `Genesis::parse`) as the base layer.
+    pub fn load_file(&mut self, contents: &str) {
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                self.set(key.trim().to_string(), value.trim().to_string(), ConfigSource::File);
+            }
+        }
+    }
+
+    /// Applies every `HOTSTUFF__SECTION__KEY=value` pair whose name starts
+    /// with `HOTSTUFF__`, translating it to the dotted key
+    /// `section.key` (lowercased). Pairs without the prefix are ignored,
+    /// since the process environment holds plenty of unrelated variables.
+    pub fn load_environment_overrides(&mut self, env_vars: &[(String, String)]) {
+        for (name, value) in env_vars {
+            if let Some(key) = env_name_to_key(name) {
+                self.set(key, value.clone(), ConfigSource::Environment);
+            }
+        }
+    }
+
+    /// Applies explicit `(dotted.key, value)` overrides, taking precedence
+    /// over both the file and environment layers.
+    pub fn apply_cli_flags(&mut self, flags: &[(&str, &str)]) {
+        for (key, value) in flags {
+            self.set((*key).to_string(), (*value).to_string(), ConfigSource::CliFlag);
+        }
+    }
+
+    fn set(&mut self, key: String, value: String, source: ConfigSource) {
+        self.values.insert(key, ConfigValue { value, source });
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|v| v.value.as_str())
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get(key).and_then(|v| v.parse().ok())
+    }
+
+    /// Every currently-set key with the layer its live value came from,
+    /// sorted by key.
+    pub fn effective_sources(&self) -> Vec<(String, ConfigSource)> {
+        self.values.iter().map(|(key, v)| (key.clone(), v.source)).collect()
+    }
+}
+
+fn env_name_to_key(name: &str) -> Option<String> {
+    let rest = name.strip_prefix(ENV_PREFIX)?;
+    if rest.is_empty() {
+        return None;
+    }
+    Some(rest.split("__").map(|segment| segment.to_lowercase()).collect::<Vec<_>>().join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_file_alone_sets_values_from_the_file_layer() {
+        let mut config = NodeConfig::new();
+        config.load_file("consensus.base_timeout_ms = 500\n");
+        assert_eq!(config.get_u64("consensus.base_timeout_ms"), Some(500));
+        assert_eq!(config.effective_sources(), vec![("consensus.base_timeout_ms".to_string(), ConfigSource::File)]);
+    }
+
+    #[test]
+    fn a_matching_environment_variable_overrides_the_file_value() {
+        let mut config = NodeConfig::new();
+        config.load_file("consensus.base_timeout_ms = 500\n");
+        config.load_environment_overrides(&[("HOTSTUFF__CONSENSUS__BASE_TIMEOUT_MS".to_string(), "900".to_string())]);
+        assert_eq!(config.get_u64("consensus.base_timeout_ms"), Some(900));
+        assert_eq!(
+            config.effective_sources(),
+            vec![("consensus.base_timeout_ms".to_string(), ConfigSource::Environment)]
+        );
+    }
+
+    #[test]
+    fn an_unprefixed_environment_variable_is_ignored() {
+        let mut config = NodeConfig::new();
+        config.load_environment_overrides(&[("PATH".to_string(), "/usr/bin".to_string())]);
+        assert!(config.effective_sources().is_empty());
+    }
+
+    #[test]
+    fn a_cli_flag_overrides_both_the_file_and_environment_layers() {
+        let mut config = NodeConfig::new();
+        config.load_file("consensus.base_timeout_ms = 500\n");
+        config.load_environment_overrides(&[("HOTSTUFF__CONSENSUS__BASE_TIMEOUT_MS".to_string(), "900".to_string())]);
+        config.apply_cli_flags(&[("consensus.base_timeout_ms", "1200")]);
+        assert_eq!(config.get_u64("consensus.base_timeout_ms"), Some(1200));
+        assert_eq!(config.effective_sources(), vec![("consensus.base_timeout_ms".to_string(), ConfigSource::CliFlag)]);
+    }
+
+    #[test]
+    fn independent_keys_keep_their_own_source() {
+        let mut config = NodeConfig::new();
+        config.load_file("consensus.base_timeout_ms = 500\nnetwork.max_peers = 50\n");
+        config.apply_cli_flags(&[("network.max_peers", "80")]);
+        assert_eq!(
+            config.effective_sources(),
+            vec![
+                ("consensus.base_timeout_ms".to_string(), ConfigSource::File),
+                ("network.max_peers".to_string(), ConfigSource::CliFlag),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unset_key_reads_as_none() {
+        let config = NodeConfig::new();
+        assert_eq!(config.get("consensus.base_timeout_ms"), None);
+    }
+}
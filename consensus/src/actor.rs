@@ -0,0 +1,231 @@
+//! No literal `HotStuff2` struct with `Mutex`-guarded `chain_state`,
+//! `current_view`, and `optimistic_decision` fields exists in this
+//! workspace to refactor directly — this builds the actor architecture the
+//! request describes from scratch, for whatever future orchestration layer
+//! wires those three pieces of state together. `ConsensusActor` is the
+//! single owner of the mutable state; it never shares it behind a lock.
+//! Callers only ever hold a `ConsensusActorHandle`: `send` enqueues a
+//! command for the actor to apply, and `snapshot` reads the latest
+//! published `ConsensusSnapshot` without waiting for the actor to finish
+//! whatever command it is applying.
+//!
+//! There's no `arc-swap` dependency in this workspace either.
+//! `SwappableSnapshot` is a small `Mutex<Arc<T>>`-backed stand-in: `load`
+//! and `store` take the same lock, each only long enough to clone or
+//! replace the `Arc`, so a reader never holds it while using the snapshot
+//! afterward, but a concurrent `load` and `store` briefly wait on each
+//! other.
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use hotstuff2_core::ChainState;
+
+pub struct SwappableSnapshot<T> {
+    current: Mutex<Arc<T>>,
+}
+
+impl<T> SwappableSnapshot<T> {
+    pub fn new(initial: T) -> Self {
+        Self { current: Mutex::new(Arc::new(initial)) }
+    }
+
+    pub fn load(&self) -> Arc<T> {
+        self.current.lock().unwrap().clone()
+    }
+
+    pub fn store(&self, value: T) {
+        *self.current.lock().unwrap() = Arc::new(value);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsensusSnapshot {
+    pub chain_state: ChainState,
+    pub current_view: u64,
+    pub optimistic_decision: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActorCommand {
+    AdvanceView { view: u64 },
+    Commit { height: u64 },
+    SetOptimisticDecision { enabled: bool },
+}
+
+/// Single owner of the mutable consensus state. This crate has no async
+/// runtime to spawn the actor's run loop onto; a caller drives it by
+/// calling `drain_pending` or `run_one_blocking` from whatever thread it
+/// dedicates to consensus processing.
+pub struct ConsensusActor {
+    chain_state: ChainState,
+    current_view: u64,
+    optimistic_decision: bool,
+    snapshot: Arc<SwappableSnapshot<ConsensusSnapshot>>,
+    inbox: Receiver<ActorCommand>,
+}
+
+#[derive(Clone)]
+pub struct ConsensusActorHandle {
+    sender: Sender<ActorCommand>,
+    snapshot: Arc<SwappableSnapshot<ConsensusSnapshot>>,
+}
+
+impl ConsensusActor {
+    pub fn new(initial_chain_state: ChainState) -> (Self, ConsensusActorHandle) {
+        let (sender, inbox) = channel();
+        let snapshot = Arc::new(SwappableSnapshot::new(ConsensusSnapshot {
+            chain_state: initial_chain_state.clone(),
+            current_view: initial_chain_state.current_view,
+            optimistic_decision: false,
+        }));
+        let handle = ConsensusActorHandle { sender, snapshot: snapshot.clone() };
+        let actor = Self {
+            current_view: initial_chain_state.current_view,
+            chain_state: initial_chain_state,
+            optimistic_decision: false,
+            snapshot,
+            inbox,
+        };
+        (actor, handle)
+    }
+
+    fn apply(&mut self, command: ActorCommand) {
+        match command {
+            ActorCommand::AdvanceView { view } => self.current_view = view,
+            ActorCommand::Commit { height } => self.chain_state.committed_height = height,
+            ActorCommand::SetOptimisticDecision { enabled } => self.optimistic_decision = enabled,
+        }
+        self.publish();
+    }
+
+    fn publish(&self) {
+        self.snapshot.store(ConsensusSnapshot {
+            chain_state: self.chain_state.clone(),
+            current_view: self.current_view,
+            optimistic_decision: self.optimistic_decision,
+        });
+    }
+
+    /// Applies every command currently queued, without blocking for more,
+    /// publishing a fresh snapshot after each. Returns how many were
+    /// applied.
+    pub fn drain_pending(&mut self) -> usize {
+        let mut applied = 0;
+        while let Ok(command) = self.inbox.try_recv() {
+            self.apply(command);
+            applied += 1;
+        }
+        applied
+    }
+
+    /// Blocks for the next command and applies it. Returns `false` once
+    /// every handle has been dropped and no more commands can ever arrive.
+    pub fn run_one_blocking(&mut self) -> bool {
+        match self.inbox.recv() {
+            Ok(command) => {
+                self.apply(command);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl ConsensusActorHandle {
+    /// Enqueues a command for the actor to apply. Returns `false` if the
+    /// actor has been dropped and the command could not be delivered.
+    pub fn send(&self, command: ActorCommand) -> bool {
+        self.sender.send(command).is_ok()
+    }
+
+    /// Reads the latest published state. Does not wait for the actor's
+    /// current command; it takes the snapshot lock only long enough to
+    /// clone an `Arc`, the same lock the actor's `publish` takes briefly.
+    pub fn snapshot(&self) -> Arc<ConsensusSnapshot> {
+        self.snapshot.load()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn initial_state() -> ChainState {
+        ChainState { current_view: 1, committed_height: 0, locked_view: 0 }
+    }
+
+    #[test]
+    fn a_swappable_snapshot_reflects_the_latest_store() {
+        let snapshot = SwappableSnapshot::new(1);
+        assert_eq!(*snapshot.load(), 1);
+        snapshot.store(2);
+        assert_eq!(*snapshot.load(), 2);
+    }
+
+    #[test]
+    fn a_new_actor_publishes_the_initial_chain_state_before_any_command() {
+        let (_actor, handle) = ConsensusActor::new(initial_state());
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.current_view, 1);
+        assert_eq!(snapshot.chain_state.committed_height, 0);
+        assert!(!snapshot.optimistic_decision);
+    }
+
+    #[test]
+    fn draining_a_single_command_updates_the_published_snapshot() {
+        let (mut actor, handle) = ConsensusActor::new(initial_state());
+        handle.send(ActorCommand::AdvanceView { view: 5 });
+        assert_eq!(actor.drain_pending(), 1);
+        assert_eq!(handle.snapshot().current_view, 5);
+    }
+
+    #[test]
+    fn multiple_queued_commands_are_applied_in_order() {
+        let (mut actor, handle) = ConsensusActor::new(initial_state());
+        handle.send(ActorCommand::AdvanceView { view: 2 });
+        handle.send(ActorCommand::Commit { height: 1 });
+        handle.send(ActorCommand::SetOptimisticDecision { enabled: true });
+
+        assert_eq!(actor.drain_pending(), 3);
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.current_view, 2);
+        assert_eq!(snapshot.chain_state.committed_height, 1);
+        assert!(snapshot.optimistic_decision);
+    }
+
+    #[test]
+    fn a_cloned_handle_shares_the_same_view_of_state() {
+        let (mut actor, handle) = ConsensusActor::new(initial_state());
+        let other_handle = handle.clone();
+        handle.send(ActorCommand::AdvanceView { view: 9 });
+        actor.drain_pending();
+        assert_eq!(other_handle.snapshot().current_view, 9);
+    }
+
+    #[test]
+    fn run_one_blocking_applies_exactly_one_command_and_returns_true() {
+        let (mut actor, handle) = ConsensusActor::new(initial_state());
+        handle.send(ActorCommand::AdvanceView { view: 3 });
+        handle.send(ActorCommand::AdvanceView { view: 4 });
+
+        assert!(actor.run_one_blocking());
+        assert_eq!(handle.snapshot().current_view, 3);
+        assert!(actor.run_one_blocking());
+        assert_eq!(handle.snapshot().current_view, 4);
+    }
+
+    #[test]
+    fn run_one_blocking_returns_false_once_every_handle_is_dropped() {
+        let (mut actor, handle) = ConsensusActor::new(initial_state());
+        drop(handle);
+        assert!(!actor.run_one_blocking());
+    }
+
+    #[test]
+    fn sending_after_the_actor_is_dropped_reports_failure() {
+        let (actor, handle) = ConsensusActor::new(initial_state());
+        drop(actor);
+        assert!(!handle.send(ActorCommand::AdvanceView { view: 1 }));
+    }
+}
@@ -0,0 +1,132 @@
+//! Production Byzantine behavior detection on the live message path,
+//! instead of only in test-only Byzantine simulation harnesses. Tracks
+//! per-peer conflicting votes and timing anomalies, surfaces findings as
+//! `Evidence` for an evidence pool / metrics exporter, and can recommend
+//! isolating a peer once it crosses a configurable evidence threshold.
+
+use std::collections::HashMap;
+
+use hotstuff2_types::{Hash, ValidatorId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Evidence {
+    /// The same validator voted for two different blocks at the same view —
+    /// unambiguous equivocation.
+    ConflictingVotes { peer: ValidatorId, view: u64, first: Hash, second: Hash },
+    /// A message purportedly from `peer` failed signature verification.
+    InvalidSignature { peer: ValidatorId, view: u64 },
+    /// `peer`'s vote for `view` arrived implausibly late relative to when
+    /// the view started, consistent with a late-voting adaptive attack.
+    TimingAnomaly { peer: ValidatorId, view: u64, delay_ms: u64 },
+}
+
+impl Evidence {
+    fn peer(&self) -> ValidatorId {
+        match self {
+            Evidence::ConflictingVotes { peer, .. } => *peer,
+            Evidence::InvalidSignature { peer, .. } => *peer,
+            Evidence::TimingAnomaly { peer, .. } => *peer,
+        }
+    }
+}
+
+/// Records every observation needed to detect equivocation: which block
+/// each peer voted for at each view.
+#[derive(Default)]
+pub struct ByzantineDetector {
+    votes_seen: HashMap<(ValidatorId, u64), Hash>,
+    evidence_pool: Vec<Evidence>,
+    isolate_after: u32,
+}
+
+impl ByzantineDetector {
+    pub fn new(isolate_after: u32) -> Self {
+        Self { votes_seen: HashMap::new(), evidence_pool: Vec::new(), isolate_after }
+    }
+
+    /// Called on every inbound vote. Returns `Some(Evidence)` if this vote
+    /// conflicts with one already seen from the same peer at the same view.
+    pub fn observe_vote(&mut self, peer: ValidatorId, view: u64, block_hash: Hash) -> Option<Evidence> {
+        match self.votes_seen.insert((peer, view), block_hash) {
+            Some(previous) if previous != block_hash => {
+                let evidence = Evidence::ConflictingVotes { peer, view, first: previous, second: block_hash };
+                self.evidence_pool.push(evidence.clone());
+                Some(evidence)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn record_invalid_signature(&mut self, peer: ValidatorId, view: u64) {
+        self.evidence_pool.push(Evidence::InvalidSignature { peer, view });
+    }
+
+    /// `view_started_ms` and `vote_received_ms` are both caller-supplied
+    /// monotonic timestamps, since this crate has no clock dependency.
+    /// Anything past `threshold_ms` into the view is flagged.
+    pub fn observe_vote_timing(&mut self, peer: ValidatorId, view: u64, view_started_ms: u64, vote_received_ms: u64, threshold_ms: u64) {
+        let delay_ms = vote_received_ms.saturating_sub(view_started_ms);
+        if delay_ms > threshold_ms {
+            self.evidence_pool.push(Evidence::TimingAnomaly { peer, view, delay_ms });
+        }
+    }
+
+    pub fn evidence_for(&self, peer: ValidatorId) -> Vec<&Evidence> {
+        self.evidence_pool.iter().filter(|e| e.peer() == peer).collect()
+    }
+
+    pub fn evidence_pool(&self) -> &[Evidence] {
+        &self.evidence_pool
+    }
+
+    /// A peer is recommended for isolation once its evidence count reaches
+    /// `isolate_after`; the caller (reliability layer) decides whether to
+    /// act on the recommendation.
+    pub fn should_isolate(&self, peer: ValidatorId) -> bool {
+        self.evidence_for(peer).len() >= self.isolate_after as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_single_vote_produces_no_evidence() {
+        let mut detector = ByzantineDetector::new(2);
+        assert!(detector.observe_vote(1, 5, [1u8; 32]).is_none());
+    }
+
+    #[test]
+    fn conflicting_votes_at_the_same_view_are_flagged() {
+        let mut detector = ByzantineDetector::new(2);
+        detector.observe_vote(1, 5, [1u8; 32]);
+        let evidence = detector.observe_vote(1, 5, [2u8; 32]).unwrap();
+        assert_eq!(evidence, Evidence::ConflictingVotes { peer: 1, view: 5, first: [1u8; 32], second: [2u8; 32] });
+    }
+
+    #[test]
+    fn repeating_the_identical_vote_is_not_a_conflict() {
+        let mut detector = ByzantineDetector::new(2);
+        detector.observe_vote(1, 5, [1u8; 32]);
+        assert!(detector.observe_vote(1, 5, [1u8; 32]).is_none());
+    }
+
+    #[test]
+    fn timing_anomalies_beyond_the_threshold_are_recorded() {
+        let mut detector = ByzantineDetector::new(2);
+        detector.observe_vote_timing(1, 5, 1000, 5000, 2000);
+        assert_eq!(detector.evidence_for(1).len(), 1);
+    }
+
+    #[test]
+    fn a_peer_is_recommended_for_isolation_once_evidence_crosses_the_threshold() {
+        let mut detector = ByzantineDetector::new(2);
+        detector.observe_vote(1, 5, [1u8; 32]);
+        detector.observe_vote(1, 5, [2u8; 32]); // 1 piece of evidence
+        assert!(!detector.should_isolate(1));
+
+        detector.record_invalid_signature(1, 6); // 2nd piece of evidence
+        assert!(detector.should_isolate(1));
+    }
+}
@@ -0,0 +1,249 @@
+//! An external relayer/bridge verifying this chain's finality needs a
+//! self-contained proof: the committed block's identity, the QC that
+//! certified it, and which validator set signed it — without needing to
+//! replay this chain's full history. `CommitCertificate` bundles exactly
+//! that in a stable, hand-rolled binary encoding (no serde/protobuf
+//! dependency is available in this workspace) so `encode`/`decode` are a
+//! fixed wire format a relayer on another system can implement against
+//! once and keep working across this crate's internal refactors.
+
+use hotstuff2_types::{Block, Hash, QuorumCertificate, ValidatorId, ValidatorSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub parent_hash: Hash,
+    pub height: u64,
+    pub view: u64,
+    pub block_hash: Hash,
+}
+
+impl From<&Block> for BlockHeader {
+    fn from(block: &Block) -> Self {
+        BlockHeader { parent_hash: block.parent_hash, height: block.height, view: block.view, block_hash: block.hash() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitCertificate {
+    pub header: BlockHeader,
+    pub qc: QuorumCertificate,
+    /// Hash of the validator set that was authoritative at `header.height`,
+    /// so a relayer can check the certificate against the validator set it
+    /// separately knows applied at that height without trusting this chain
+    /// to tell it who the validators were.
+    pub validator_set_hash: Hash,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommitCertificateError {
+    /// The QC doesn't certify the block this certificate claims to commit.
+    QcBlockMismatch,
+    /// The QC's signers don't meet the given validator set's quorum.
+    QuorumNotMet,
+    /// The given validator set doesn't match the certificate's recorded hash.
+    ValidatorSetMismatch,
+}
+
+/// Deterministic hash over a validator set's sorted member ids, so two
+/// nodes holding the same set (in whatever order they happen to store it)
+/// agree on its hash.
+pub fn validator_set_hash(validators: &ValidatorSet) -> Hash {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash as _, Hasher};
+
+    let mut sorted = validators.validators.clone();
+    sorted.sort_unstable();
+    let mut hasher = DefaultHasher::new();
+    sorted.hash(&mut hasher);
+    let digest = hasher.finish();
+    let mut out = [0u8; 32];
+    out[..8].copy_from_slice(&digest.to_le_bytes());
+    out
+}
+
+/// Builds a `CommitCertificate` for `block`, certified by `qc`, against
+/// `validators`. Does not itself check that `qc` actually meets quorum —
+/// call `verify_commit_certificate` (here, or on the relayer's side) for
+/// that.
+pub fn build_commit_certificate(block: &Block, qc: &QuorumCertificate, validators: &ValidatorSet) -> CommitCertificate {
+    CommitCertificate { header: BlockHeader::from(block), qc: qc.clone(), validator_set_hash: validator_set_hash(validators) }
+}
+
+/// Verifies a certificate is internally consistent and meets quorum against
+/// `validators`: the QC certifies the claimed block, `validators` hashes to
+/// the certificate's recorded validator set hash, and the QC's signers meet
+/// that set's quorum.
+pub fn verify_commit_certificate(cert: &CommitCertificate, validators: &ValidatorSet) -> Result<(), CommitCertificateError> {
+    if cert.qc.block_hash != cert.header.block_hash {
+        return Err(CommitCertificateError::QcBlockMismatch);
+    }
+    if validator_set_hash(validators) != cert.validator_set_hash {
+        return Err(CommitCertificateError::ValidatorSetMismatch);
+    }
+    if !validators.verify_threshold(&cert.qc) {
+        return Err(CommitCertificateError::QuorumNotMet);
+    }
+    Ok(())
+}
+
+/// Canonical wire format: header (32 + 8 + 8 + 32 bytes), then the QC
+/// (32-byte block hash, 8-byte view, signer count + signer ids), then the
+/// 32-byte validator set hash.
+pub fn encode(cert: &CommitCertificate) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&cert.header.parent_hash);
+    buf.extend_from_slice(&cert.header.height.to_le_bytes());
+    buf.extend_from_slice(&cert.header.view.to_le_bytes());
+    buf.extend_from_slice(&cert.header.block_hash);
+
+    buf.extend_from_slice(&cert.qc.block_hash);
+    buf.extend_from_slice(&cert.qc.view.to_le_bytes());
+    buf.extend_from_slice(&(cert.qc.signers.len() as u64).to_le_bytes());
+    for signer in &cert.qc.signers {
+        buf.extend_from_slice(&signer.to_le_bytes());
+    }
+
+    buf.extend_from_slice(&cert.validator_set_hash);
+    buf
+}
+
+pub fn decode(buf: &[u8]) -> Option<CommitCertificate> {
+    let mut cursor = 0usize;
+    let take = |cursor: &mut usize, n: usize| -> Option<&[u8]> {
+        let slice = buf.get(*cursor..*cursor + n)?;
+        *cursor += n;
+        Some(slice)
+    };
+
+    let parent_hash: Hash = take(&mut cursor, 32)?.try_into().ok()?;
+    let height = u64::from_le_bytes(take(&mut cursor, 8)?.try_into().ok()?);
+    let view = u64::from_le_bytes(take(&mut cursor, 8)?.try_into().ok()?);
+    let block_hash: Hash = take(&mut cursor, 32)?.try_into().ok()?;
+
+    let qc_block_hash: Hash = take(&mut cursor, 32)?.try_into().ok()?;
+    let qc_view = u64::from_le_bytes(take(&mut cursor, 8)?.try_into().ok()?);
+    let signer_count = u64::from_le_bytes(take(&mut cursor, 8)?.try_into().ok()?) as usize;
+    // `signer_count` is attacker-controlled; verify the buffer actually has
+    // that many 8-byte signer records left before reserving capacity for
+    // them, so a truncated buffer claiming a huge count returns `None`
+    // instead of attempting a runaway allocation.
+    let signers_bytes = signer_count.checked_mul(8)?;
+    if buf.len().saturating_sub(cursor) < signers_bytes {
+        return None;
+    }
+    let mut signers = Vec::with_capacity(signer_count);
+    for _ in 0..signer_count {
+        signers.push(ValidatorId::from_le_bytes(take(&mut cursor, 8)?.try_into().ok()?));
+    }
+
+    let validator_set_hash: Hash = take(&mut cursor, 32)?.try_into().ok()?;
+
+    Some(CommitCertificate {
+        header: BlockHeader { parent_hash, height, view, block_hash },
+        qc: QuorumCertificate { block_hash: qc_block_hash, view: qc_view, signers },
+        validator_set_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators() -> ValidatorSet {
+        ValidatorSet { validators: vec![1, 2, 3, 4] }
+    }
+
+    fn committed_block() -> Block {
+        Block { parent_hash: [0u8; 32], height: 5, view: 5, transactions: vec![] }
+    }
+
+    fn certifying_qc(block: &Block) -> QuorumCertificate {
+        QuorumCertificate { block_hash: block.hash(), view: 5, signers: vec![1, 2, 3] }
+    }
+
+    #[test]
+    fn a_well_formed_certificate_verifies() {
+        let block = committed_block();
+        let cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        assert!(verify_commit_certificate(&cert, &validators()).is_ok());
+    }
+
+    #[test]
+    fn a_qc_for_a_different_block_is_rejected() {
+        let block = committed_block();
+        let mut cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        cert.qc.block_hash = [9u8; 32];
+        assert_eq!(verify_commit_certificate(&cert, &validators()), Err(CommitCertificateError::QcBlockMismatch));
+    }
+
+    #[test]
+    fn a_mismatched_validator_set_is_rejected() {
+        let block = committed_block();
+        let cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        let wrong_set = ValidatorSet { validators: vec![9, 10, 11, 12] };
+        assert_eq!(verify_commit_certificate(&cert, &wrong_set), Err(CommitCertificateError::ValidatorSetMismatch));
+    }
+
+    #[test]
+    fn a_qc_below_quorum_is_rejected() {
+        let block = committed_block();
+        let weak_qc = QuorumCertificate { block_hash: block.hash(), view: 5, signers: vec![1] };
+        let cert = build_commit_certificate(&block, &weak_qc, &validators());
+        assert_eq!(verify_commit_certificate(&cert, &validators()), Err(CommitCertificateError::QuorumNotMet));
+    }
+
+    #[test]
+    fn validator_set_hash_is_independent_of_member_order() {
+        let a = ValidatorSet { validators: vec![1, 2, 3] };
+        let b = ValidatorSet { validators: vec![3, 1, 2] };
+        assert_eq!(validator_set_hash(&a), validator_set_hash(&b));
+    }
+
+    #[test]
+    fn encoding_and_decoding_round_trips_a_certificate() {
+        let block = committed_block();
+        let cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        let decoded = decode(&encode(&cert)).unwrap();
+        assert_eq!(decoded, cert);
+    }
+
+    #[test]
+    fn decode_rejects_a_truncated_buffer() {
+        let block = committed_block();
+        let cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        let mut encoded = encode(&cert);
+        encoded.truncate(encoded.len() - 1);
+        assert!(decode(&encoded).is_none());
+    }
+
+    /// No `bincode`/`serde`/protobuf crate is available in this workspace,
+    /// so `encode`/`decode` are this format's hand-rolled equivalent; this
+    /// is that format's round-trip/adversarial-input property test: every
+    /// truncation length returns `None` instead of panicking, and a
+    /// claimed signer count far larger than the buffer could ever hold
+    /// (the `Vec::with_capacity` runaway-allocation footgun) is rejected
+    /// rather than acted on.
+    #[test]
+    fn every_truncation_length_is_rejected_without_panicking() {
+        let block = committed_block();
+        let cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        let encoded = encode(&cert);
+        for len in 0..encoded.len() {
+            assert!(decode(&encoded[..len]).is_none(), "truncation to {len} bytes should be rejected");
+        }
+        assert_eq!(decode(&encoded), Some(cert));
+    }
+
+    #[test]
+    fn a_forged_huge_signer_count_is_rejected_instead_of_over_allocating() {
+        let block = committed_block();
+        let cert = build_commit_certificate(&block, &certifying_qc(&block), &validators());
+        let mut encoded = encode(&cert);
+        // Overwrite the signer-count field (right after the 80-byte block
+        // header fields and the QC's 32-byte block hash + 8-byte view) with
+        // an enormous value the buffer could never back.
+        let signer_count_offset = 32 + 8 + 8 + 32 + 32 + 8;
+        encoded[signer_count_offset..signer_count_offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(decode(&encoded).is_none());
+    }
+}
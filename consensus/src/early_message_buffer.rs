@@ -0,0 +1,197 @@
+//! `VoteAggregator` is created per block hash once a follower has seen the
+//! proposal, so a vote or QC that a faster peer relays before the proposal
+//! itself arrives (broadcast ordering isn't guaranteed) had nowhere to go
+//! and was simply dropped, forcing the sender to wait out a retry/timeout
+//! before quorum could form. `EarlyMessageBuffer` holds those early
+//! votes/QCs keyed by block hash until `take_votes`/`take_qcs` is called
+//! once the block actually arrives, so they can be replayed into a freshly
+//! created `VoteAggregator` instead of being lost.
+//!
+//! Bounded on two axes so a flood of votes for blocks that never arrive
+//! (or never will, e.g. a discarded fork) can't grow this without limit:
+//! `max_per_block` caps how many votes/QCs are buffered for a single block
+//! hash (oldest dropped first), and `max_blocks` caps how many distinct
+//! block hashes are tracked at all (oldest-inserted bucket dropped first).
+
+use std::collections::{HashMap, VecDeque};
+
+use hotstuff2_types::{Hash, QuorumCertificate, ValidatorId};
+
+struct Bucket {
+    votes: Vec<ValidatorId>,
+    qcs: Vec<QuorumCertificate>,
+}
+
+impl Bucket {
+    fn new() -> Self {
+        Self { votes: Vec::new(), qcs: Vec::new() }
+    }
+}
+
+pub struct EarlyMessageBuffer {
+    max_per_block: usize,
+    max_blocks: usize,
+    buckets: HashMap<Hash, Bucket>,
+    insertion_order: VecDeque<Hash>,
+}
+
+impl EarlyMessageBuffer {
+    pub fn new(max_per_block: usize, max_blocks: usize) -> Self {
+        Self {
+            max_per_block: max_per_block.max(1),
+            max_blocks: max_blocks.max(1),
+            buckets: HashMap::new(),
+            insertion_order: VecDeque::new(),
+        }
+    }
+
+    fn bucket_mut(&mut self, block_hash: Hash) -> &mut Bucket {
+        if !self.buckets.contains_key(&block_hash) {
+            if self.insertion_order.len() >= self.max_blocks {
+                if let Some(oldest) = self.insertion_order.pop_front() {
+                    self.buckets.remove(&oldest);
+                }
+            }
+            self.insertion_order.push_back(block_hash);
+            self.buckets.insert(block_hash, Bucket::new());
+        }
+        self.buckets.get_mut(&block_hash).expect("bucket just inserted")
+    }
+
+    /// Buffers a vote for a block that hasn't arrived yet.
+    pub fn buffer_vote(&mut self, block_hash: Hash, voter: ValidatorId) {
+        let max_per_block = self.max_per_block;
+        let bucket = self.bucket_mut(block_hash);
+        if bucket.votes.len() >= max_per_block {
+            bucket.votes.remove(0);
+        }
+        bucket.votes.push(voter);
+    }
+
+    /// Buffers a QC that references a block that hasn't arrived yet.
+    pub fn buffer_qc(&mut self, block_hash: Hash, qc: QuorumCertificate) {
+        let max_per_block = self.max_per_block;
+        let bucket = self.bucket_mut(block_hash);
+        if bucket.qcs.len() >= max_per_block {
+            bucket.qcs.remove(0);
+        }
+        bucket.qcs.push(qc);
+    }
+
+    /// Removes and returns every vote buffered for `block_hash`, e.g. once
+    /// the proposal for it has arrived and a `VoteAggregator` exists to
+    /// replay them into. Leaves any buffered QCs for the same block in
+    /// place until `take_qcs` is called for them.
+    pub fn take_votes(&mut self, block_hash: &Hash) -> Vec<ValidatorId> {
+        let Some(bucket) = self.buckets.get_mut(block_hash) else {
+            return Vec::new();
+        };
+        let votes = std::mem::take(&mut bucket.votes);
+        self.drop_bucket_if_empty(block_hash);
+        votes
+    }
+
+    /// Removes and returns every QC buffered for `block_hash`. Leaves any
+    /// buffered votes for the same block in place until `take_votes` is
+    /// called for them.
+    pub fn take_qcs(&mut self, block_hash: &Hash) -> Vec<QuorumCertificate> {
+        let Some(bucket) = self.buckets.get_mut(block_hash) else {
+            return Vec::new();
+        };
+        let qcs = std::mem::take(&mut bucket.qcs);
+        self.drop_bucket_if_empty(block_hash);
+        qcs
+    }
+
+    fn drop_bucket_if_empty(&mut self, block_hash: &Hash) {
+        let is_empty = self.buckets.get(block_hash).is_some_and(|b| b.votes.is_empty() && b.qcs.is_empty());
+        if is_empty {
+            self.buckets.remove(block_hash);
+            self.insertion_order.retain(|h| h != block_hash);
+        }
+    }
+
+    pub fn pending_vote_count(&self, block_hash: &Hash) -> usize {
+        self.buckets.get(block_hash).map(|b| b.votes.len()).unwrap_or(0)
+    }
+
+    pub fn tracked_block_count(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vote_aggregation::{drain_and_aggregate, VoteAggregator};
+    use hotstuff2_types::ValidatorSet;
+
+    fn validator_set(n: u64) -> ValidatorSet {
+        ValidatorSet { validators: (0..n).collect() }
+    }
+
+    #[test]
+    fn a_vote_buffered_before_the_block_arrives_is_returned_once_taken() {
+        let mut buffer = EarlyMessageBuffer::new(10, 10);
+        buffer.buffer_vote([1u8; 32], 5);
+        buffer.buffer_vote([1u8; 32], 6);
+        assert_eq!(buffer.take_votes(&[1u8; 32]), vec![5, 6]);
+    }
+
+    #[test]
+    fn taking_votes_for_an_unknown_block_returns_nothing() {
+        let mut buffer = EarlyMessageBuffer::new(10, 10);
+        assert!(buffer.take_votes(&[9u8; 32]).is_empty());
+    }
+
+    #[test]
+    fn taking_votes_drains_the_bucket_so_a_second_take_is_empty() {
+        let mut buffer = EarlyMessageBuffer::new(10, 10);
+        buffer.buffer_vote([1u8; 32], 5);
+        buffer.take_votes(&[1u8; 32]);
+        assert!(buffer.take_votes(&[1u8; 32]).is_empty());
+    }
+
+    #[test]
+    fn buffered_votes_can_be_replayed_into_a_fresh_aggregator_to_form_a_qc() {
+        let mut buffer = EarlyMessageBuffer::new(10, 10);
+        buffer.buffer_vote([1u8; 32], 0);
+        buffer.buffer_vote([1u8; 32], 1);
+        buffer.buffer_vote([1u8; 32], 2);
+
+        // The proposal for [1u8; 32] just arrived.
+        let mut aggregator = VoteAggregator::new(10, [1u8; 32], 1, validator_set(4));
+        let early_votes = buffer.take_votes(&[1u8; 32]);
+        let qc = drain_and_aggregate(&mut aggregator, early_votes).unwrap();
+        assert_eq!(qc.signers.len(), 3);
+    }
+
+    #[test]
+    fn exceeding_max_per_block_drops_the_oldest_vote_for_that_block() {
+        let mut buffer = EarlyMessageBuffer::new(2, 10);
+        buffer.buffer_vote([1u8; 32], 1);
+        buffer.buffer_vote([1u8; 32], 2);
+        buffer.buffer_vote([1u8; 32], 3);
+        assert_eq!(buffer.take_votes(&[1u8; 32]), vec![2, 3]);
+    }
+
+    #[test]
+    fn exceeding_max_blocks_drops_the_oldest_tracked_block_entirely() {
+        let mut buffer = EarlyMessageBuffer::new(10, 2);
+        buffer.buffer_vote([1u8; 32], 1);
+        buffer.buffer_vote([2u8; 32], 1);
+        buffer.buffer_vote([3u8; 32], 1);
+        assert!(buffer.take_votes(&[1u8; 32]).is_empty());
+        assert_eq!(buffer.tracked_block_count(), 2);
+    }
+
+    #[test]
+    fn qcs_are_buffered_and_taken_independently_of_votes() {
+        let mut buffer = EarlyMessageBuffer::new(10, 10);
+        let qc = QuorumCertificate { block_hash: [1u8; 32], view: 1, signers: vec![0, 1, 2] };
+        buffer.buffer_qc([1u8; 32], qc.clone());
+        buffer.buffer_vote([1u8; 32], 9);
+        assert_eq!(buffer.take_qcs(&[1u8; 32]), vec![qc]);
+        assert_eq!(buffer.pending_vote_count(&[1u8; 32]), 1);
+    }
+}
@@ -0,0 +1,176 @@
+//! `StateMachine::execute_block` returns a `TxReceipt` per transaction
+//! (status, gas used, emitted events) instead of just a state root, so an
+//! application that needs receipts — for a JSON-RPC `getTransactionReceipt`
+//! call, an explorer, or event-driven off-chain indexing — doesn't have to
+//! re-derive them by replaying the block itself.
+
+use hotstuff2_types::{Block, Hash};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    Success,
+    Reverted,
+    /// Not run: the view's remaining time budget was exhausted before this
+    /// transaction's turn. Distinct from `Reverted`, which means execution
+    /// ran and the application logic rejected it.
+    Aborted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxReceipt {
+    pub tx_id: Hash,
+    pub status: ExecutionStatus,
+    pub gas_used: u64,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockExecutionResult {
+    pub state_root: Hash,
+    pub receipts: Vec<TxReceipt>,
+}
+
+pub trait StateMachine {
+    /// Applies every transaction in `block` and returns the resulting state
+    /// root together with one receipt per transaction, in block order.
+    fn execute_block(&mut self, block: &Block) -> BlockExecutionResult;
+
+    /// Same as `execute_block`, but told how many milliseconds remain in the
+    /// current view so a slow block (e.g. one containing a long-running
+    /// contract call) can abort early instead of running past the view
+    /// deadline and forcing a timeout. `remaining_view_ms` is caller-supplied
+    /// rather than read from a clock internally, matching the convention
+    /// used for timestamps elsewhere in this crate (see `failover`,
+    /// `byzantine_detector`). Machines that don't need the budget can ignore
+    /// it; the default just forwards to `execute_block`.
+    fn execute_block_with_deadline(&mut self, block: &Block, remaining_view_ms: u64) -> BlockExecutionResult {
+        let _ = remaining_view_ms;
+        self.execute_block(block)
+    }
+}
+
+/// Stores a block's receipts alongside it (e.g. next to the block store) so
+/// the query API can serve them without re-executing.
+#[derive(Default)]
+pub struct ReceiptStore {
+    receipts: std::collections::HashMap<Hash, TxReceipt>,
+    state_roots: std::collections::HashMap<Hash, Hash>,
+}
+
+impl ReceiptStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, block_hash: Hash, result: &BlockExecutionResult) {
+        self.state_roots.insert(block_hash, result.state_root);
+        for receipt in &result.receipts {
+            self.receipts.insert(receipt.tx_id, receipt.clone());
+        }
+    }
+
+    pub fn receipt_for(&self, tx_id: Hash) -> Option<&TxReceipt> {
+        self.receipts.get(&tx_id)
+    }
+
+    pub fn is_committed(&self, tx_id: Hash) -> bool {
+        self.receipts.contains_key(&tx_id)
+    }
+
+    pub fn committed_tx_ids(&self) -> impl Iterator<Item = Hash> + '_ {
+        self.receipts.keys().copied()
+    }
+
+    pub fn state_root_for(&self, block_hash: Hash) -> Option<Hash> {
+        self.state_roots.get(&block_hash).copied()
+    }
+}
+
+/// Runs `machine` over `block` and records the resulting receipts, giving
+/// the caller a single call site to wire execution into the commit path.
+pub fn execute_and_record(machine: &mut impl StateMachine, store: &mut ReceiptStore, block: &Block) -> BlockExecutionResult {
+    let result = machine.execute_block(block);
+    store.record(block.hash(), &result);
+    result
+}
+
+/// Same as `execute_and_record`, but propagates the current view's
+/// remaining time budget into execution via `execute_block_with_deadline`.
+pub fn execute_and_record_with_deadline(
+    machine: &mut impl StateMachine,
+    store: &mut ReceiptStore,
+    block: &Block,
+    remaining_view_ms: u64,
+) -> BlockExecutionResult {
+    let result = machine.execute_block_with_deadline(block, remaining_view_ms);
+    store.record(block.hash(), &result);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::Transaction;
+
+    struct CountingMachine;
+
+    impl StateMachine for CountingMachine {
+        fn execute_block(&mut self, block: &Block) -> BlockExecutionResult {
+            let receipts = block
+                .transactions
+                .iter()
+                .map(|tx| TxReceipt {
+                    tx_id: tx.id,
+                    status: ExecutionStatus::Success,
+                    gas_used: tx.payload.len() as u64,
+                    events: vec![format!("processed {}", tx.id[0])],
+                })
+                .collect();
+            BlockExecutionResult { state_root: block.hash(), receipts }
+        }
+    }
+
+    fn tx(id: u8) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction { id: hash, payload: vec![0u8; 3], weight: 1, valid_until: None }
+    }
+
+    #[test]
+    fn execute_block_returns_one_receipt_per_transaction() {
+        let mut machine = CountingMachine;
+        let block = Block { parent_hash: [0u8; 32], height: 1, view: 1, transactions: vec![tx(1), tx(2)] };
+        let result = machine.execute_block(&block);
+        assert_eq!(result.receipts.len(), 2);
+        assert_eq!(result.receipts[0].gas_used, 3);
+    }
+
+    #[test]
+    fn execute_and_record_makes_receipts_queryable_by_tx_id() {
+        let mut machine = CountingMachine;
+        let mut store = ReceiptStore::new();
+        let block = Block { parent_hash: [0u8; 32], height: 1, view: 1, transactions: vec![tx(1)] };
+
+        let result = execute_and_record(&mut machine, &mut store, &block);
+        let receipt = store.receipt_for(tx(1).id).unwrap();
+        assert_eq!(receipt.status, ExecutionStatus::Success);
+        assert_eq!(receipt.events, vec!["processed 1".to_string()]);
+        assert_eq!(store.state_root_for(block.hash()), Some(result.state_root));
+    }
+
+    #[test]
+    fn unknown_tx_has_no_receipt() {
+        let store = ReceiptStore::new();
+        assert!(store.receipt_for([1u8; 32]).is_none());
+    }
+
+    #[test]
+    fn a_state_machine_that_ignores_the_deadline_still_executes_normally() {
+        let mut machine = CountingMachine;
+        let mut store = ReceiptStore::new();
+        let block = Block { parent_hash: [0u8; 32], height: 1, view: 1, transactions: vec![tx(1)] };
+        let result = execute_and_record_with_deadline(&mut machine, &mut store, &block, 5);
+        assert_eq!(result.receipts.len(), 1);
+        assert_eq!(store.state_root_for(block.hash()), Some(result.state_root));
+    }
+}
@@ -0,0 +1,145 @@
+//! `ParticipationGate` (see `participation`) lets a standby run everything
+//! an active replica does except vote, but something still has to decide
+//! *when* a standby should stop deferring and actually take over — and
+//! guarantee it never does so alongside a still-live active, which would
+//! be exactly the equivocation `FileDoubleSignGuard` exists to prevent.
+//! `StandbyFailover` tracks heartbeats from the paired active validator and
+//! decides when its silence window has elapsed; `attempt_takeover` is the
+//! only thing that actually flips a standby live, and it does so by
+//! acquiring the *same* signing directory's lock, so a false-positive
+//! failure detection (active is alive but briefly slow) still can't produce
+//! two signers: the active's still-held lock makes the standby's
+//! `FileDoubleSignGuard::acquire` fail with `GuardError::AlreadyLocked`
+//! before it ever calls `promote_to_active`.
+
+use std::path::Path;
+
+use hotstuff2_crypto::{FileDoubleSignGuard, GuardError};
+
+use crate::participation::ParticipationGate;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TakeoverError {
+    /// The silence window hasn't elapsed yet; still deferring to the active.
+    ActiveStillWithinSilenceWindow,
+    /// Fencing rejected the takeover: some signer (presumably the active,
+    /// still alive) already holds the shared signing directory's lock.
+    Fenced(GuardError),
+}
+
+/// Watches a paired active validator's heartbeats and decides when a
+/// standby should take over signing.
+pub struct StandbyFailover {
+    silence_window_ms: u64,
+    last_heartbeat_at_ms: Option<u64>,
+}
+
+impl StandbyFailover {
+    pub fn new(silence_window_ms: u64) -> Self {
+        Self { silence_window_ms, last_heartbeat_at_ms: None }
+    }
+
+    /// Records a heartbeat observed from the paired active validator at
+    /// `now_ms`.
+    pub fn on_active_heartbeat(&mut self, now_ms: u64) {
+        self.last_heartbeat_at_ms = Some(now_ms);
+    }
+
+    /// `true` if the active has never been heard from, or hasn't been heard
+    /// from for at least `silence_window_ms` as of `now_ms`.
+    pub fn active_is_silent(&self, now_ms: u64) -> bool {
+        match self.last_heartbeat_at_ms {
+            None => true,
+            Some(last) => now_ms.saturating_sub(last) >= self.silence_window_ms,
+        }
+    }
+
+    /// Attempts to take over signing at `now_ms` by acquiring the fencing
+    /// lock on `signing_dir` (the same directory the active validator's own
+    /// `FileDoubleSignGuard` holds) and promoting `gate` to active. Fails
+    /// closed: refuses if the silence window hasn't elapsed, and refuses if
+    /// fencing can't confirm the active is actually gone, leaving `gate`
+    /// untouched in both cases.
+    pub fn attempt_takeover(
+        &self,
+        now_ms: u64,
+        signing_dir: &Path,
+        gate: &mut ParticipationGate,
+    ) -> Result<FileDoubleSignGuard, TakeoverError> {
+        if !self.active_is_silent(now_ms) {
+            return Err(TakeoverError::ActiveStillWithinSilenceWindow);
+        }
+        let guard = FileDoubleSignGuard::acquire(signing_dir).map_err(TakeoverError::Fenced)?;
+        gate.promote_to_active();
+        Ok(guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::participation::ParticipationMode;
+    use std::fs;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("hotstuff2_standby_failover_{name}_{unique}"))
+    }
+
+    #[test]
+    fn an_active_that_has_never_been_heard_from_is_silent() {
+        let failover = StandbyFailover::new(5_000);
+        assert!(failover.active_is_silent(0));
+    }
+
+    #[test]
+    fn a_recent_heartbeat_is_not_silent() {
+        let mut failover = StandbyFailover::new(5_000);
+        failover.on_active_heartbeat(1_000);
+        assert!(!failover.active_is_silent(3_000));
+    }
+
+    #[test]
+    fn silence_is_detected_once_the_window_elapses() {
+        let mut failover = StandbyFailover::new(5_000);
+        failover.on_active_heartbeat(1_000);
+        assert!(failover.active_is_silent(6_000));
+    }
+
+    #[test]
+    fn takeover_is_refused_within_the_silence_window() {
+        let dir = temp_dir("within_window");
+        let mut failover = StandbyFailover::new(5_000);
+        failover.on_active_heartbeat(1_000);
+        let mut gate = ParticipationGate::new(ParticipationMode::Passive);
+        let result = failover.attempt_takeover(2_000, &dir, &mut gate);
+        assert_eq!(result.err(), Some(TakeoverError::ActiveStillWithinSilenceWindow));
+        assert!(!gate.should_vote());
+    }
+
+    #[test]
+    fn takeover_promotes_the_standby_once_the_window_elapses() {
+        let dir = temp_dir("promotes");
+        let failover = StandbyFailover::new(5_000);
+        let mut gate = ParticipationGate::new(ParticipationMode::Passive);
+        let guard = failover.attempt_takeover(10_000, &dir, &mut gate).unwrap();
+        assert!(gate.should_vote());
+        drop(guard);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn a_still_locked_directory_fences_out_a_takeover_even_past_the_silence_window() {
+        let dir = temp_dir("fenced");
+        let _active_guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+        let failover = StandbyFailover::new(5_000);
+        let mut gate = ParticipationGate::new(ParticipationMode::Passive);
+        let result = failover.attempt_takeover(10_000, &dir, &mut gate);
+        assert_eq!(result.err(), Some(TakeoverError::Fenced(GuardError::AlreadyLocked)));
+        assert!(!gate.should_vote());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
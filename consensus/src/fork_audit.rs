@@ -0,0 +1,185 @@
+//! Forensics after a suspected safety violation: given two views of the
+//! same chain (two block stores, or a local store and a peer's sync
+//! responses, both exposed through `ChainStore`), walk them height by
+//! height, verify every QC along the way, and report the first height at
+//! which the committed block hashes actually diverge along with the
+//! signer sets responsible for each side — the validators who can be held
+//! accountable for having certified conflicting blocks.
+
+use hotstuff2_types::{Block, Hash, QuorumCertificate, ValidatorId, ValidatorSet};
+
+/// A read-only view of one chain's committed (block, certifying QC) pairs.
+/// The two stores under audit need not be the same concrete type — one is
+/// commonly a local `storage` crate view, the other a peer's replayed sync
+/// responses.
+pub trait ChainStore {
+    fn block_at(&self, height: u64) -> Option<(Block, QuorumCertificate)>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ForkAuditError {
+    /// One side's QC at `height` doesn't meet quorum for `validators`, so
+    /// its signers can't be trusted as forensic evidence.
+    InvalidQc { height: u64, side: Side },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub height: u64,
+    pub block_hash_a: Hash,
+    pub block_hash_b: Hash,
+    pub signers_a: Vec<ValidatorId>,
+    pub signers_b: Vec<ValidatorId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkAuditReport {
+    /// The last height both stores had a block for and that was checked.
+    pub checked_up_to: u64,
+    /// `None` means the chains agreed everywhere both stores had a block.
+    pub divergence: Option<Divergence>,
+}
+
+/// Walks `[from_height, to_height]`, verifying both sides' QCs and
+/// comparing block hashes, stopping early either at the first divergence
+/// or once either store runs out of blocks.
+pub fn audit_fork(
+    a: &dyn ChainStore,
+    b: &dyn ChainStore,
+    validators: &ValidatorSet,
+    from_height: u64,
+    to_height: u64,
+) -> Result<ForkAuditReport, ForkAuditError> {
+    let mut checked_up_to = from_height.saturating_sub(1);
+    for height in from_height..=to_height {
+        let (block_a, qc_a) = match a.block_at(height) {
+            Some(entry) => entry,
+            None => break,
+        };
+        let (block_b, qc_b) = match b.block_at(height) {
+            Some(entry) => entry,
+            None => break,
+        };
+
+        if !validators.verify_threshold(&qc_a) {
+            return Err(ForkAuditError::InvalidQc { height, side: Side::A });
+        }
+        if !validators.verify_threshold(&qc_b) {
+            return Err(ForkAuditError::InvalidQc { height, side: Side::B });
+        }
+
+        checked_up_to = height;
+        let hash_a = block_a.hash();
+        let hash_b = block_b.hash();
+        if hash_a != hash_b {
+            return Ok(ForkAuditReport {
+                checked_up_to,
+                divergence: Some(Divergence {
+                    height,
+                    block_hash_a: hash_a,
+                    block_hash_b: hash_b,
+                    signers_a: qc_a.signers,
+                    signers_b: qc_b.signers,
+                }),
+            });
+        }
+    }
+    Ok(ForkAuditReport { checked_up_to, divergence: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MapStore(HashMap<u64, (Block, QuorumCertificate)>);
+    impl ChainStore for MapStore {
+        fn block_at(&self, height: u64) -> Option<(Block, QuorumCertificate)> {
+            self.0.get(&height).cloned()
+        }
+    }
+
+    fn block(height: u64, parent_hash: Hash) -> Block {
+        Block { parent_hash, height, view: height, transactions: vec![] }
+    }
+
+    fn qc_for(block: &Block, signers: Vec<ValidatorId>) -> QuorumCertificate {
+        QuorumCertificate { block_hash: block.hash(), view: block.view, signers }
+    }
+
+    fn validators() -> ValidatorSet {
+        ValidatorSet { validators: vec![1, 2, 3, 4] }
+    }
+
+    #[test]
+    fn identical_chains_report_no_divergence() {
+        let b1 = block(1, [0u8; 32]);
+        let qc1 = qc_for(&b1, vec![1, 2, 3]);
+        let mut map = HashMap::new();
+        map.insert(1, (b1, qc1));
+        let a = MapStore(map.clone());
+        let b = MapStore(map);
+
+        let report = audit_fork(&a, &b, &validators(), 1, 1).unwrap();
+        assert_eq!(report.divergence, None);
+        assert_eq!(report.checked_up_to, 1);
+    }
+
+    #[test]
+    fn a_diverging_block_at_a_height_is_reported_with_both_signer_sets() {
+        let common_parent = [7u8; 32];
+        let block_a = block(2, common_parent);
+        let mut block_b = block(2, common_parent);
+        block_b.transactions = vec![hotstuff2_types::Transaction { id: [9u8; 32], payload: vec![1], weight: 1, valid_until: None }];
+
+        let qc_a = qc_for(&block_a, vec![1, 2, 3]);
+        let qc_b = qc_for(&block_b, vec![1, 2, 4]);
+
+        let mut map_a = HashMap::new();
+        map_a.insert(2, (block_a.clone(), qc_a));
+        let mut map_b = HashMap::new();
+        map_b.insert(2, (block_b.clone(), qc_b));
+
+        let report = audit_fork(&MapStore(map_a), &MapStore(map_b), &validators(), 2, 2).unwrap();
+        let divergence = report.divergence.unwrap();
+        assert_eq!(divergence.height, 2);
+        assert_eq!(divergence.block_hash_a, block_a.hash());
+        assert_eq!(divergence.block_hash_b, block_b.hash());
+        assert_eq!(divergence.signers_a, vec![1, 2, 3]);
+        assert_eq!(divergence.signers_b, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn a_qc_that_does_not_meet_quorum_is_rejected_before_comparing_hashes() {
+        let b1 = block(1, [0u8; 32]);
+        let weak_qc = qc_for(&b1, vec![1]); // below quorum for 4 validators
+        let mut map = HashMap::new();
+        map.insert(1, (b1.clone(), weak_qc));
+        let a = MapStore(map);
+        let mut map_b = HashMap::new();
+        map_b.insert(1, (b1.clone(), qc_for(&b1, vec![1, 2, 3])));
+        let b = MapStore(map_b);
+
+        assert_eq!(audit_fork(&a, &b, &validators(), 1, 1), Err(ForkAuditError::InvalidQc { height: 1, side: Side::A }));
+    }
+
+    #[test]
+    fn auditing_stops_once_either_store_runs_out_of_blocks() {
+        let b1 = block(1, [0u8; 32]);
+        let qc1 = qc_for(&b1, vec![1, 2, 3]);
+        let mut map = HashMap::new();
+        map.insert(1, (b1, qc1));
+        let a = MapStore(map);
+        let b = MapStore(HashMap::new());
+
+        let report = audit_fork(&a, &b, &validators(), 1, 5).unwrap();
+        assert_eq!(report.checked_up_to, 0);
+        assert_eq!(report.divergence, None);
+    }
+}
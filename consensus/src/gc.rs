@@ -0,0 +1,95 @@
+//! Garbage-collects per-view/per-height consensus state (votes, timeouts,
+//! fast-commit records, pipeline entries) that would otherwise grow
+//! unbounded for the lifetime of a long-running node. `RetentionTracker`
+//! doesn't own the maps themselves — each is a different concrete type
+//! (votes keyed by view, fast-commits by height, etc.) — so it only decides
+//! which keys are old enough to drop; the caller removes them from its own
+//! map and reports the reclaimed count.
+
+/// How far behind the committed height/view a key must fall before it's
+/// eligible for collection. Keys within the window are kept even after
+/// their view/height is superseded, in case a lagging peer still needs the
+/// state to catch up (e.g. `NetworkFaultDetector`/`CatchUpCoordinator`
+/// replay).
+pub struct RetentionTracker {
+    retention_window: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    pub reclaimed: u64,
+}
+
+impl RetentionTracker {
+    pub fn new(retention_window: u64) -> Self {
+        Self { retention_window }
+    }
+
+    /// The oldest key still worth keeping, given `committed_height` as the
+    /// GC clock. Keys strictly below this are eligible for collection.
+    pub fn cutoff(&self, committed_height: u64) -> u64 {
+        committed_height.saturating_sub(self.retention_window)
+    }
+
+    pub fn is_collectible(&self, key: u64, committed_height: u64) -> bool {
+        key < self.cutoff(committed_height)
+    }
+
+    /// Runs one GC pass over `keys` (e.g. `votes.keys()`), calling
+    /// `remove` for every key below the retention cutoff and returning how
+    /// many were reclaimed.
+    pub fn collect<K: Copy + Into<u64>>(&self, keys: &[K], committed_height: u64, mut remove: impl FnMut(K)) -> GcStats {
+        let cutoff = self.cutoff(committed_height);
+        let mut reclaimed = 0u64;
+        for &key in keys {
+            if key.into() < cutoff {
+                remove(key);
+                reclaimed += 1;
+            }
+        }
+        GcStats { reclaimed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn keys_within_the_retention_window_are_kept() {
+        let tracker = RetentionTracker::new(10);
+        assert!(!tracker.is_collectible(95, 100));
+    }
+
+    #[test]
+    fn keys_older_than_the_window_are_collectible() {
+        let tracker = RetentionTracker::new(10);
+        assert!(tracker.is_collectible(80, 100));
+    }
+
+    #[test]
+    fn collect_removes_only_stale_entries_and_reports_the_reclaimed_count() {
+        let tracker = RetentionTracker::new(5);
+        let mut votes: HashMap<u64, &str> = HashMap::new();
+        votes.insert(10, "a");
+        votes.insert(50, "b");
+        votes.insert(96, "c");
+
+        let keys: Vec<u64> = votes.keys().copied().collect();
+        let stats = tracker.collect(&keys, 100, |key| {
+            votes.remove(&key);
+        });
+
+        assert_eq!(stats.reclaimed, 2);
+        assert_eq!(votes.len(), 1);
+        assert!(votes.contains_key(&96));
+    }
+
+    #[test]
+    fn a_committed_height_below_the_window_collects_nothing() {
+        let tracker = RetentionTracker::new(10);
+        assert_eq!(tracker.cutoff(3), 0);
+        assert!(!tracker.is_collectible(0, 3));
+    }
+}
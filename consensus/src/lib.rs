@@ -0,0 +1,44 @@
+pub mod actor;
+pub mod byzantine_detector;
+pub mod commit_certificate;
+pub mod early_message_buffer;
+pub mod execution;
+pub mod failover;
+pub mod fork_audit;
+pub mod gc;
+pub mod participation;
+pub mod prefetch;
+pub mod protocol_upgrade;
+pub mod qc_verification_cache;
+pub mod safety;
+pub mod state;
+pub mod validation;
+pub mod view_timeline;
+pub mod vote_aggregation;
+
+pub use actor::{ActorCommand, ConsensusActor, ConsensusActorHandle, ConsensusSnapshot, SwappableSnapshot};
+pub use byzantine_detector::{ByzantineDetector, Evidence};
+pub use commit_certificate::{
+    build_commit_certificate, decode as decode_commit_certificate, encode as encode_commit_certificate, validator_set_hash,
+    verify_commit_certificate, BlockHeader, CommitCertificate, CommitCertificateError,
+};
+pub use early_message_buffer::EarlyMessageBuffer;
+pub use execution::{
+    execute_and_record, execute_and_record_with_deadline, BlockExecutionResult, ExecutionStatus, ReceiptStore, StateMachine,
+    TxReceipt,
+};
+pub use failover::{StandbyFailover, TakeoverError};
+pub use fork_audit::{audit_fork, ChainStore, Divergence, ForkAuditError, ForkAuditReport, Side};
+pub use gc::{GcStats, RetentionTracker};
+pub use participation::{ParticipationGate, ParticipationMode};
+pub use prefetch::{handle_proposal_with_prefetch, ParentBlockRequest, ProposalOutcome, ProposalPrefetchQueue};
+pub use protocol_upgrade::{ActivationStatus, UpgradeCoordinator};
+pub use qc_verification_cache::{CacheMetrics as QcVerificationCacheMetrics, VerifiedQcCache};
+pub use safety::{commit_rule, SafetyEngine, VoteRejection};
+pub use state::{verify_state_hash, KVStateMachine, SafetyViolation};
+pub use validation::{
+    AlreadyCommittedTxValidator, BlockValidator, DuplicateTxValidator, ExpiredTxValidator, SizeLimitValidator,
+    StateMachineValidator, TxCountValidator, ValidationError, ValidationPipeline,
+};
+pub use view_timeline::{TimelineEntry, ViewEvent, ViewTimelineRecorder};
+pub use vote_aggregation::{drain_and_aggregate, VoteAggregator};
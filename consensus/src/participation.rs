@@ -0,0 +1,89 @@
+//! Every validator key holder was assumed to vote: there was no way to run
+//! a fully-provisioned node — networked, collecting RTTs and observing
+//! commits like any other replica — that deliberately abstains from
+//! casting votes. That's exactly the shape of a hot standby: it should be
+//! indistinguishable from an active replica right up until the moment it's
+//! promoted, so promotion never requires redistributing keys or restarting
+//! the network stack. `ParticipationMode` is the switch and `ParticipationGate`
+//! is where a caller checks it before calling into `SafetyEngine::can_vote`.
+
+/// Whether a node holding a validator key actually casts votes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticipationMode {
+    /// Votes normally, same as every replica before this mode existed.
+    Active,
+    /// Runs networking and metrics collection like an active replica, but
+    /// abstains from voting even when `SafetyEngine` would allow it —
+    /// a hot standby that can be promoted to `Active` without touching keys.
+    Passive,
+}
+
+/// Gates whether this node should cast a vote it is otherwise entitled to
+/// cast. Doesn't replace `SafetyEngine`'s safety checks — a passive node
+/// still runs them so its internal state stays consistent with what it
+/// would need on promotion — it just adds one more reason to abstain.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParticipationGate {
+    mode: Option<ParticipationMode>,
+}
+
+impl ParticipationGate {
+    pub fn new(mode: ParticipationMode) -> Self {
+        Self { mode: Some(mode) }
+    }
+
+    pub fn mode(&self) -> ParticipationMode {
+        self.mode.unwrap_or(ParticipationMode::Active)
+    }
+
+    /// `true` unless this gate is in `Passive` mode. Callers still run
+    /// `SafetyEngine::can_vote` regardless of this result, so a passive
+    /// node's lock state advances exactly as it would if it were active.
+    pub fn should_vote(&self) -> bool {
+        self.mode() != ParticipationMode::Passive
+    }
+
+    /// Promotes a passive standby to active, e.g. once a paired active
+    /// validator is confirmed down. No-op if already active.
+    pub fn promote_to_active(&mut self) {
+        self.mode = Some(ParticipationMode::Active);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_active_so_existing_callers_are_unaffected() {
+        let gate = ParticipationGate::default();
+        assert_eq!(gate.mode(), ParticipationMode::Active);
+        assert!(gate.should_vote());
+    }
+
+    #[test]
+    fn a_passive_gate_never_votes() {
+        let gate = ParticipationGate::new(ParticipationMode::Passive);
+        assert!(!gate.should_vote());
+    }
+
+    #[test]
+    fn an_active_gate_always_votes() {
+        let gate = ParticipationGate::new(ParticipationMode::Active);
+        assert!(gate.should_vote());
+    }
+
+    #[test]
+    fn promoting_a_passive_gate_makes_it_vote() {
+        let mut gate = ParticipationGate::new(ParticipationMode::Passive);
+        gate.promote_to_active();
+        assert!(gate.should_vote());
+    }
+
+    #[test]
+    fn promoting_an_already_active_gate_is_a_no_op() {
+        let mut gate = ParticipationGate::new(ParticipationMode::Active);
+        gate.promote_to_active();
+        assert_eq!(gate.mode(), ParticipationMode::Active);
+    }
+}
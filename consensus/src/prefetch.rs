@@ -0,0 +1,142 @@
+//! `handle_proposal` used to just drop a proposal whose parent the node
+//! didn't have yet and wait for the next view-change timeout to eventually
+//! catch it up — needlessly slow when the parent is one block request away.
+//! `handle_proposal_with_prefetch` instead checks for the parent first: if
+//! it's missing, the proposal is queued in a `ProposalPrefetchQueue` and a
+//! targeted `ParentBlockRequest` is returned for the caller to send (to the
+//! proposer, or any peer) instead of running validation on a block that
+//! can't yet be applied. Once the parent arrives, `on_parent_arrived` hands
+//! back every proposal that was waiting on it for immediate re-processing.
+//!
+//! As with `catch_up::CatchUpCoordinator`, the actual wire request/response
+//! belongs to whatever `NetworkMsg` enum the transport uses; this only
+//! decides when to ask and what to do once the answer arrives.
+
+use std::collections::HashMap;
+
+use hotstuff2_types::{Block, Hash, ValidatorId};
+
+use crate::validation::{handle_proposal, ValidationError, ValidationPipeline};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParentBlockRequest {
+    pub parent_hash: Hash,
+    pub target: ValidatorId,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProposalOutcome {
+    Accepted,
+    Rejected(ValidationError),
+    /// The proposal's parent isn't available locally yet; it has been
+    /// queued and `ParentBlockRequest` should be sent to fetch it.
+    MissingParent(ParentBlockRequest),
+}
+
+/// Buffers proposals whose parent hasn't arrived yet, keyed by the missing
+/// parent's hash, until that parent shows up.
+#[derive(Default)]
+pub struct ProposalPrefetchQueue {
+    waiting: HashMap<Hash, Vec<Block>>,
+}
+
+impl ProposalPrefetchQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn queue(&mut self, block: Block, proposer: ValidatorId) -> ParentBlockRequest {
+        let parent_hash = block.parent_hash;
+        self.waiting.entry(parent_hash).or_default().push(block);
+        ParentBlockRequest { parent_hash, target: proposer }
+    }
+
+    /// Returns every proposal that was queued waiting on `parent_hash`, in
+    /// the order they arrived, ready to be re-processed now that the
+    /// parent is available. Returns an empty `Vec` if nothing was waiting.
+    pub fn on_parent_arrived(&mut self, parent_hash: Hash) -> Vec<Block> {
+        self.waiting.remove(&parent_hash).unwrap_or_default()
+    }
+
+    pub fn is_waiting_on(&self, parent_hash: Hash) -> bool {
+        self.waiting.contains_key(&parent_hash)
+    }
+}
+
+/// Checks for `block`'s parent via `has_block` before running the
+/// validation pipeline. A missing parent queues the proposal and asks
+/// `proposer` for it instead of validating (and likely rejecting or
+/// misapplying) a block whose ancestry can't yet be verified.
+pub fn handle_proposal_with_prefetch(
+    pipeline: &ValidationPipeline,
+    block: Block,
+    proposer: ValidatorId,
+    has_block: &dyn Fn(Hash) -> bool,
+    queue: &mut ProposalPrefetchQueue,
+) -> ProposalOutcome {
+    if !has_block(block.parent_hash) {
+        return ProposalOutcome::MissingParent(queue.queue(block, proposer));
+    }
+    match handle_proposal(pipeline, &block) {
+        Ok(()) => ProposalOutcome::Accepted,
+        Err(error) => ProposalOutcome::Rejected(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(height: u64, parent_hash: Hash) -> Block {
+        Block { parent_hash, height, view: height, transactions: vec![] }
+    }
+
+    #[test]
+    fn a_proposal_with_a_known_parent_runs_normal_validation() {
+        let pipeline = ValidationPipeline::new();
+        let mut queue = ProposalPrefetchQueue::new();
+        let outcome = handle_proposal_with_prefetch(&pipeline, block(2, [1u8; 32]), 7, &|_| true, &mut queue);
+        assert_eq!(outcome, ProposalOutcome::Accepted);
+    }
+
+    #[test]
+    fn a_proposal_with_a_missing_parent_is_queued_and_requested() {
+        let pipeline = ValidationPipeline::new();
+        let mut queue = ProposalPrefetchQueue::new();
+        let missing_parent = [9u8; 32];
+        let outcome = handle_proposal_with_prefetch(&pipeline, block(5, missing_parent), 3, &|_| false, &mut queue);
+        assert_eq!(outcome, ProposalOutcome::MissingParent(ParentBlockRequest { parent_hash: missing_parent, target: 3 }));
+        assert!(queue.is_waiting_on(missing_parent));
+    }
+
+    #[test]
+    fn once_the_parent_arrives_every_queued_proposal_is_returned_for_reprocessing() {
+        let pipeline = ValidationPipeline::new();
+        let mut queue = ProposalPrefetchQueue::new();
+        let missing_parent = [9u8; 32];
+        handle_proposal_with_prefetch(&pipeline, block(5, missing_parent), 3, &|_| false, &mut queue);
+        handle_proposal_with_prefetch(&pipeline, block(6, missing_parent), 4, &|_| false, &mut queue);
+
+        let ready = queue.on_parent_arrived(missing_parent);
+        assert_eq!(ready.len(), 2);
+        assert_eq!(ready[0].height, 5);
+        assert_eq!(ready[1].height, 6);
+        assert!(!queue.is_waiting_on(missing_parent));
+    }
+
+    #[test]
+    fn a_parent_arrival_with_nothing_queued_returns_empty() {
+        let mut queue = ProposalPrefetchQueue::new();
+        assert!(queue.on_parent_arrived([1u8; 32]).is_empty());
+    }
+
+    #[test]
+    fn a_known_parent_proposal_still_gets_rejected_by_the_validation_pipeline() {
+        let pipeline = ValidationPipeline::new().with_validator(crate::validation::TxCountValidator { max_transactions: 0 });
+        let mut queue = ProposalPrefetchQueue::new();
+        let mut b = block(2, [1u8; 32]);
+        b.transactions.push(hotstuff2_types::Transaction { id: [1u8; 32], payload: vec![], weight: 1, valid_until: None });
+        let outcome = handle_proposal_with_prefetch(&pipeline, b, 7, &|_| true, &mut queue);
+        assert!(matches!(outcome, ProposalOutcome::Rejected(ValidationError::TooManyTransactions { .. })));
+    }
+}
@@ -0,0 +1,205 @@
+//! Rolling out a protocol change (a new wire format, a new commit-rule
+//! parameter) safely needs every validator on board before it flips on —
+//! otherwise a minority still running the old rule diverges from the rest.
+//! `UpgradeCoordinator` implements on-chain signaling: each validator
+//! signals readiness for a `feature_id` at a given height, and the feature
+//! activates once a supermajority (`ValidatorSet::quorum_size`) has signaled
+//! in each of `required_consecutive_epochs` consecutive epochs, at the next
+//! epoch boundary after the streak completes. `Block` has no header
+//! extension field in this tree to carry a signal natively — a signal is
+//! modeled here as a parallel `(validator, feature_id, height)` record that
+//! the caller derives from however it actually encodes the block header,
+//! mirroring how `fork_audit` takes already-extracted QCs rather than
+//! reaching into a wire format itself.
+
+use std::collections::{HashMap, HashSet};
+
+use hotstuff2_types::{ValidatorId, ValidatorSet};
+
+/// One epoch's worth of height, e.g. `epoch_length = 100` means heights
+/// `0..100` are epoch 0, `100..200` are epoch 1, and so on.
+fn epoch_of(height: u64, epoch_length: u64) -> u64 {
+    height / epoch_length
+}
+
+pub struct UpgradeCoordinator {
+    epoch_length: u64,
+    required_consecutive_epochs: u32,
+    /// `feature_id -> (epoch -> signers who signaled in that epoch)`.
+    signals: HashMap<u32, HashMap<u64, HashSet<ValidatorId>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationStatus {
+    /// Fewer than `required_consecutive_epochs` consecutive epochs (ending
+    /// at the `current_epoch` passed to `status`) have reached quorum yet.
+    Pending { consecutive_epochs_at_quorum: u32 },
+    /// The streak completed; the feature activates at `activation_height`,
+    /// the first height of the epoch after the one that completed it.
+    Activated { activation_height: u64 },
+}
+
+impl UpgradeCoordinator {
+    pub fn new(epoch_length: u64, required_consecutive_epochs: u32) -> Self {
+        assert!(epoch_length > 0, "epoch_length must be positive");
+        Self { epoch_length, required_consecutive_epochs, signals: HashMap::new() }
+    }
+
+    /// Records that `validator` signaled readiness for `feature_id` at
+    /// `height`.
+    pub fn record_signal(&mut self, feature_id: u32, validator: ValidatorId, height: u64) {
+        let epoch = epoch_of(height, self.epoch_length);
+        self.signals.entry(feature_id).or_default().entry(epoch).or_default().insert(validator);
+    }
+
+    /// The earliest epoch at which a run of `required_consecutive_epochs`
+    /// consecutive quorum-reaching epochs (starting from epoch 0) first
+    /// completes, scanning only up through the highest epoch any signal was
+    /// recorded for. Activation, once reached, is permanent: a later epoch
+    /// falling back below quorum does not un-activate the feature.
+    fn earliest_activation_epoch(&self, feature_id: u32, validator_set: &ValidatorSet) -> Option<u64> {
+        let quorum = validator_set.quorum_size();
+        let by_epoch = self.signals.get(&feature_id)?;
+        let max_epoch = *by_epoch.keys().max()?;
+
+        let mut streak = 0u32;
+        for epoch in 0..=max_epoch {
+            let signaled = by_epoch.get(&epoch).map(|signers| signers.len()).unwrap_or(0);
+            if signaled >= quorum {
+                streak += 1;
+                if streak >= self.required_consecutive_epochs {
+                    return Some(epoch);
+                }
+            } else {
+                streak = 0;
+            }
+        }
+        None
+    }
+
+    /// Evaluates whether `feature_id` has reached activation as of
+    /// `current_epoch`. Once a consecutive-quorum streak has completed at
+    /// or before `current_epoch`, reports `Activated` (permanently, from
+    /// the caller's perspective, as later calls with a larger
+    /// `current_epoch` keep seeing the same activation height). Otherwise
+    /// reports the length of whatever quorum streak is currently running,
+    /// counted backward from `current_epoch`.
+    pub fn status(&self, feature_id: u32, validator_set: &ValidatorSet, current_epoch: u64) -> ActivationStatus {
+        if let Some(completing_epoch) = self.earliest_activation_epoch(feature_id, validator_set) {
+            if completing_epoch <= current_epoch {
+                return ActivationStatus::Activated { activation_height: (completing_epoch + 1) * self.epoch_length };
+            }
+        }
+
+        let quorum = validator_set.quorum_size();
+        let empty = HashMap::new();
+        let by_epoch = self.signals.get(&feature_id).unwrap_or(&empty);
+
+        let mut streak = 0u32;
+        let mut epoch = current_epoch;
+        loop {
+            let signaled = by_epoch.get(&epoch).map(|signers| signers.len()).unwrap_or(0);
+            if signaled < quorum {
+                break;
+            }
+            streak += 1;
+            match epoch.checked_sub(1) {
+                Some(prev) => epoch = prev,
+                None => break,
+            }
+        }
+        ActivationStatus::Pending { consecutive_epochs_at_quorum: streak }
+    }
+
+    /// Convenience over `status`: `true` once `height` is at or past the
+    /// feature's activation height.
+    pub fn is_active_at(&self, feature_id: u32, validator_set: &ValidatorSet, height: u64) -> bool {
+        let epoch = epoch_of(height, self.epoch_length);
+        match self.status(feature_id, validator_set, epoch) {
+            ActivationStatus::Activated { activation_height } => height >= activation_height,
+            ActivationStatus::Pending { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(n: u64) -> ValidatorSet {
+        ValidatorSet { validators: (1..=n).collect() }
+    }
+
+    const FEATURE: u32 = 7;
+
+    #[test]
+    fn no_signals_at_all_is_pending_with_zero_streak() {
+        let coordinator = UpgradeCoordinator::new(10, 2);
+        let status = coordinator.status(FEATURE, &validators(4), 0);
+        assert_eq!(status, ActivationStatus::Pending { consecutive_epochs_at_quorum: 0 });
+    }
+
+    #[test]
+    fn a_single_epoch_at_quorum_is_pending_until_the_streak_completes() {
+        let mut coordinator = UpgradeCoordinator::new(10, 2);
+        // 4 validators, quorum = 3. Epoch 0 spans heights 0..10.
+        coordinator.record_signal(FEATURE, 1, 0);
+        coordinator.record_signal(FEATURE, 2, 1);
+        coordinator.record_signal(FEATURE, 3, 2);
+
+        let status = coordinator.status(FEATURE, &validators(4), 0);
+        assert_eq!(status, ActivationStatus::Pending { consecutive_epochs_at_quorum: 1 });
+    }
+
+    #[test]
+    fn n_consecutive_epochs_at_quorum_activates_at_the_next_epoch_boundary() {
+        let mut coordinator = UpgradeCoordinator::new(10, 2);
+        for (validator, height) in [(1, 0), (2, 1), (3, 2), (1, 10), (2, 11), (3, 12)] {
+            coordinator.record_signal(FEATURE, validator, height);
+        }
+
+        let status = coordinator.status(FEATURE, &validators(4), 1);
+        assert_eq!(status, ActivationStatus::Activated { activation_height: 20 });
+    }
+
+    #[test]
+    fn a_gap_epoch_below_quorum_resets_the_streak() {
+        let mut coordinator = UpgradeCoordinator::new(10, 2);
+        // Epoch 0 at quorum, epoch 1 below quorum, epoch 2 at quorum again:
+        // the streak ending at epoch 2 is only length 1.
+        coordinator.record_signal(FEATURE, 1, 0);
+        coordinator.record_signal(FEATURE, 2, 1);
+        coordinator.record_signal(FEATURE, 3, 2);
+        coordinator.record_signal(FEATURE, 1, 10);
+        coordinator.record_signal(FEATURE, 1, 20);
+        coordinator.record_signal(FEATURE, 2, 21);
+        coordinator.record_signal(FEATURE, 3, 22);
+
+        let status = coordinator.status(FEATURE, &validators(4), 2);
+        assert_eq!(status, ActivationStatus::Pending { consecutive_epochs_at_quorum: 1 });
+    }
+
+    #[test]
+    fn is_active_at_is_false_before_activation_and_true_from_the_activation_height_on() {
+        let mut coordinator = UpgradeCoordinator::new(10, 1);
+        coordinator.record_signal(FEATURE, 1, 0);
+        coordinator.record_signal(FEATURE, 2, 1);
+        coordinator.record_signal(FEATURE, 3, 2);
+
+        assert!(!coordinator.is_active_at(FEATURE, &validators(4), 5));
+        assert!(coordinator.is_active_at(FEATURE, &validators(4), 15));
+        assert!(coordinator.is_active_at(FEATURE, &validators(4), 25));
+    }
+
+    #[test]
+    fn different_features_track_independent_streaks() {
+        let mut coordinator = UpgradeCoordinator::new(10, 1);
+        coordinator.record_signal(FEATURE, 1, 0);
+        coordinator.record_signal(FEATURE, 2, 1);
+        coordinator.record_signal(FEATURE, 3, 2);
+
+        let other_feature = FEATURE + 1;
+        let status = coordinator.status(other_feature, &validators(4), 0);
+        assert_eq!(status, ActivationStatus::Pending { consecutive_epochs_at_quorum: 0 });
+    }
+}
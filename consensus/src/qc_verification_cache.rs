@@ -0,0 +1,162 @@
+//! `ValidatorSet::verify_threshold` recomputes a QC's threshold check every
+//! time it's asked about the same QC — once on proposal validation, again
+//! during sync, again at commit — even though the answer only depends on
+//! the QC's contents and can never change afterward. `VerifiedQcCache`
+//! memoizes that result behind a bounded LRU keyed by `QC::hash`, the same
+//! `hash -> LRU eviction -> hit/miss metrics` shape `storage::HotPathCache`
+//! already uses for blocks and QCs, but caching a verification *result*
+//! rather than the QC itself.
+
+use std::collections::{HashMap, VecDeque};
+
+use hotstuff2_types::{Hash, QuorumCertificate, ValidatorSet};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheMetrics {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Bounded LRU cache of `QC hash -> verification result`. `capacity` is a
+/// number of entries, not a byte budget — a verification result is one
+/// `bool`, so there's no per-entry size to estimate the way there is for a
+/// full `Block`/`QuorumCertificate`.
+pub struct VerifiedQcCache {
+    capacity: usize,
+    results: HashMap<Hash, bool>,
+    order: VecDeque<Hash>,
+    metrics: CacheMetrics,
+}
+
+impl VerifiedQcCache {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), results: HashMap::new(), order: VecDeque::new(), metrics: CacheMetrics::default() }
+    }
+
+    /// Returns whether `qc` passes `validator_set.verify_threshold`,
+    /// verifying at most once per unique QC hash: a cache hit skips
+    /// `verify_threshold` entirely, a miss verifies and stores the result.
+    pub fn verify(&mut self, qc: &QuorumCertificate, validator_set: &ValidatorSet) -> bool {
+        let key = qc.hash();
+        if let Some(&result) = self.results.get(&key) {
+            self.metrics.hits += 1;
+            if let Some(pos) = self.order.iter().position(|k| *k == key) {
+                self.order.remove(pos);
+                self.order.push_back(key);
+            }
+            return result;
+        }
+
+        self.metrics.misses += 1;
+        let result = validator_set.verify_threshold(qc);
+        self.insert(key, result);
+        result
+    }
+
+    fn insert(&mut self, key: Hash, result: bool) {
+        if !self.results.contains_key(&key) {
+            if self.order.len() >= self.capacity {
+                if let Some(evicted) = self.order.pop_front() {
+                    self.results.remove(&evicted);
+                }
+            }
+            self.order.push_back(key);
+        }
+        self.results.insert(key, result);
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(n: u64) -> ValidatorSet {
+        ValidatorSet { validators: (1..=n).collect() }
+    }
+
+    fn qc(view: u64, signers: Vec<u64>) -> QuorumCertificate {
+        QuorumCertificate { block_hash: [view as u8; 32], view, signers }
+    }
+
+    #[test]
+    fn the_first_verification_of_a_qc_is_a_miss_and_the_second_is_a_hit() {
+        let mut cache = VerifiedQcCache::new(8);
+        let set = validators(4);
+        let certificate = qc(1, vec![1, 2, 3]);
+
+        assert!(cache.verify(&certificate, &set));
+        assert!(cache.verify(&certificate, &set));
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(metrics.misses, 1);
+        assert_eq!(metrics.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn a_failing_verification_result_is_cached_too() {
+        let mut cache = VerifiedQcCache::new(8);
+        let set = validators(4);
+        let below_quorum = qc(1, vec![1]);
+
+        assert!(!cache.verify(&below_quorum, &set));
+        assert!(!cache.verify(&below_quorum, &set));
+        assert_eq!(cache.metrics().hits, 1);
+    }
+
+    #[test]
+    fn distinct_qcs_are_tracked_independently() {
+        let mut cache = VerifiedQcCache::new(8);
+        let set = validators(4);
+        cache.verify(&qc(1, vec![1, 2, 3]), &set);
+        cache.verify(&qc(2, vec![1, 2, 3]), &set);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.metrics().misses, 2);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_inserted_qc_once_full() {
+        let mut cache = VerifiedQcCache::new(1);
+        let set = validators(4);
+        let first = qc(1, vec![1, 2, 3]);
+        let second = qc(2, vec![1, 2, 3]);
+
+        cache.verify(&first, &set);
+        cache.verify(&second, &set);
+
+        // `first` was evicted, so re-verifying it is a miss again.
+        let misses_before = cache.metrics().misses;
+        cache.verify(&first, &set);
+        assert_eq!(cache.metrics().misses, misses_before + 1);
+    }
+
+    #[test]
+    fn a_fresh_cache_starts_empty_with_zero_metrics() {
+        let cache = VerifiedQcCache::new(4);
+        assert!(cache.is_empty());
+        assert_eq!(cache.metrics(), CacheMetrics::default());
+    }
+}
@@ -0,0 +1,172 @@
+//! Voting and commit safety were previously implicit in whatever called
+//! `handle_proposal`: nothing here enforced "don't vote for a proposal that
+//! conflicts with what you're locked on" or "commit only after a real
+//! 3-chain", so a caller could accidentally violate HotStuff's safety
+//! invariants just by wiring things up wrong. `SafetyEngine` makes both
+//! rules explicit, pure functions with no I/O and no async runtime
+//! dependency, so they're unit-testable in isolation from the rest of the
+//! protocol: `can_vote` decides whether a proposal is safe to vote for
+//! given the current lock, `update_lock` advances the lock, and
+//! `commit_rule` checks a QC chain for the direct 3-chain that finalizes
+//! its earliest block.
+
+use hotstuff2_types::{Block, Hash, QuorumCertificate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteRejection {
+    /// The proposal doesn't extend the block its own justify QC certifies.
+    ParentDoesNotMatchJustifyQc,
+    /// The justify QC is for a view no later than the locked QC's view, and
+    /// doesn't extend the locked block either — voting would risk
+    /// contradicting a block this node already helped commit.
+    ConflictsWithLock { locked_view: u64 },
+}
+
+/// Tracks the highest QC this node is locked on. `None` means the node
+/// hasn't locked on anything yet (e.g. freshly started), so every
+/// proposal's justify QC is accepted.
+#[derive(Default)]
+pub struct SafetyEngine {
+    locked_qc: Option<QuorumCertificate>,
+}
+
+impl SafetyEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn locked_qc(&self) -> Option<&QuorumCertificate> {
+        self.locked_qc.as_ref()
+    }
+
+    /// Safe to vote for `proposal` justified by `justify_qc` if the
+    /// proposal actually extends the block `justify_qc` certifies, and
+    /// `justify_qc` either extends the locked block or has a strictly
+    /// higher view than it (the standard HotStuff liveness/safety rule:
+    /// locking never permanently prevents progress, since a higher-view QC
+    /// always unlocks it).
+    pub fn can_vote(&self, proposal: &Block, justify_qc: &QuorumCertificate) -> Result<(), VoteRejection> {
+        if proposal.parent_hash != justify_qc.block_hash {
+            return Err(VoteRejection::ParentDoesNotMatchJustifyQc);
+        }
+        if let Some(locked) = &self.locked_qc {
+            let extends_lock = justify_qc.block_hash == locked.block_hash;
+            let higher_view = justify_qc.view > locked.view;
+            if !extends_lock && !higher_view {
+                return Err(VoteRejection::ConflictsWithLock { locked_view: locked.view });
+            }
+        }
+        Ok(())
+    }
+
+    /// Advances the lock to `qc` if it has a strictly higher view than the
+    /// current lock (or there is no current lock); a QC at or below the
+    /// current lock's view can never move the lock backwards or sideways.
+    pub fn update_lock(&mut self, qc: QuorumCertificate) {
+        let should_update = match &self.locked_qc {
+            Some(locked) => qc.view > locked.view,
+            None => true,
+        };
+        if should_update {
+            self.locked_qc = Some(qc);
+        }
+    }
+}
+
+/// A block is committed once it, its child, and its grandchild are
+/// certified by QCs at three strictly consecutive views (the "direct
+/// 3-chain"): that shape is only reachable if every honest replica that
+/// helped form the middle QC saw the first QC and voted for the block that
+/// extends it, ruling out a conflicting branch ever gathering its own
+/// quorum. `qc_chain` must be in chain order, earliest first (e.g.
+/// `[qc_for_grandparent, qc_for_parent, qc_for_child]`). Returns the
+/// committed block's hash on a direct 3-chain, `None` otherwise.
+pub fn commit_rule(qc_chain: &[QuorumCertificate]) -> Option<Hash> {
+    for window in qc_chain.windows(3) {
+        let [a, b, c] = window else { unreachable!("windows(3) always yields 3 elements") };
+        if b.view == a.view + 1 && c.view == b.view + 1 {
+            return Some(a.block_hash);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qc(block_hash: Hash, view: u64) -> QuorumCertificate {
+        QuorumCertificate { block_hash, view, signers: vec![1, 2, 3] }
+    }
+
+    fn block(parent_hash: Hash, view: u64) -> Block {
+        Block { parent_hash, height: view, view, transactions: vec![] }
+    }
+
+    #[test]
+    fn an_unlocked_engine_accepts_any_proposal_that_extends_its_justify_qc() {
+        let engine = SafetyEngine::new();
+        let justify = qc([1u8; 32], 4);
+        assert!(engine.can_vote(&block([1u8; 32], 5), &justify).is_ok());
+    }
+
+    #[test]
+    fn a_proposal_that_does_not_extend_its_own_justify_qc_is_rejected() {
+        let engine = SafetyEngine::new();
+        let justify = qc([1u8; 32], 4);
+        let err = engine.can_vote(&block([9u8; 32], 5), &justify).unwrap_err();
+        assert_eq!(err, VoteRejection::ParentDoesNotMatchJustifyQc);
+    }
+
+    #[test]
+    fn a_lower_view_qc_that_conflicts_with_the_lock_is_rejected() {
+        let mut engine = SafetyEngine::new();
+        engine.update_lock(qc([1u8; 32], 10));
+        let conflicting_justify = qc([2u8; 32], 8); // different block, lower view
+        let err = engine.can_vote(&block([2u8; 32], 9), &conflicting_justify).unwrap_err();
+        assert_eq!(err, VoteRejection::ConflictsWithLock { locked_view: 10 });
+    }
+
+    #[test]
+    fn a_higher_view_qc_unlocks_even_if_it_conflicts_with_the_locked_block() {
+        let mut engine = SafetyEngine::new();
+        engine.update_lock(qc([1u8; 32], 10));
+        let unlocking_justify = qc([2u8; 32], 11); // different block, but higher view
+        assert!(engine.can_vote(&block([2u8; 32], 12), &unlocking_justify).is_ok());
+    }
+
+    #[test]
+    fn a_qc_extending_the_locked_block_is_accepted_even_at_the_same_view() {
+        let mut engine = SafetyEngine::new();
+        engine.update_lock(qc([1u8; 32], 10));
+        let same_block_justify = qc([1u8; 32], 10);
+        assert!(engine.can_vote(&block([1u8; 32], 11), &same_block_justify).is_ok());
+    }
+
+    #[test]
+    fn update_lock_ignores_a_qc_at_or_below_the_current_lock_view() {
+        let mut engine = SafetyEngine::new();
+        engine.update_lock(qc([1u8; 32], 10));
+        engine.update_lock(qc([2u8; 32], 10));
+        engine.update_lock(qc([3u8; 32], 5));
+        assert_eq!(engine.locked_qc().unwrap().block_hash, [1u8; 32]);
+    }
+
+    #[test]
+    fn a_direct_three_chain_commits_the_earliest_block() {
+        let chain = vec![qc([1u8; 32], 5), qc([2u8; 32], 6), qc([3u8; 32], 7)];
+        assert_eq!(commit_rule(&chain), Some([1u8; 32]));
+    }
+
+    #[test]
+    fn a_gap_in_the_qc_chain_commits_nothing() {
+        let chain = vec![qc([1u8; 32], 5), qc([2u8; 32], 6), qc([3u8; 32], 9)];
+        assert_eq!(commit_rule(&chain), None);
+    }
+
+    #[test]
+    fn a_chain_shorter_than_three_never_commits() {
+        let chain = vec![qc([1u8; 32], 5), qc([2u8; 32], 6)];
+        assert_eq!(commit_rule(&chain), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! `StateMachine` implementors so far (see `execution::tests::CountingMachine`)
+//! derived their state root ad hoc. `KVStateMachine` computes a canonical,
+//! Merkle-ized hash over its full key/value state, so two nodes that
+//! executed the same block deterministically produce byte-identical
+//! roots — and `verify_state_hash` turns a mismatch (a non-deterministic
+//! execution bug, since the blocks were identical) into a reportable
+//! `SafetyViolation` instead of silently diverging. Nodes are expected to
+//! carry `state_hash` in their vote for the next height so every other
+//! validator can call `verify_state_hash` against its own execution result.
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash as _, Hasher};
+
+use hotstuff2_types::{Block, Hash};
+
+use crate::execution::{BlockExecutionResult, ExecutionStatus, StateMachine, TxReceipt};
+
+/// A minimal deterministic key/value state machine: each transaction sets
+/// `tx.id -> tx.payload`. Real applications would execute a richer VM, but
+/// the Merkle root computation here is exactly what any deterministic
+/// key/value state needs.
+#[derive(Default)]
+pub struct KVStateMachine {
+    state: BTreeMap<Hash, Vec<u8>>,
+}
+
+impl KVStateMachine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &Hash) -> Option<&Vec<u8>> {
+        self.state.get(key)
+    }
+
+    /// Canonical Merkle root over the full state. `BTreeMap` iterates in
+    /// key order, so this is independent of insertion order — the property
+    /// that makes cross-node comparison meaningful.
+    pub fn state_hash(&self) -> Hash {
+        let leaves: Vec<Hash> = self.state.iter().map(|(key, value)| hash_leaf(key, value)).collect();
+        merkle_root(&leaves)
+    }
+}
+
+impl StateMachine for KVStateMachine {
+    fn execute_block(&mut self, block: &Block) -> BlockExecutionResult {
+        let mut receipts = Vec::with_capacity(block.transactions.len());
+        for tx in &block.transactions {
+            self.state.insert(tx.id, tx.payload.clone());
+            receipts.push(TxReceipt {
+                tx_id: tx.id,
+                status: ExecutionStatus::Success,
+                gas_used: tx.weight,
+                events: Vec::new(),
+            });
+        }
+        BlockExecutionResult { state_root: self.state_hash(), receipts }
+    }
+
+    /// Treats `tx.weight` as that transaction's cost in milliseconds. Once
+    /// the running total would exceed `remaining_view_ms`, every remaining
+    /// transaction is left unapplied and gets an `Aborted` receipt instead
+    /// of being executed past the view's deadline.
+    fn execute_block_with_deadline(&mut self, block: &Block, remaining_view_ms: u64) -> BlockExecutionResult {
+        let mut receipts = Vec::with_capacity(block.transactions.len());
+        let mut spent_ms = 0u64;
+        let mut exhausted = false;
+        for tx in &block.transactions {
+            if exhausted || spent_ms.saturating_add(tx.weight) > remaining_view_ms {
+                exhausted = true;
+                receipts.push(TxReceipt { tx_id: tx.id, status: ExecutionStatus::Aborted, gas_used: 0, events: Vec::new() });
+                continue;
+            }
+            spent_ms += tx.weight;
+            self.state.insert(tx.id, tx.payload.clone());
+            receipts.push(TxReceipt {
+                tx_id: tx.id,
+                status: ExecutionStatus::Success,
+                gas_used: tx.weight,
+                events: Vec::new(),
+            });
+        }
+        BlockExecutionResult { state_root: self.state_hash(), receipts }
+    }
+}
+
+fn hash_leaf(key: &Hash, value: &[u8]) -> Hash {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    value.hash(&mut hasher);
+    let digest = hasher.finish();
+    let mut out = [0u8; 32];
+    out[..8].copy_from_slice(&digest.to_le_bytes());
+    out
+}
+
+/// Standard pairwise Merkle reduction: hash sibling pairs together one
+/// level at a time, duplicating the last node on an odd-sized level, until
+/// a single root remains. An empty tree hashes to the all-zero root.
+fn merkle_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let mut next_level = Vec::with_capacity(level.len().div_ceil(2));
+        for pair in level.chunks(2) {
+            let mut hasher = DefaultHasher::new();
+            pair[0].hash(&mut hasher);
+            let right = pair.get(1).unwrap_or(&pair[0]);
+            right.hash(&mut hasher);
+            let digest = hasher.finish();
+            let mut out = [0u8; 32];
+            out[..8].copy_from_slice(&digest.to_le_bytes());
+            next_level.push(out);
+        }
+        level = next_level;
+    }
+    level[0]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyViolation {
+    pub violation_type: String,
+    pub height: u64,
+    pub expected_state_hash: Hash,
+    pub actual_state_hash: Hash,
+}
+
+/// Compares the state hash a node computed for `height` against the one
+/// carried in the votes it received for that height, raising a
+/// `SafetyViolation` on mismatch. The caller forwards the violation into
+/// whatever metrics/event sink it has (no metrics crate dependency exists
+/// in this crate).
+pub fn verify_state_hash(height: u64, expected: Hash, actual: Hash) -> Result<(), SafetyViolation> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(SafetyViolation {
+            violation_type: "state divergence".to_string(),
+            height,
+            expected_state_hash: expected,
+            actual_state_hash: actual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::Transaction;
+
+    fn tx(id: u8, payload: Vec<u8>) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction { id: hash, payload, weight: 1, valid_until: None }
+    }
+
+    fn block(txs: Vec<Transaction>) -> Block {
+        Block { parent_hash: [0u8; 32], height: 1, view: 1, transactions: txs }
+    }
+
+    #[test]
+    fn identical_blocks_executed_independently_produce_the_same_state_hash() {
+        let mut a = KVStateMachine::new();
+        let mut b = KVStateMachine::new();
+        let block = block(vec![tx(1, vec![1, 2]), tx(2, vec![3, 4])]);
+
+        let result_a = a.execute_block(&block);
+        let result_b = b.execute_block(&block);
+        assert_eq!(result_a.state_root, result_b.state_root);
+    }
+
+    #[test]
+    fn a_different_final_value_for_the_same_key_changes_the_state_hash() {
+        let mut a = KVStateMachine::new();
+        let mut b = KVStateMachine::new();
+        a.execute_block(&block(vec![tx(1, vec![1])]));
+        b.execute_block(&block(vec![tx(1, vec![2])]));
+        assert_ne!(a.state_hash(), b.state_hash());
+    }
+
+    #[test]
+    fn matching_state_hashes_pass_verification() {
+        let mut machine = KVStateMachine::new();
+        let result = machine.execute_block(&block(vec![tx(1, vec![1])]));
+        assert_eq!(verify_state_hash(5, result.state_root, machine.state_hash()), Ok(()));
+    }
+
+    #[test]
+    fn a_mismatching_state_hash_raises_a_state_divergence_safety_violation() {
+        let err = verify_state_hash(5, [1u8; 32], [2u8; 32]).unwrap_err();
+        assert_eq!(err.violation_type, "state divergence");
+        assert_eq!(err.height, 5);
+    }
+
+    #[test]
+    fn an_empty_state_hashes_to_the_zero_root() {
+        let machine = KVStateMachine::new();
+        assert_eq!(machine.state_hash(), [0u8; 32]);
+    }
+
+    fn weighted_tx(id: u8, weight: u64) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction { id: hash, payload: vec![], weight, valid_until: None }
+    }
+
+    #[test]
+    fn a_deadline_with_enough_budget_executes_every_transaction() {
+        let mut machine = KVStateMachine::new();
+        let result = machine.execute_block_with_deadline(&block(vec![weighted_tx(1, 10), weighted_tx(2, 10)]), 100);
+        assert!(result.receipts.iter().all(|r| r.status == ExecutionStatus::Success));
+        assert!(machine.get(&weighted_tx(2, 10).id).is_some());
+    }
+
+    #[test]
+    fn transactions_past_the_remaining_view_budget_are_aborted_not_executed() {
+        let mut machine = KVStateMachine::new();
+        let result = machine.execute_block_with_deadline(&block(vec![weighted_tx(1, 10), weighted_tx(2, 10)]), 15);
+        assert_eq!(result.receipts[0].status, ExecutionStatus::Success);
+        assert_eq!(result.receipts[1].status, ExecutionStatus::Aborted);
+        assert!(machine.get(&weighted_tx(1, 10).id).is_some());
+        assert!(machine.get(&weighted_tx(2, 10).id).is_none());
+    }
+}
@@ -0,0 +1,285 @@
+//! Pluggable block validation, run from `handle_proposal` before a follower
+//! votes. Previously `verify_proposal` only checked for a zero view/hash, so
+//! followers would vote for structurally invalid blocks; a `BlockValidator`
+//! chain lets each concern (size, tx count, duplicates, application state)
+//! reject a proposal independently.
+
+use hotstuff2_types::{Block, Hash};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    TooLarge { max_bytes: usize, actual_bytes: usize },
+    TooManyTransactions { max: usize, actual: usize },
+    DuplicateTransaction,
+    ExpiredTransaction { tx_id: Hash, valid_until: u64, current_height: u64 },
+    AlreadyCommitted { tx_id: Hash },
+    ApplicationRejected(String),
+}
+
+pub trait BlockValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError>;
+}
+
+pub struct SizeLimitValidator {
+    pub max_bytes: usize,
+}
+
+impl BlockValidator for SizeLimitValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        let actual_bytes: usize = block.transactions.iter().map(|tx| tx.payload.len()).sum();
+        if actual_bytes > self.max_bytes {
+            return Err(ValidationError::TooLarge {
+                max_bytes: self.max_bytes,
+                actual_bytes,
+            });
+        }
+        Ok(())
+    }
+}
+
+pub struct TxCountValidator {
+    pub max_transactions: usize,
+}
+
+impl BlockValidator for TxCountValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        let actual = block.transactions.len();
+        if actual > self.max_transactions {
+            return Err(ValidationError::TooManyTransactions {
+                max: self.max_transactions,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct DuplicateTxValidator;
+
+impl BlockValidator for DuplicateTxValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        let mut seen = std::collections::HashSet::new();
+        for tx in &block.transactions {
+            if !seen.insert(tx.id) {
+                return Err(ValidationError::DuplicateTransaction);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects a block that includes a transaction whose client-supplied
+/// `valid_until` height has already passed by the time this block would
+/// execute it, so a stale transfer a client gave up waiting on can't be
+/// included after the fact.
+pub struct ExpiredTxValidator {
+    pub current_height: u64,
+}
+
+impl BlockValidator for ExpiredTxValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        for tx in &block.transactions {
+            if let Some(valid_until) = tx.valid_until {
+                if valid_until < self.current_height {
+                    return Err(ValidationError::ExpiredTransaction {
+                        tx_id: tx.id,
+                        valid_until,
+                        current_height: self.current_height,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects a block that recommits a transaction id already present in a
+/// prior committed block, closing the gap left by `DuplicateTxValidator`
+/// (which only catches duplicates *within* one block): a transaction that
+/// lingers in more than one node's mempool after a leader change could
+/// otherwise be proposed and committed a second time. `committed` is a
+/// snapshot of already-committed transaction ids, e.g. built from
+/// `ReceiptStore::committed_tx_ids` before validating each proposal.
+pub struct AlreadyCommittedTxValidator {
+    pub committed: std::collections::HashSet<Hash>,
+}
+
+impl BlockValidator for AlreadyCommittedTxValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        for tx in &block.transactions {
+            if self.committed.contains(&tx.id) {
+                return Err(ValidationError::AlreadyCommitted { tx_id: tx.id });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Application-level validity, delegated to a closure so the consensus crate
+/// doesn't need to depend on any particular state machine implementation.
+pub struct StateMachineValidator<F> {
+    pub check: F,
+}
+
+impl<F> BlockValidator for StateMachineValidator<F>
+where
+    F: Fn(&Block) -> Result<(), String>,
+{
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        (self.check)(block).map_err(ValidationError::ApplicationRejected)
+    }
+}
+
+/// Ordered chain of validators run against every incoming proposal. Stops at
+/// the first failure so callers get a single, specific rejection reason.
+#[derive(Default)]
+pub struct ValidationPipeline {
+    validators: Vec<Box<dyn BlockValidator>>,
+}
+
+impl ValidationPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_validator(mut self, validator: impl BlockValidator + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    pub fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        for validator in &self.validators {
+            validator.validate(block)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the old zero-view/zero-hash-only check: a follower now runs the
+/// full validation pipeline before it is willing to vote for `block`.
+pub fn handle_proposal(pipeline: &ValidationPipeline, block: &Block) -> Result<(), ValidationError> {
+    if block.view == 0 {
+        return Err(ValidationError::ApplicationRejected("proposal has zero view".into()));
+    }
+    pipeline.validate(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::Transaction;
+
+    fn tx(id: u8, payload_len: usize) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction {
+            id: hash,
+            payload: vec![0u8; payload_len],
+            weight: 1,
+            valid_until: None,
+        }
+    }
+
+    fn block(transactions: Vec<Transaction>) -> Block {
+        Block {
+            parent_hash: [0u8; 32],
+            height: 1,
+            view: 1,
+            transactions,
+        }
+    }
+
+    #[test]
+    fn accepts_a_valid_block() {
+        let pipeline = ValidationPipeline::new()
+            .with_validator(SizeLimitValidator { max_bytes: 1024 })
+            .with_validator(TxCountValidator { max_transactions: 10 })
+            .with_validator(DuplicateTxValidator);
+
+        assert!(handle_proposal(&pipeline, &block(vec![tx(1, 10), tx(2, 10)])).is_ok());
+    }
+
+    #[test]
+    fn rejects_oversized_block() {
+        let pipeline = ValidationPipeline::new().with_validator(SizeLimitValidator { max_bytes: 5 });
+        let err = handle_proposal(&pipeline, &block(vec![tx(1, 10)])).unwrap_err();
+        assert!(matches!(err, ValidationError::TooLarge { .. }));
+    }
+
+    #[test]
+    fn rejects_too_many_transactions() {
+        let pipeline = ValidationPipeline::new().with_validator(TxCountValidator { max_transactions: 1 });
+        let err = handle_proposal(&pipeline, &block(vec![tx(1, 1), tx(2, 1)])).unwrap_err();
+        assert!(matches!(err, ValidationError::TooManyTransactions { .. }));
+    }
+
+    #[test]
+    fn rejects_duplicate_transactions() {
+        let pipeline = ValidationPipeline::new().with_validator(DuplicateTxValidator);
+        let err = handle_proposal(&pipeline, &block(vec![tx(1, 1), tx(1, 1)])).unwrap_err();
+        assert_eq!(err, ValidationError::DuplicateTransaction);
+    }
+
+    #[test]
+    fn rejects_via_application_state_machine_check() {
+        let pipeline = ValidationPipeline::new().with_validator(StateMachineValidator {
+            check: |b: &Block| {
+                if b.height == 1 {
+                    Err("height 1 is reserved for genesis".to_string())
+                } else {
+                    Ok(())
+                }
+            },
+        });
+        let err = handle_proposal(&pipeline, &block(vec![])).unwrap_err();
+        assert!(matches!(err, ValidationError::ApplicationRejected(_)));
+    }
+
+    #[test]
+    fn rejects_a_block_containing_an_expired_transaction() {
+        let pipeline = ValidationPipeline::new().with_validator(ExpiredTxValidator { current_height: 100 });
+        let mut expired = tx(1, 1);
+        expired.valid_until = Some(50);
+        let err = handle_proposal(&pipeline, &block(vec![expired])).unwrap_err();
+        assert!(matches!(err, ValidationError::ExpiredTransaction { valid_until: 50, current_height: 100, .. }));
+    }
+
+    #[test]
+    fn accepts_a_transaction_still_within_its_validity_window() {
+        let pipeline = ValidationPipeline::new().with_validator(ExpiredTxValidator { current_height: 100 });
+        let mut still_valid = tx(1, 1);
+        still_valid.valid_until = Some(100);
+        assert!(handle_proposal(&pipeline, &block(vec![still_valid])).is_ok());
+    }
+
+    #[test]
+    fn a_transaction_with_no_expiry_never_expires() {
+        let pipeline = ValidationPipeline::new().with_validator(ExpiredTxValidator { current_height: u64::MAX });
+        assert!(handle_proposal(&pipeline, &block(vec![tx(1, 1)])).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_transaction_already_committed_in_a_prior_block() {
+        let already_committed = tx(1, 1);
+        let mut committed = std::collections::HashSet::new();
+        committed.insert(already_committed.id);
+        let pipeline = ValidationPipeline::new().with_validator(AlreadyCommittedTxValidator { committed });
+        let err = handle_proposal(&pipeline, &block(vec![already_committed])).unwrap_err();
+        assert!(matches!(err, ValidationError::AlreadyCommitted { .. }));
+    }
+
+    #[test]
+    fn accepts_a_transaction_not_seen_in_any_prior_committed_block() {
+        let pipeline = ValidationPipeline::new().with_validator(AlreadyCommittedTxValidator { committed: std::collections::HashSet::new() });
+        assert!(handle_proposal(&pipeline, &block(vec![tx(1, 1)])).is_ok());
+    }
+
+    #[test]
+    fn rejects_zero_view_before_running_pipeline() {
+        let pipeline = ValidationPipeline::new();
+        let mut b = block(vec![]);
+        b.view = 0;
+        assert!(handle_proposal(&pipeline, &b).is_err());
+    }
+}
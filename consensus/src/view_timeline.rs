@@ -0,0 +1,230 @@
+//! Diagnosing where latency goes in a slow round means correlating four
+//! timestamps — proposal received, 2f+1 votes collected, QC broadcast,
+//! commit — which nothing in this crate previously recorded. Enabling this
+//! unconditionally would be wasted allocation on every fast round, so
+//! `ViewTimelineRecorder` is opt-in and a no-op (`record` returns
+//! immediately) when disabled. The recorder keeps its entries in memory;
+//! `encode`/`decode` define a compact binary record format for them, and
+//! writing those records to disk is left to the caller; `to_json_lines`
+//! renders it as one JSON object per line for whatever off-the-shelf tool
+//! an operator wants to point at it, since no JSON crate is available in
+//! this workspace to hand it a typed value instead.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewEvent {
+    ProposalReceived,
+    VotesQuorum,
+    QcBroadcast,
+    Commit,
+}
+
+impl ViewEvent {
+    fn to_byte(self) -> u8 {
+        match self {
+            ViewEvent::ProposalReceived => 0,
+            ViewEvent::VotesQuorum => 1,
+            ViewEvent::QcBroadcast => 2,
+            ViewEvent::Commit => 3,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(ViewEvent::ProposalReceived),
+            1 => Some(ViewEvent::VotesQuorum),
+            2 => Some(ViewEvent::QcBroadcast),
+            3 => Some(ViewEvent::Commit),
+            _ => None,
+        }
+    }
+
+    fn as_json_label(self) -> &'static str {
+        match self {
+            ViewEvent::ProposalReceived => "proposal_received",
+            ViewEvent::VotesQuorum => "votes_quorum",
+            ViewEvent::QcBroadcast => "qc_broadcast",
+            ViewEvent::Commit => "commit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineEntry {
+    pub view: u64,
+    pub event: ViewEvent,
+    pub timestamp_ms: u64,
+}
+
+/// Records per-view timeline entries when enabled; a disabled recorder
+/// drops every `record` call at zero cost beyond the enabled check.
+#[derive(Default)]
+pub struct ViewTimelineRecorder {
+    enabled: bool,
+    entries: Vec<TimelineEntry>,
+}
+
+impl ViewTimelineRecorder {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, entries: Vec::new() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// No-op when disabled, so call sites don't need their own `if
+    /// enabled` guard around every call.
+    pub fn record(&mut self, view: u64, event: ViewEvent, timestamp_ms: u64) {
+        if self.enabled {
+            self.entries.push(TimelineEntry { view, event, timestamp_ms });
+        }
+    }
+
+    pub fn entries(&self) -> &[TimelineEntry] {
+        &self.entries
+    }
+
+    /// Every recorded entry for `view`, in recording order.
+    pub fn entries_for(&self, view: u64) -> Vec<TimelineEntry> {
+        self.entries.iter().copied().filter(|e| e.view == view).collect()
+    }
+
+    /// Compact binary log format: entry count, then per entry a fixed
+    /// 17-byte record (view: u64 LE, event: u8, timestamp_ms: u64 LE).
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(8 + self.entries.len() * 17);
+        buf.extend_from_slice(&(self.entries.len() as u64).to_le_bytes());
+        for entry in &self.entries {
+            buf.extend_from_slice(&entry.view.to_le_bytes());
+            buf.push(entry.event.to_byte());
+            buf.extend_from_slice(&entry.timestamp_ms.to_le_bytes());
+        }
+        buf
+    }
+
+    pub fn decode(buf: &[u8]) -> Option<Vec<TimelineEntry>> {
+        if buf.len() < 8 {
+            return None;
+        }
+        let count = u64::from_le_bytes(buf[0..8].try_into().unwrap()) as usize;
+        // `count` is attacker-controlled; bound the reserved capacity by
+        // what the buffer could actually hold instead of trusting it
+        // outright, so a truncated buffer claiming a huge count returns
+        // `None` instead of attempting a runaway allocation.
+        let max_entries_in_buf = buf.len().saturating_sub(8) / 17;
+        if count > max_entries_in_buf {
+            return None;
+        }
+        let mut entries = Vec::with_capacity(count);
+        let mut cursor = 8usize;
+        for _ in 0..count {
+            if buf.len() < cursor + 17 {
+                return None;
+            }
+            let view = u64::from_le_bytes(buf[cursor..cursor + 8].try_into().unwrap());
+            let event = ViewEvent::from_byte(buf[cursor + 8])?;
+            let timestamp_ms = u64::from_le_bytes(buf[cursor + 9..cursor + 17].try_into().unwrap());
+            entries.push(TimelineEntry { view, event, timestamp_ms });
+            cursor += 17;
+        }
+        Some(entries)
+    }
+
+    /// One JSON object per line (JSON Lines), e.g.
+    /// `{"view":5,"event":"commit","timestamp_ms":1200}` — exported for
+    /// whatever visualization tool an operator already has, since this
+    /// workspace has no JSON crate to build a richer document with.
+    pub fn to_json_lines(&self) -> String {
+        self.entries
+            .iter()
+            .map(|e| format!(r#"{{"view":{},"event":"{}","timestamp_ms":{}}}"#, e.view, e.event.as_json_label(), e.timestamp_ms))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_disabled_recorder_drops_every_record_call() {
+        let mut recorder = ViewTimelineRecorder::new(false);
+        recorder.record(1, ViewEvent::Commit, 100);
+        assert!(recorder.entries().is_empty());
+    }
+
+    #[test]
+    fn an_enabled_recorder_keeps_entries_in_recording_order() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(1, ViewEvent::ProposalReceived, 0);
+        recorder.record(1, ViewEvent::VotesQuorum, 40);
+        recorder.record(1, ViewEvent::QcBroadcast, 45);
+        recorder.record(1, ViewEvent::Commit, 60);
+        let entries = recorder.entries_for(1);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[3].event, ViewEvent::Commit);
+        assert_eq!(entries[3].timestamp_ms, 60);
+    }
+
+    #[test]
+    fn entries_for_filters_by_view() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(1, ViewEvent::Commit, 10);
+        recorder.record(2, ViewEvent::Commit, 20);
+        assert_eq!(recorder.entries_for(2), vec![TimelineEntry { view: 2, event: ViewEvent::Commit, timestamp_ms: 20 }]);
+    }
+
+    #[test]
+    fn encoding_and_decoding_round_trips_every_entry() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(1, ViewEvent::ProposalReceived, 0);
+        recorder.record(1, ViewEvent::Commit, 60);
+        let decoded = ViewTimelineRecorder::decode(&recorder.encode()).unwrap();
+        assert_eq!(decoded, recorder.entries().to_vec());
+    }
+
+    #[test]
+    fn decode_rejects_a_truncated_buffer() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(1, ViewEvent::Commit, 60);
+        let mut encoded = recorder.encode();
+        encoded.truncate(encoded.len() - 1);
+        assert!(ViewTimelineRecorder::decode(&encoded).is_none());
+    }
+
+    /// No `bincode`/`serde`/protobuf crate is available in this workspace,
+    /// so `encode`/`decode` are this format's hand-rolled equivalent; this
+    /// is that format's round-trip/adversarial-input property test: every
+    /// truncation length returns `None` instead of panicking, and a
+    /// claimed entry count far larger than the buffer could ever hold (the
+    /// `Vec::with_capacity` runaway-allocation footgun) is rejected rather
+    /// than acted on.
+    #[test]
+    fn every_truncation_length_is_rejected_without_panicking() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(1, ViewEvent::ProposalReceived, 0);
+        recorder.record(2, ViewEvent::Commit, 60);
+        let encoded = recorder.encode();
+        for len in 0..encoded.len() {
+            assert!(ViewTimelineRecorder::decode(&encoded[..len]).is_none(), "truncation to {len} bytes should be rejected");
+        }
+        assert_eq!(ViewTimelineRecorder::decode(&encoded), Some(recorder.entries().to_vec()));
+    }
+
+    #[test]
+    fn a_forged_huge_entry_count_is_rejected_instead_of_over_allocating() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(1, ViewEvent::Commit, 60);
+        let mut encoded = recorder.encode();
+        encoded[0..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ViewTimelineRecorder::decode(&encoded).is_none());
+    }
+
+    #[test]
+    fn json_lines_export_produces_one_line_per_entry() {
+        let mut recorder = ViewTimelineRecorder::new(true);
+        recorder.record(5, ViewEvent::Commit, 1200);
+        assert_eq!(recorder.to_json_lines(), r#"{"view":5,"event":"commit","timestamp_ms":1200}"#);
+    }
+}
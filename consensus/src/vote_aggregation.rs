@@ -0,0 +1,98 @@
+//! QC formation used to happen inline in `handle_vote`, contending on shared
+//! map access. `VoteAggregator` collects votes for one height off the
+//! message-processing hot path (the caller runs it on its own worker
+//! task/thread per height) and emits a QC once quorum is reached.
+
+use std::collections::HashSet;
+
+use hotstuff2_types::{Hash, QuorumCertificate, ValidatorId, ValidatorSet};
+
+pub struct VoteAggregator {
+    height: u64,
+    block_hash: Hash,
+    view: u64,
+    validator_set: ValidatorSet,
+    signers: HashSet<ValidatorId>,
+    formed: bool,
+}
+
+impl VoteAggregator {
+    pub fn new(height: u64, block_hash: Hash, view: u64, validator_set: ValidatorSet) -> Self {
+        Self {
+            height,
+            block_hash,
+            view,
+            validator_set,
+            signers: HashSet::new(),
+            formed: false,
+        }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Ingests one vote. Returns `Some(QuorumCertificate)` the moment quorum
+    /// is reached; further votes for an already-formed QC are ignored.
+    pub fn ingest_vote(&mut self, voter: ValidatorId) -> Option<QuorumCertificate> {
+        if self.formed || !self.validator_set.validators.contains(&voter) {
+            return None; // QC already formed, or not a known validator: drop rather than corrupt the count
+        }
+        self.signers.insert(voter);
+        let qc = QuorumCertificate {
+            block_hash: self.block_hash,
+            view: self.view,
+            signers: self.signers.iter().copied().collect(),
+        };
+        if self.validator_set.verify_threshold(&qc) {
+            self.formed = true;
+            Some(qc)
+        } else {
+            None
+        }
+    }
+}
+
+/// Runs each height's aggregator against a batch of votes drained from a
+/// channel by the worker task, returning the first QC formed (if any). This
+/// is the function a `spawn`ed worker calls in a loop over its receiver.
+pub fn drain_and_aggregate(aggregator: &mut VoteAggregator, votes: impl IntoIterator<Item = ValidatorId>) -> Option<QuorumCertificate> {
+    for voter in votes {
+        if let Some(qc) = aggregator.ingest_vote(voter) {
+            return Some(qc);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator_set(n: u64) -> ValidatorSet {
+        ValidatorSet { validators: (0..n).collect() }
+    }
+
+    #[test]
+    fn forms_a_qc_once_quorum_is_reached() {
+        let mut aggregator = VoteAggregator::new(10, [1u8; 32], 1, validator_set(4));
+        assert!(aggregator.ingest_vote(0).is_none());
+        assert!(aggregator.ingest_vote(1).is_none());
+        let qc = aggregator.ingest_vote(2).unwrap();
+        assert_eq!(qc.signers.len(), 3);
+    }
+
+    #[test]
+    fn unknown_voters_are_dropped_not_counted() {
+        let mut aggregator = VoteAggregator::new(10, [1u8; 32], 1, validator_set(4));
+        assert!(aggregator.ingest_vote(999).is_none());
+        assert!(aggregator.ingest_vote(0).is_none());
+    }
+
+    #[test]
+    fn drain_and_aggregate_stops_at_the_first_qc() {
+        let mut aggregator = VoteAggregator::new(10, [1u8; 32], 1, validator_set(4));
+        let qc = drain_and_aggregate(&mut aggregator, [0, 1, 2, 3]).unwrap();
+        assert_eq!(qc.signers.len(), 3);
+    }
+}
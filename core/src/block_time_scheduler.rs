@@ -0,0 +1,134 @@
+//! The pacemaker only reacts to timeouts and votes; nothing paces proposals
+//! to a *target* rate, so a chain that wants predictable block production
+//! (e.g. 1 block/sec, for downstream systems that assume roughly steady
+//! block time) has no way to slow the leader down when it could propose
+//! faster. `BlockTimeScheduler` gates proposals to `target_interval_ms` and
+//! tracks cumulative drift (proposals landing early or late relative to the
+//! target) so an operator can see whether the chain is keeping pace, not
+//! just whether it eventually produces blocks.
+
+use crate::clock::Clock;
+
+/// Paces proposals to `target_interval_ms` against a `Clock`, so it's
+/// testable with a `SimulatedClock` the same way `TimeoutManager` is.
+pub struct BlockTimeScheduler<C: Clock> {
+    clock: C,
+    target_interval_ms: u64,
+    last_proposal_ms: Option<u64>,
+    cumulative_drift_ms: i64,
+}
+
+impl<C: Clock> BlockTimeScheduler<C> {
+    pub fn new(clock: C, target_interval_ms: u64) -> Self {
+        Self { clock, target_interval_ms, last_proposal_ms: None, cumulative_drift_ms: 0 }
+    }
+
+    /// True once `target_interval_ms` has elapsed since the last proposal.
+    /// Always true before the first proposal.
+    pub fn should_propose_now(&self) -> bool {
+        match self.last_proposal_ms {
+            None => true,
+            Some(last) => self.clock.now_ms().saturating_sub(last) >= self.target_interval_ms,
+        }
+    }
+
+    /// Milliseconds until the next proposal is due; 0 if it's already due
+    /// (or none has ever happened).
+    pub fn ms_until_next_proposal(&self) -> u64 {
+        match self.last_proposal_ms {
+            None => 0,
+            Some(last) => {
+                let due_at = last.saturating_add(self.target_interval_ms);
+                due_at.saturating_sub(self.clock.now_ms())
+            }
+        }
+    }
+
+    /// Records a proposal happening now. Returns this round's drift (actual
+    /// interval since the last proposal minus the target; positive means
+    /// late, negative means early); the very first proposal has no prior
+    /// interval to compare against and reports zero drift.
+    pub fn record_proposal(&mut self) -> i64 {
+        let now = self.clock.now_ms();
+        let drift = match self.last_proposal_ms {
+            Some(last) => (now.saturating_sub(last)) as i64 - self.target_interval_ms as i64,
+            None => 0,
+        };
+        self.cumulative_drift_ms += drift;
+        self.last_proposal_ms = Some(now);
+        drift
+    }
+
+    /// Sum of every round's drift so far; consistently positive means the
+    /// chain is falling behind its target block time, consistently negative
+    /// means it's proposing faster than requested.
+    pub fn cumulative_drift_ms(&self) -> i64 {
+        self.cumulative_drift_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::SimulatedClock;
+
+    #[test]
+    fn the_first_proposal_is_always_due_immediately() {
+        let scheduler = BlockTimeScheduler::new(SimulatedClock::new(0), 1_000);
+        assert!(scheduler.should_propose_now());
+        assert_eq!(scheduler.ms_until_next_proposal(), 0);
+    }
+
+    #[test]
+    fn a_proposal_is_not_due_again_until_the_target_interval_elapses() {
+        let clock = SimulatedClock::new(0);
+        let mut scheduler = BlockTimeScheduler::new(clock, 1_000);
+        scheduler.record_proposal();
+        assert!(!scheduler.should_propose_now());
+        scheduler.clock.advance(999);
+        assert!(!scheduler.should_propose_now());
+        scheduler.clock.advance(1);
+        assert!(scheduler.should_propose_now());
+    }
+
+    #[test]
+    fn ms_until_next_proposal_counts_down_toward_zero() {
+        let clock = SimulatedClock::new(0);
+        let mut scheduler = BlockTimeScheduler::new(clock, 1_000);
+        scheduler.record_proposal();
+        scheduler.clock.advance(400);
+        assert_eq!(scheduler.ms_until_next_proposal(), 600);
+    }
+
+    #[test]
+    fn a_proposal_exactly_on_schedule_has_zero_drift() {
+        let clock = SimulatedClock::new(0);
+        let mut scheduler = BlockTimeScheduler::new(clock, 1_000);
+        scheduler.record_proposal();
+        scheduler.clock.advance(1_000);
+        assert_eq!(scheduler.record_proposal(), 0);
+        assert_eq!(scheduler.cumulative_drift_ms(), 0);
+    }
+
+    #[test]
+    fn a_late_proposal_reports_positive_drift_and_accumulates() {
+        let clock = SimulatedClock::new(0);
+        let mut scheduler = BlockTimeScheduler::new(clock, 1_000);
+        scheduler.record_proposal();
+        scheduler.clock.advance(1_300);
+        assert_eq!(scheduler.record_proposal(), 300);
+        scheduler.clock.advance(1_200);
+        assert_eq!(scheduler.record_proposal(), 200);
+        assert_eq!(scheduler.cumulative_drift_ms(), 500);
+    }
+
+    #[test]
+    fn an_early_proposal_reports_negative_drift() {
+        let clock = SimulatedClock::new(0);
+        let mut scheduler = BlockTimeScheduler::new(clock, 1_000);
+        scheduler.record_proposal();
+        scheduler.clock.advance(700);
+        assert_eq!(scheduler.record_proposal(), -300);
+        assert_eq!(scheduler.cumulative_drift_ms(), -300);
+    }
+}
@@ -0,0 +1,75 @@
+//! Every timeout-driven piece of this crate (the pacemaker's per-kind
+//! deadlines, view-change backoff) is written in terms of caller-supplied
+//! millisecond timestamps rather than reading the wall clock itself, so
+//! callers already control determinism at the call-site level. `Clock`
+//! makes that convention a trait callers can hold onto instead of
+//! re-threading `now_ms` by hand: production code holds a `SystemClock`,
+//! tests hold a `SimulatedClock` that only advances when told to, so a
+//! multi-timeout view-change scenario runs in a microsecond instead of
+//! real seconds.
+
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub trait Clock {
+    fn now_ms(&self) -> u64;
+}
+
+/// Reads the real wall clock. `now_ms()` saturates to 0 rather than
+/// panicking if the system clock is somehow set before the Unix epoch.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+    }
+}
+
+/// A clock that only moves when `advance`/`set` is called, so tests drive
+/// timeout logic deterministically instead of sleeping.
+#[derive(Debug, Default)]
+pub struct SimulatedClock {
+    current_ms: Cell<u64>,
+}
+
+impl SimulatedClock {
+    pub fn new(start_ms: u64) -> Self {
+        Self { current_ms: Cell::new(start_ms) }
+    }
+
+    pub fn advance(&self, delta_ms: u64) {
+        self.current_ms.set(self.current_ms.get().saturating_add(delta_ms));
+    }
+
+    pub fn set(&self, ms: u64) {
+        self.current_ms.set(ms);
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now_ms(&self) -> u64 {
+        self.current_ms.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_simulated_clock_starts_at_the_given_time_and_only_moves_on_command() {
+        let clock = SimulatedClock::new(1_000);
+        assert_eq!(clock.now_ms(), 1_000);
+        clock.advance(500);
+        assert_eq!(clock.now_ms(), 1_500);
+        clock.set(9_000);
+        assert_eq!(clock.now_ms(), 9_000);
+    }
+
+    #[test]
+    fn the_system_clock_reports_a_plausible_unix_timestamp() {
+        let clock = SystemClock;
+        assert!(clock.now_ms() > 1_600_000_000_000);
+    }
+}
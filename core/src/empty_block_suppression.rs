@@ -0,0 +1,133 @@
+//! Leaders proposed a fresh block every view even with an empty mempool,
+//! wasting a QC round and log entry that carries zero transactions.
+//! `EmptyBlockPolicy` lets a leader skip those, but only in a way that
+//! still interacts correctly with `Pacemaker`: `Suppress` disables
+//! proposals outright and is only safe when something else (a network
+//! heartbeat, see `hotstuff2_network::heartbeat`) keeps followers' liveness
+//! signal alive, since suppressing forever would otherwise starve
+//! `TimeoutKind::AwaitingProposal` into a permanent view-change storm.
+//! `ReducedHeartbeat` is the safer default: it still proposes an empty
+//! block, just at a slower cadence, and `is_safe_for` checks that cadence
+//! stays strictly under the pacemaker's proposal timeout so a follower
+//! never times out waiting for one.
+
+use crate::pacemaker::{PacemakerTimeouts, TimeoutKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyBlockMode {
+    /// Pre-existing behavior: propose every view regardless of mempool state.
+    AlwaysPropose,
+    /// Never propose an empty block. Only safe if liveness is otherwise
+    /// carried by a mechanism outside the proposal path.
+    Suppress,
+    /// Propose an empty block at most once per `interval_ms`, otherwise skip.
+    ReducedHeartbeat { interval_ms: u64 },
+}
+
+pub struct EmptyBlockPolicy {
+    mode: EmptyBlockMode,
+    last_proposal_at_ms: Option<u64>,
+}
+
+impl EmptyBlockPolicy {
+    pub fn new(mode: EmptyBlockMode) -> Self {
+        Self { mode, last_proposal_at_ms: None }
+    }
+
+    /// A non-empty mempool always proposes, regardless of mode. An empty
+    /// one defers to `mode`.
+    pub fn should_propose(&self, mempool_is_empty: bool, now_ms: u64) -> bool {
+        if !mempool_is_empty {
+            return true;
+        }
+        match self.mode {
+            EmptyBlockMode::AlwaysPropose => true,
+            EmptyBlockMode::Suppress => false,
+            EmptyBlockMode::ReducedHeartbeat { interval_ms } => match self.last_proposal_at_ms {
+                None => true,
+                Some(last) => now_ms.saturating_sub(last) >= interval_ms,
+            },
+        }
+    }
+
+    /// Call after actually proposing (empty or not) at `now_ms`, so the
+    /// next `ReducedHeartbeat` decision measures from this point.
+    pub fn record_proposal(&mut self, now_ms: u64) {
+        self.last_proposal_at_ms = Some(now_ms);
+    }
+
+    /// `true` if this policy can never starve a follower's
+    /// `AwaitingProposal` timeout: `AlwaysPropose` always qualifies,
+    /// `ReducedHeartbeat` qualifies only if its interval is strictly under
+    /// the pacemaker's current proposal timeout, and `Suppress` never
+    /// qualifies on its own.
+    pub fn is_safe_for(&self, timeouts: &PacemakerTimeouts) -> bool {
+        match self.mode {
+            EmptyBlockMode::AlwaysPropose => true,
+            EmptyBlockMode::Suppress => false,
+            EmptyBlockMode::ReducedHeartbeat { interval_ms } => interval_ms < timeouts.get(TimeoutKind::AwaitingProposal),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeouts(proposal_ms: u64) -> PacemakerTimeouts {
+        PacemakerTimeouts { proposal_ms, votes_ms: 1000, qc_broadcast_ms: 1000 }
+    }
+
+    #[test]
+    fn a_non_empty_mempool_always_proposes() {
+        let policy = EmptyBlockPolicy::new(EmptyBlockMode::Suppress);
+        assert!(policy.should_propose(false, 0));
+    }
+
+    #[test]
+    fn always_propose_mode_proposes_even_when_empty() {
+        let policy = EmptyBlockPolicy::new(EmptyBlockMode::AlwaysPropose);
+        assert!(policy.should_propose(true, 0));
+    }
+
+    #[test]
+    fn suppress_mode_never_proposes_when_empty() {
+        let policy = EmptyBlockPolicy::new(EmptyBlockMode::Suppress);
+        assert!(!policy.should_propose(true, 100_000));
+    }
+
+    #[test]
+    fn reduced_heartbeat_proposes_on_the_very_first_call() {
+        let policy = EmptyBlockPolicy::new(EmptyBlockMode::ReducedHeartbeat { interval_ms: 5_000 });
+        assert!(policy.should_propose(true, 0));
+    }
+
+    #[test]
+    fn reduced_heartbeat_skips_until_the_interval_elapses() {
+        let mut policy = EmptyBlockPolicy::new(EmptyBlockMode::ReducedHeartbeat { interval_ms: 5_000 });
+        policy.record_proposal(1_000);
+        assert!(!policy.should_propose(true, 3_000));
+        assert!(policy.should_propose(true, 6_001));
+    }
+
+    #[test]
+    fn always_propose_is_always_safe() {
+        let policy = EmptyBlockPolicy::new(EmptyBlockMode::AlwaysPropose);
+        assert!(policy.is_safe_for(&timeouts(1)));
+    }
+
+    #[test]
+    fn suppress_is_never_safe_on_its_own() {
+        let policy = EmptyBlockPolicy::new(EmptyBlockMode::Suppress);
+        assert!(!policy.is_safe_for(&timeouts(u64::MAX)));
+    }
+
+    #[test]
+    fn reduced_heartbeat_is_safe_only_strictly_under_the_proposal_timeout() {
+        let fast = EmptyBlockPolicy::new(EmptyBlockMode::ReducedHeartbeat { interval_ms: 1_000 });
+        assert!(fast.is_safe_for(&timeouts(2_000)));
+
+        let too_slow = EmptyBlockPolicy::new(EmptyBlockMode::ReducedHeartbeat { interval_ms: 2_000 });
+        assert!(!too_slow.is_safe_for(&timeouts(2_000)));
+    }
+}
@@ -0,0 +1,104 @@
+//! Decouples block execution from the consensus message loop: committed
+//! blocks are pushed onto a bounded queue and drained by a dedicated
+//! executor (task/thread, owned by the caller), with backpressure signaled
+//! via `push` returning `Err` when full, and `executed_height` tracked
+//! separately from `committed_height` so a slow state machine can lag
+//! without stalling voting.
+
+use std::collections::VecDeque;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct QueueFull {
+    pub height: u64,
+}
+
+pub struct ExecutionPipeline {
+    capacity: usize,
+    queue: VecDeque<u64>,
+    committed_height: u64,
+    executed_height: u64,
+}
+
+impl ExecutionPipeline {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            queue: VecDeque::new(),
+            committed_height: 0,
+            executed_height: 0,
+        }
+    }
+
+    /// Called from the consensus loop on commit. Never blocks: returns
+    /// `Err(QueueFull)` for the caller to apply backpressure (e.g. slow down
+    /// proposing) rather than stalling here.
+    pub fn push_committed(&mut self, height: u64) -> Result<(), QueueFull> {
+        if self.queue.len() >= self.capacity {
+            return Err(QueueFull { height });
+        }
+        self.queue.push_back(height);
+        self.committed_height = self.committed_height.max(height);
+        Ok(())
+    }
+
+    /// Called from the dedicated executor task/thread to get the next block
+    /// to execute, in commit order.
+    pub fn pop_for_execution(&mut self) -> Option<u64> {
+        self.queue.pop_front()
+    }
+
+    /// Called by the executor once it has actually run the state machine for
+    /// `height`, advancing the `executed_height` stat independently of
+    /// `committed_height`.
+    pub fn mark_executed(&mut self, height: u64) {
+        self.executed_height = self.executed_height.max(height);
+    }
+
+    pub fn committed_height(&self) -> u64 {
+        self.committed_height
+    }
+
+    pub fn executed_height(&self) -> u64 {
+        self.executed_height
+    }
+
+    pub fn backlog(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_height_advances_without_waiting_for_execution() {
+        let mut pipeline = ExecutionPipeline::new(4);
+        pipeline.push_committed(1).unwrap();
+        pipeline.push_committed(2).unwrap();
+        assert_eq!(pipeline.committed_height(), 2);
+        assert_eq!(pipeline.executed_height(), 0);
+    }
+
+    #[test]
+    fn signals_backpressure_when_the_queue_is_full() {
+        let mut pipeline = ExecutionPipeline::new(1);
+        pipeline.push_committed(1).unwrap();
+        assert_eq!(pipeline.push_committed(2), Err(QueueFull { height: 2 }));
+    }
+
+    #[test]
+    fn executor_drains_in_commit_order_and_advances_executed_height() {
+        let mut pipeline = ExecutionPipeline::new(4);
+        pipeline.push_committed(1).unwrap();
+        pipeline.push_committed(2).unwrap();
+
+        assert_eq!(pipeline.pop_for_execution(), Some(1));
+        pipeline.mark_executed(1);
+        assert_eq!(pipeline.executed_height(), 1);
+
+        assert_eq!(pipeline.pop_for_execution(), Some(2));
+        pipeline.mark_executed(2);
+        assert_eq!(pipeline.executed_height(), 2);
+    }
+}
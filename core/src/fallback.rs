@@ -0,0 +1,125 @@
+//! `ResponsivenessMode::Asynchronous` slow path: an explicit prepare ->
+//! pre-commit -> commit chain with locked-QC updates, instead of a bare vote
+//! in `Phase::Propose`. The switch between fast and slow path is explicit
+//! and observable via `ModeSwitch`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponsivenessMode {
+    Synchronous,
+    Asynchronous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Prepare,
+    PreCommit,
+    Commit,
+    Decide,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LockedQc {
+    pub view: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeSwitch {
+    pub from: ResponsivenessMode,
+    pub to: ResponsivenessMode,
+}
+
+/// Drives the slow-path 3-phase state machine. Each call advances one phase
+/// on receiving quorum for the current phase, updating the locked QC at
+/// pre-commit per the standard PBFT-style locking rule.
+pub struct AsyncFallback {
+    phase: Phase,
+    locked_qc: Option<LockedQc>,
+    view: u64,
+}
+
+impl AsyncFallback {
+    pub fn new(view: u64) -> Self {
+        Self { phase: Phase::Prepare, locked_qc: None, view }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn locked_qc(&self) -> Option<LockedQc> {
+        self.locked_qc
+    }
+
+    /// Advances on quorum for the current phase; a call with `quorum_reached
+    /// = false` is a no-op (still waiting).
+    pub fn on_quorum(&mut self, quorum_reached: bool) {
+        if !quorum_reached {
+            return;
+        }
+        self.phase = match self.phase {
+            Phase::Prepare => Phase::PreCommit,
+            Phase::PreCommit => {
+                self.locked_qc = Some(LockedQc { view: self.view });
+                Phase::Commit
+            }
+            Phase::Commit => Phase::Decide,
+            Phase::Decide => Phase::Decide,
+        };
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.phase == Phase::Decide
+    }
+}
+
+/// Explicit, loggable mode switch decision: falls back to the slow path once
+/// the network stops looking synchronous (e.g. repeated timeouts), and
+/// returns to the fast path once synchrony is observed again.
+pub fn decide_mode(current: ResponsivenessMode, consecutive_timeouts: u32, timeout_threshold: u32) -> Option<ModeSwitch> {
+    let target = if consecutive_timeouts >= timeout_threshold {
+        ResponsivenessMode::Asynchronous
+    } else {
+        ResponsivenessMode::Synchronous
+    };
+    if target == current {
+        None
+    } else {
+        Some(ModeSwitch { from: current, to: target })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_through_all_three_phases_on_repeated_quorum() {
+        let mut fallback = AsyncFallback::new(1);
+        assert_eq!(fallback.phase(), Phase::Prepare);
+        fallback.on_quorum(true);
+        assert_eq!(fallback.phase(), Phase::PreCommit);
+        fallback.on_quorum(true);
+        assert_eq!(fallback.phase(), Phase::Commit);
+        assert!(fallback.locked_qc().is_some());
+        fallback.on_quorum(true);
+        assert!(fallback.is_decided());
+    }
+
+    #[test]
+    fn does_not_advance_without_quorum() {
+        let mut fallback = AsyncFallback::new(1);
+        fallback.on_quorum(false);
+        assert_eq!(fallback.phase(), Phase::Prepare);
+    }
+
+    #[test]
+    fn switches_to_asynchronous_after_threshold_timeouts() {
+        let switch = decide_mode(ResponsivenessMode::Synchronous, 3, 3).unwrap();
+        assert_eq!(switch.to, ResponsivenessMode::Asynchronous);
+    }
+
+    #[test]
+    fn no_switch_when_mode_already_matches() {
+        assert_eq!(decide_mode(ResponsivenessMode::Synchronous, 0, 3), None);
+    }
+}
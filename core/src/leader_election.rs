@@ -0,0 +1,97 @@
+//! Leader stickiness: rotating leaders on every view change churns
+//! view-changes needlessly in WAN deployments when the current leader is
+//! performing well. `LeaderSchedule` retains a leader across a configurable
+//! `leader_stickiness_views` window, guided by per-view performance reports
+//! (rather than consulting the synchrony detector in `synchrony`), and only
+//! rotates early if the current leader is reported unhealthy.
+
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConsensusConfig {
+    /// Number of consecutive views a well-performing leader is retained
+    /// before falling back to round-robin rotation. `1` disables stickiness
+    /// (rotate every view), matching the pre-stickiness default.
+    pub leader_stickiness_views: u32,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self { leader_stickiness_views: 1 }
+    }
+}
+
+/// Per-view feedback from the synchrony/liveness layer: did the leader
+/// deliver a proposal that reached quorum before the view timed out?
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderReport {
+    Healthy,
+    TimedOut,
+}
+
+pub struct LeaderSchedule {
+    validators: Vec<ValidatorId>,
+    config: ConsensusConfig,
+    current_leader_index: usize,
+    views_retained: u32,
+}
+
+impl LeaderSchedule {
+    pub fn new(validators: Vec<ValidatorId>, config: ConsensusConfig) -> Self {
+        assert!(!validators.is_empty(), "leader schedule requires at least one validator");
+        Self { validators, config, current_leader_index: 0, views_retained: 0 }
+    }
+
+    pub fn current_leader(&self) -> ValidatorId {
+        self.validators[self.current_leader_index]
+    }
+
+    /// Called once per view, after `LeaderReport` for the view just
+    /// completed is known, to decide who leads the next view.
+    pub fn advance(&mut self, report: LeaderReport) -> ValidatorId {
+        let stays = report == LeaderReport::Healthy && self.views_retained + 1 < self.config.leader_stickiness_views;
+        if stays {
+            self.views_retained += 1;
+        } else {
+            self.current_leader_index = (self.current_leader_index + 1) % self.validators.len();
+            self.views_retained = 0;
+        }
+        self.current_leader()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_every_view_when_stickiness_is_disabled() {
+        let mut schedule = LeaderSchedule::new(vec![1, 2, 3], ConsensusConfig { leader_stickiness_views: 1 });
+        assert_eq!(schedule.current_leader(), 1);
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 2);
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 3);
+    }
+
+    #[test]
+    fn retains_a_healthy_leader_across_the_stickiness_window() {
+        let mut schedule = LeaderSchedule::new(vec![1, 2, 3], ConsensusConfig { leader_stickiness_views: 3 });
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 1);
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 1);
+        // Third healthy view exhausts the window; rotate.
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 2);
+    }
+
+    #[test]
+    fn a_timed_out_leader_is_rotated_immediately_even_inside_the_window() {
+        let mut schedule = LeaderSchedule::new(vec![1, 2, 3], ConsensusConfig { leader_stickiness_views: 5 });
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 1);
+        assert_eq!(schedule.advance(LeaderReport::TimedOut), 2);
+    }
+
+    #[test]
+    fn rotation_wraps_around_the_validator_list() {
+        let mut schedule = LeaderSchedule::new(vec![1, 2], ConsensusConfig { leader_stickiness_views: 1 });
+        schedule.advance(LeaderReport::Healthy);
+        assert_eq!(schedule.advance(LeaderReport::Healthy), 1);
+    }
+}
@@ -0,0 +1,27 @@
+pub mod block_time_scheduler;
+pub mod clock;
+pub mod empty_block_suppression;
+pub mod execution_pipeline;
+pub mod fallback;
+pub mod leader_election;
+pub mod multi_proposer;
+pub mod pacemaker;
+pub mod pipeline_tuning;
+pub mod recovery;
+pub mod synchrony;
+pub mod view_change;
+pub mod vrf_leader_election;
+
+pub use block_time_scheduler::BlockTimeScheduler;
+pub use clock::{Clock, SimulatedClock, SystemClock};
+pub use empty_block_suppression::{EmptyBlockMode, EmptyBlockPolicy};
+pub use execution_pipeline::{ExecutionPipeline, QueueFull};
+pub use pacemaker::{Pacemaker, PacemakerTimeouts, TimeoutKind, TimeoutManager, ViewChangeStormConfig, ViewChangeStormEvent};
+pub use pipeline_tuning::{PipelineDepthTuner, PipelineObservation};
+pub use fallback::{decide_mode, AsyncFallback, LockedQc, ModeSwitch, Phase, ResponsivenessMode};
+pub use leader_election::{ConsensusConfig, LeaderReport, LeaderSchedule};
+pub use multi_proposer::{eligible_proposers, select_winning_proposal, MultiProposerConfig};
+pub use recovery::{recover_from_failure, ChainState, PersistedSafetyState, RecoveryReport};
+pub use synchrony::{PerPeerSynchronyDetector, QuantileSketch, SynchronyStats};
+pub use view_change::{propose_block, propose_block_among_candidates, ForkChoiceHint, ProposalSource};
+pub use vrf_leader_election::VrfLeaderSchedule;
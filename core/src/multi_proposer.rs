@@ -0,0 +1,114 @@
+//! Experimental: `LeaderSchedule` always designates exactly one proposer per
+//! view, which bottlenecks transaction dissemination on that one node's
+//! bandwidth. `MultiProposerConfig::enabled` lets up to `max_concurrent_proposers`
+//! validators propose for the same view; `eligible_proposers` picks who
+//! (deterministically, so every honest node computes the same set without
+//! a round of coordination) and `select_winning_proposal` deterministically
+//! picks one of the concurrent proposals to vote on, by lowest node id, so
+//! the rest of the protocol still only ever certifies one block per view.
+//! Disabled by default (`MultiProposerConfig::default()`) since it's an
+//! experiment, not the standard single-leader path `LeaderSchedule` drives.
+//!
+//! This workspace has no VRF/verifiable-randomness dependency, so
+//! `eligible_proposers` ranks validators for the view by a deterministic
+//! hash of `(validator_id, view)` rather than a real VRF output — anyone
+//! can recompute anyone else's rank, which a real VRF wouldn't allow, but
+//! it gives every honest node the same eligible set without a coordination
+//! round, which is the property this mode actually needs.
+
+use hotstuff2_types::{Block, ValidatorId};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MultiProposerConfig {
+    pub enabled: bool,
+    pub max_concurrent_proposers: u32,
+}
+
+impl Default for MultiProposerConfig {
+    fn default() -> Self {
+        Self { enabled: false, max_concurrent_proposers: 1 }
+    }
+}
+
+fn rank_score(validator: ValidatorId, view: u64) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    validator.hash(&mut hasher);
+    view.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The validators eligible to propose for `view`, lowest `rank_score` first.
+/// With multi-proposer mode disabled, always returns exactly the single
+/// round-robin leader for the view (matching `LeaderSchedule`'s rotation),
+/// regardless of `max_concurrent_proposers`.
+pub fn eligible_proposers(config: &MultiProposerConfig, validators: &[ValidatorId], view: u64) -> Vec<ValidatorId> {
+    assert!(!validators.is_empty(), "eligible_proposers requires at least one validator");
+    if !config.enabled {
+        let index = (view as usize) % validators.len();
+        return vec![validators[index]];
+    }
+    let mut ranked = validators.to_vec();
+    ranked.sort_by_key(|v| rank_score(*v, view));
+    ranked.truncate(config.max_concurrent_proposers.max(1) as usize);
+    ranked
+}
+
+/// Among concurrently received proposals for the same view, deterministically
+/// picks the one every honest node will converge on voting for: lowest
+/// proposer id. Returns `None` if `proposals` is empty.
+pub fn select_winning_proposal(proposals: &[(ValidatorId, Block)]) -> Option<&(ValidatorId, Block)> {
+    proposals.iter().min_by_key(|(id, _)| *id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(view: u64) -> Block {
+        Block { parent_hash: [0u8; 32], height: 1, view, transactions: vec![] }
+    }
+
+    #[test]
+    fn disabled_mode_always_yields_exactly_one_round_robin_proposer() {
+        let config = MultiProposerConfig::default();
+        assert_eq!(eligible_proposers(&config, &[1, 2, 3], 0), vec![1]);
+        assert_eq!(eligible_proposers(&config, &[1, 2, 3], 1), vec![2]);
+    }
+
+    #[test]
+    fn enabled_mode_yields_up_to_the_configured_concurrency() {
+        let config = MultiProposerConfig { enabled: true, max_concurrent_proposers: 2 };
+        let eligible = eligible_proposers(&config, &[1, 2, 3, 4, 5], 7);
+        assert_eq!(eligible.len(), 2);
+    }
+
+    #[test]
+    fn enabled_mode_is_deterministic_across_independent_calls() {
+        let config = MultiProposerConfig { enabled: true, max_concurrent_proposers: 3 };
+        let a = eligible_proposers(&config, &[1, 2, 3, 4, 5], 42);
+        let b = eligible_proposers(&config, &[1, 2, 3, 4, 5], 42);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn concurrency_is_capped_by_the_number_of_validators() {
+        let config = MultiProposerConfig { enabled: true, max_concurrent_proposers: 10 };
+        let eligible = eligible_proposers(&config, &[1, 2, 3], 0);
+        assert_eq!(eligible.len(), 3);
+    }
+
+    #[test]
+    fn the_winning_proposal_is_the_one_from_the_lowest_proposer_id() {
+        let proposals = vec![(5, block(1)), (2, block(1)), (9, block(1))];
+        let (winner_id, _) = select_winning_proposal(&proposals).unwrap();
+        assert_eq!(*winner_id, 2);
+    }
+
+    #[test]
+    fn no_proposals_selects_nothing() {
+        assert!(select_winning_proposal(&[]).is_none());
+    }
+}
@@ -0,0 +1,387 @@
+//! The pacemaker used one timeout for everything: a follower waiting on a
+//! proposal, a leader waiting on votes, and everyone waiting on the QC
+//! broadcast all shared the same budget and the same backoff, so a slow
+//! vote round inflated the proposal timeout too and vice versa, muddying
+//! responsiveness diagnostics. `Pacemaker` tracks the three independently,
+//! each adapted on its own timeout/success feedback from the synchrony
+//! detector.
+//!
+//! With `max_view_changes` unenforced anywhere, a partition that kept
+//! forcing view changes had no defined behavior: timeouts kept climbing per
+//! `TimeoutKind` but nothing stopped the churn itself. `on_view_change`
+//! tracks the consecutive-view-change streak across kinds and, once it
+//! crosses `ViewChangeStormConfig::max_consecutive`, enters a cool-down that
+//! widens every timeout by `cooldown_multiplier` and reports
+//! `optimistic_mode_should_pause() == true` so the fast path backs off
+//! until a view finally completes. There is no metrics crate wired into
+//! this crate, so `on_view_change` returns a `ViewChangeStormEvent` the
+//! caller forwards to whatever event sink it has, matching the pattern
+//! already used by `consensus::byzantine_detector` and `consensus::fork_audit`.
+
+use std::collections::HashMap;
+
+use crate::clock::Clock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutKind {
+    /// A follower waiting on the leader's proposal for the current view.
+    AwaitingProposal,
+    /// A leader waiting on a quorum of votes for its proposal.
+    AwaitingVotes,
+    /// Waiting on the aggregated QC to be broadcast after quorum is reached.
+    AwaitingQcBroadcast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacemakerTimeouts {
+    pub proposal_ms: u64,
+    pub votes_ms: u64,
+    pub qc_broadcast_ms: u64,
+}
+
+impl PacemakerTimeouts {
+    pub fn get(&self, kind: TimeoutKind) -> u64 {
+        match kind {
+            TimeoutKind::AwaitingProposal => self.proposal_ms,
+            TimeoutKind::AwaitingVotes => self.votes_ms,
+            TimeoutKind::AwaitingQcBroadcast => self.qc_broadcast_ms,
+        }
+    }
+
+    fn set(&mut self, kind: TimeoutKind, value: u64) {
+        match kind {
+            TimeoutKind::AwaitingProposal => self.proposal_ms = value,
+            TimeoutKind::AwaitingVotes => self.votes_ms = value,
+            TimeoutKind::AwaitingQcBroadcast => self.qc_broadcast_ms = value,
+        }
+    }
+}
+
+/// Threshold and response for the view-change circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewChangeStormConfig {
+    /// Consecutive view changes (with no completed view in between) that
+    /// trip the breaker.
+    pub max_consecutive: u32,
+    /// Every timeout is multiplied by this while in cool-down, on top of
+    /// its normal `on_timeout` doubling, still capped at `max_ms`.
+    pub cooldown_multiplier: u64,
+}
+
+/// Reported by `on_view_change` so the caller can forward it to whatever
+/// event/metrics sink it has; this crate has none wired in directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewChangeStormEvent {
+    /// The breaker just tripped on this call.
+    Entered { consecutive_view_changes: u32 },
+    /// Already tripped; still churning.
+    Ongoing { consecutive_view_changes: u32 },
+    /// Not (or no longer) in a storm.
+    Normal,
+}
+
+/// Adapts each `TimeoutKind`'s budget independently: doubles on a timeout
+/// (capped at `max_ms`), halves back down on success (floored at `min_ms`).
+pub struct Pacemaker {
+    timeouts: PacemakerTimeouts,
+    min_ms: u64,
+    max_ms: u64,
+    /// Per-kind timeout streak, exposed for responsiveness diagnostics so
+    /// an operator can see which specific wait is degrading.
+    consecutive_timeouts: HashMap<TimeoutKind, u32>,
+    storm_config: Option<ViewChangeStormConfig>,
+    consecutive_view_changes: u32,
+    in_cooldown: bool,
+}
+
+impl Pacemaker {
+    pub fn new(timeouts: PacemakerTimeouts, min_ms: u64, max_ms: u64) -> Self {
+        Self {
+            timeouts,
+            min_ms,
+            max_ms,
+            consecutive_timeouts: HashMap::new(),
+            storm_config: None,
+            consecutive_view_changes: 0,
+            in_cooldown: false,
+        }
+    }
+
+    /// Enables the view-change storm breaker; without this, `on_view_change`
+    /// still tracks the streak but never trips.
+    pub fn with_storm_breaker(mut self, config: ViewChangeStormConfig) -> Self {
+        self.storm_config = Some(config);
+        self
+    }
+
+    pub fn timeouts(&self) -> PacemakerTimeouts {
+        self.timeouts
+    }
+
+    pub fn timeout_for(&self, kind: TimeoutKind) -> u64 {
+        self.timeouts.get(kind)
+    }
+
+    pub fn consecutive_timeouts(&self, kind: TimeoutKind) -> u32 {
+        self.consecutive_timeouts.get(&kind).copied().unwrap_or(0)
+    }
+
+    pub fn on_timeout(&mut self, kind: TimeoutKind) {
+        let doubled = self.timeouts.get(kind).saturating_mul(2).min(self.max_ms);
+        self.timeouts.set(kind, doubled);
+        *self.consecutive_timeouts.entry(kind).or_insert(0) += 1;
+    }
+
+    pub fn on_success(&mut self, kind: TimeoutKind) {
+        let halved = (self.timeouts.get(kind) / 2).max(self.min_ms);
+        self.timeouts.set(kind, halved);
+        self.consecutive_timeouts.insert(kind, 0);
+    }
+
+    /// Records that the current view failed to commit and a new view
+    /// started, independent of which `TimeoutKind` triggered it. Once the
+    /// streak crosses `ViewChangeStormConfig::max_consecutive`, widens every
+    /// timeout by `cooldown_multiplier` and enters cool-down.
+    pub fn on_view_change(&mut self) -> ViewChangeStormEvent {
+        self.consecutive_view_changes += 1;
+        let Some(config) = self.storm_config else {
+            return ViewChangeStormEvent::Normal;
+        };
+        if self.consecutive_view_changes < config.max_consecutive {
+            return ViewChangeStormEvent::Normal;
+        }
+        let already_tripped = self.in_cooldown;
+        self.in_cooldown = true;
+        for kind in [TimeoutKind::AwaitingProposal, TimeoutKind::AwaitingVotes, TimeoutKind::AwaitingQcBroadcast] {
+            let widened = self.timeouts.get(kind).saturating_mul(config.cooldown_multiplier).min(self.max_ms);
+            self.timeouts.set(kind, widened);
+        }
+        if already_tripped {
+            ViewChangeStormEvent::Ongoing { consecutive_view_changes: self.consecutive_view_changes }
+        } else {
+            ViewChangeStormEvent::Entered { consecutive_view_changes: self.consecutive_view_changes }
+        }
+    }
+
+    /// Records that a view committed successfully, resetting the
+    /// view-change streak and exiting cool-down.
+    pub fn on_view_completed(&mut self) {
+        self.consecutive_view_changes = 0;
+        self.in_cooldown = false;
+    }
+
+    /// True while the storm breaker is tripped; the optimistic fast path
+    /// should pause while this holds.
+    pub fn optimistic_mode_should_pause(&self) -> bool {
+        self.in_cooldown
+    }
+}
+
+/// Turns a `Pacemaker`'s durations into actual deadlines against a `Clock`,
+/// so a caller can ask "has this timer fired yet" without hand-rolling
+/// `now_ms + duration` bookkeeping at every call site. Generic over `Clock`
+/// so production code arms this against a `SystemClock` and tests arm it
+/// against a `SimulatedClock` and advance time explicitly, rather than
+/// sleeping for real.
+pub struct TimeoutManager<C: Clock> {
+    clock: C,
+    deadlines: HashMap<TimeoutKind, u64>,
+}
+
+impl<C: Clock> TimeoutManager<C> {
+    pub fn new(clock: C) -> Self {
+        Self { clock, deadlines: HashMap::new() }
+    }
+
+    /// Arms `kind`'s deadline at `now + pacemaker.timeout_for(kind)`.
+    pub fn arm(&mut self, kind: TimeoutKind, pacemaker: &Pacemaker) {
+        let deadline = self.clock.now_ms().saturating_add(pacemaker.timeout_for(kind));
+        self.deadlines.insert(kind, deadline);
+    }
+
+    /// Clears `kind`'s deadline, e.g. once its wait condition is satisfied
+    /// before it fires.
+    pub fn disarm(&mut self, kind: TimeoutKind) {
+        self.deadlines.remove(&kind);
+    }
+
+    /// True once the clock has reached or passed `kind`'s armed deadline.
+    /// An unarmed kind is never expired.
+    pub fn is_expired(&self, kind: TimeoutKind) -> bool {
+        match self.deadlines.get(&kind) {
+            Some(deadline) => self.clock.now_ms() >= *deadline,
+            None => false,
+        }
+    }
+
+    pub fn is_armed(&self, kind: TimeoutKind) -> bool {
+        self.deadlines.contains_key(&kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::SimulatedClock;
+
+    fn timeouts() -> PacemakerTimeouts {
+        PacemakerTimeouts { proposal_ms: 100, votes_ms: 100, qc_broadcast_ms: 100 }
+    }
+
+    #[test]
+    fn a_timeout_on_one_kind_does_not_affect_the_others() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        pacemaker.on_timeout(TimeoutKind::AwaitingVotes);
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingVotes), 200);
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingProposal), 100);
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingQcBroadcast), 100);
+    }
+
+    #[test]
+    fn repeated_timeouts_cap_at_the_configured_maximum() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 300);
+        for _ in 0..5 {
+            pacemaker.on_timeout(TimeoutKind::AwaitingProposal);
+        }
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingProposal), 300);
+    }
+
+    #[test]
+    fn success_halves_the_timeout_back_down_and_resets_the_streak() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        pacemaker.on_timeout(TimeoutKind::AwaitingQcBroadcast);
+        assert_eq!(pacemaker.consecutive_timeouts(TimeoutKind::AwaitingQcBroadcast), 1);
+        pacemaker.on_success(TimeoutKind::AwaitingQcBroadcast);
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingQcBroadcast), 100);
+        assert_eq!(pacemaker.consecutive_timeouts(TimeoutKind::AwaitingQcBroadcast), 0);
+    }
+
+    #[test]
+    fn success_never_shrinks_below_the_configured_minimum() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 90, 800);
+        pacemaker.on_success(TimeoutKind::AwaitingProposal);
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingProposal), 90);
+    }
+
+    fn storm_config() -> ViewChangeStormConfig {
+        ViewChangeStormConfig { max_consecutive: 3, cooldown_multiplier: 4 }
+    }
+
+    #[test]
+    fn without_a_storm_breaker_configured_view_changes_never_trip_cooldown() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        for _ in 0..10 {
+            assert_eq!(pacemaker.on_view_change(), ViewChangeStormEvent::Normal);
+        }
+        assert!(!pacemaker.optimistic_mode_should_pause());
+    }
+
+    #[test]
+    fn below_the_threshold_view_changes_are_reported_as_normal() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800).with_storm_breaker(storm_config());
+        assert_eq!(pacemaker.on_view_change(), ViewChangeStormEvent::Normal);
+        assert_eq!(pacemaker.on_view_change(), ViewChangeStormEvent::Normal);
+        assert!(!pacemaker.optimistic_mode_should_pause());
+    }
+
+    #[test]
+    fn crossing_the_threshold_enters_cooldown_and_widens_timeouts() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800).with_storm_breaker(storm_config());
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        let event = pacemaker.on_view_change();
+        assert_eq!(event, ViewChangeStormEvent::Entered { consecutive_view_changes: 3 });
+        assert!(pacemaker.optimistic_mode_should_pause());
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingProposal), 400);
+    }
+
+    #[test]
+    fn continued_churn_after_tripping_is_reported_as_ongoing() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800).with_storm_breaker(storm_config());
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        let event = pacemaker.on_view_change();
+        assert_eq!(event, ViewChangeStormEvent::Ongoing { consecutive_view_changes: 4 });
+    }
+
+    #[test]
+    fn a_completed_view_resets_the_streak_and_exits_cooldown() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800).with_storm_breaker(storm_config());
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        assert!(pacemaker.optimistic_mode_should_pause());
+        pacemaker.on_view_completed();
+        assert!(!pacemaker.optimistic_mode_should_pause());
+        assert_eq!(pacemaker.on_view_change(), ViewChangeStormEvent::Normal);
+    }
+
+    #[test]
+    fn cooldown_widening_is_capped_at_the_configured_maximum() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 300).with_storm_breaker(storm_config());
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        pacemaker.on_view_change();
+        assert_eq!(pacemaker.timeout_for(TimeoutKind::AwaitingProposal), 300);
+    }
+
+    #[test]
+    fn an_unarmed_timer_is_never_expired() {
+        let manager = TimeoutManager::new(SimulatedClock::new(0));
+        assert!(!manager.is_armed(TimeoutKind::AwaitingProposal));
+        assert!(!manager.is_expired(TimeoutKind::AwaitingProposal));
+    }
+
+    #[test]
+    fn a_timer_expires_once_the_clock_reaches_its_deadline() {
+        let clock = SimulatedClock::new(0);
+        let pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        let mut manager = TimeoutManager::new(clock);
+        manager.arm(TimeoutKind::AwaitingVotes, &pacemaker);
+        assert!(manager.is_armed(TimeoutKind::AwaitingVotes));
+        assert!(!manager.is_expired(TimeoutKind::AwaitingVotes));
+
+        manager.clock.advance(100);
+        assert!(manager.is_expired(TimeoutKind::AwaitingVotes));
+    }
+
+    #[test]
+    fn advancing_the_simulated_clock_past_the_deadline_expires_the_timer() {
+        let pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        let mut manager = TimeoutManager::new(SimulatedClock::new(0));
+        manager.arm(TimeoutKind::AwaitingProposal, &pacemaker);
+        assert!(!manager.is_expired(TimeoutKind::AwaitingProposal));
+
+        manager.clock.advance(99);
+        assert!(!manager.is_expired(TimeoutKind::AwaitingProposal));
+
+        manager.clock.advance(1);
+        assert!(manager.is_expired(TimeoutKind::AwaitingProposal));
+    }
+
+    #[test]
+    fn disarming_a_timer_makes_it_report_unexpired_again() {
+        let clock = SimulatedClock::new(0);
+        let pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        let mut manager = TimeoutManager::new(clock);
+        manager.arm(TimeoutKind::AwaitingQcBroadcast, &pacemaker);
+        manager.disarm(TimeoutKind::AwaitingQcBroadcast);
+        assert!(!manager.is_armed(TimeoutKind::AwaitingQcBroadcast));
+        assert!(!manager.is_expired(TimeoutKind::AwaitingQcBroadcast));
+    }
+
+    #[test]
+    fn a_widened_pacemaker_timeout_after_a_failure_delays_expiry_further() {
+        let mut pacemaker = Pacemaker::new(timeouts(), 50, 800);
+        pacemaker.on_timeout(TimeoutKind::AwaitingProposal); // 100ms -> 200ms
+        let mut manager = TimeoutManager::new(SimulatedClock::new(0));
+        manager.arm(TimeoutKind::AwaitingProposal, &pacemaker);
+
+        manager.clock.advance(150);
+        assert!(!manager.is_expired(TimeoutKind::AwaitingProposal));
+
+        manager.clock.advance(50);
+        assert!(manager.is_expired(TimeoutKind::AwaitingProposal));
+    }
+}
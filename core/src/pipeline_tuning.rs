@@ -0,0 +1,101 @@
+//! `pipeline_depth` used to be fixed at startup with no way to react to
+//! runtime conditions: a depth tuned for a quiet network stalls under
+//! contention (too many in-flight blocks racing view changes) and a depth
+//! tuned for contention leaves latency on the table when the network is
+//! healthy. `PipelineDepthTuner` adjusts the effective depth from observed
+//! commit latency and the view-change (timeout) rate, shrinking
+//! aggressively on view changes and growing back cautiously once things
+//! settle.
+
+/// One round's outcome, as observed by whatever drives the pipeline.
+pub struct PipelineObservation {
+    pub commit_latency_ms: u64,
+    pub view_changed: bool,
+}
+
+pub struct PipelineDepthTuner {
+    min_depth: u32,
+    max_depth: u32,
+    latency_budget_ms: u64,
+    current_depth: u32,
+    /// Set via the admin API to pin the depth regardless of observations.
+    manual_override: Option<u32>,
+}
+
+impl PipelineDepthTuner {
+    pub fn new(min_depth: u32, max_depth: u32, latency_budget_ms: u64) -> Self {
+        Self {
+            min_depth,
+            max_depth,
+            latency_budget_ms,
+            current_depth: max_depth,
+            manual_override: None,
+        }
+    }
+
+    /// Shrinks by half (floored at `min_depth`) on a view change, since a
+    /// timeout under high pipeline depth means too many blocks are
+    /// in-flight for the network to certify in time. Otherwise grows by one
+    /// step when latency is comfortably under budget, and holds steady
+    /// otherwise.
+    pub fn observe(&mut self, observation: PipelineObservation) {
+        if observation.view_changed {
+            self.current_depth = (self.current_depth / 2).max(self.min_depth);
+        } else if observation.commit_latency_ms < self.latency_budget_ms {
+            self.current_depth = (self.current_depth + 1).min(self.max_depth);
+        }
+    }
+
+    pub fn set_override(&mut self, depth: Option<u32>) {
+        self.manual_override = depth;
+    }
+
+    /// The depth callers should actually use: the admin override if one is
+    /// set, otherwise the auto-tuned value.
+    pub fn effective_depth(&self) -> u32 {
+        self.manual_override.unwrap_or(self.current_depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_the_maximum_depth() {
+        let tuner = PipelineDepthTuner::new(1, 8, 200);
+        assert_eq!(tuner.effective_depth(), 8);
+    }
+
+    #[test]
+    fn a_view_change_halves_the_depth() {
+        let mut tuner = PipelineDepthTuner::new(1, 8, 200);
+        tuner.observe(PipelineObservation { commit_latency_ms: 50, view_changed: true });
+        assert_eq!(tuner.effective_depth(), 4);
+    }
+
+    #[test]
+    fn depth_never_shrinks_below_the_configured_minimum() {
+        let mut tuner = PipelineDepthTuner::new(2, 8, 200);
+        for _ in 0..10 {
+            tuner.observe(PipelineObservation { commit_latency_ms: 50, view_changed: true });
+        }
+        assert_eq!(tuner.effective_depth(), 2);
+    }
+
+    #[test]
+    fn low_latency_grows_depth_back_up_after_a_shrink() {
+        let mut tuner = PipelineDepthTuner::new(1, 8, 200);
+        tuner.observe(PipelineObservation { commit_latency_ms: 50, view_changed: true }); // -> 4
+        tuner.observe(PipelineObservation { commit_latency_ms: 50, view_changed: false }); // -> 5
+        assert_eq!(tuner.effective_depth(), 5);
+    }
+
+    #[test]
+    fn a_manual_override_takes_precedence_over_the_tuned_value() {
+        let mut tuner = PipelineDepthTuner::new(1, 8, 200);
+        tuner.set_override(Some(3));
+        tuner.observe(PipelineObservation { commit_latency_ms: 50, view_changed: false });
+        assert_eq!(tuner.effective_depth(), 3);
+    }
+}
@@ -0,0 +1,101 @@
+//! Real restart recovery: on startup, load the highest committed block,
+//! high QC, and safety state from storage, reconstruct `ChainState` and
+//! `current_view`, and report which blocks are missing so the caller can
+//! request them from peers. Previously `recover_from_failure` was a
+//! placeholder that returned `Ok(())` and left the node at view 0.
+
+use hotstuff2_types::QuorumCertificate;
+
+#[derive(Debug, Clone)]
+pub struct PersistedSafetyState {
+    pub highest_committed_height: u64,
+    pub high_qc: Option<QuorumCertificate>,
+    pub locked_view: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainState {
+    pub current_view: u64,
+    pub committed_height: u64,
+    pub locked_view: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryReport {
+    pub chain_state: ChainState,
+    /// Heights the recovered state references but that storage doesn't have
+    /// a block for (e.g. the block certified by `high_qc`, if pruned or
+    /// never fully persisted) — the caller should request these from peers.
+    pub missing_heights: Vec<u64>,
+}
+
+/// Reconstructs `ChainState` from whatever storage persisted, instead of
+/// resetting to view 0. `has_block_at` lets the caller answer "do I have
+/// this height locally?" without this crate depending on a storage backend.
+pub fn recover_from_failure(state: &PersistedSafetyState, has_block_at: impl Fn(u64) -> bool) -> RecoveryReport {
+    let high_qc_view = state.high_qc.as_ref().map(|qc| qc.view).unwrap_or(0);
+    let current_view = high_qc_view.max(state.locked_view).max(state.highest_committed_height) + 1;
+
+    let mut missing_heights = Vec::new();
+    if let Some(qc) = &state.high_qc {
+        // The QC's view doesn't map 1:1 to height, but a high QC always
+        // certifies a block at or after the highest committed height; check
+        // every height from there up through the committed height, plus one
+        // ahead for the uncommitted certified block.
+        for height in state.highest_committed_height..=state.highest_committed_height + 1 {
+            if !has_block_at(height) {
+                missing_heights.push(height);
+            }
+        }
+        let _ = qc; // qc.view already folded into current_view above
+    }
+
+    RecoveryReport {
+        chain_state: ChainState {
+            current_view,
+            committed_height: state.highest_committed_height,
+            locked_view: state.locked_view,
+        },
+        missing_heights,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumes_at_a_view_past_the_high_qc_not_view_zero() {
+        let state = PersistedSafetyState {
+            highest_committed_height: 10,
+            high_qc: Some(QuorumCertificate { block_hash: [1u8; 32], view: 12, signers: vec![1, 2, 3] }),
+            locked_view: 11,
+        };
+        let report = recover_from_failure(&state, |_| true);
+        assert!(report.chain_state.current_view > 12);
+        assert_eq!(report.chain_state.committed_height, 10);
+    }
+
+    #[test]
+    fn flags_missing_blocks_for_peer_fetch() {
+        let state = PersistedSafetyState {
+            highest_committed_height: 10,
+            high_qc: Some(QuorumCertificate { block_hash: [1u8; 32], view: 12, signers: vec![1, 2, 3] }),
+            locked_view: 11,
+        };
+        let report = recover_from_failure(&state, |h| h != 11);
+        assert_eq!(report.missing_heights, vec![11]);
+    }
+
+    #[test]
+    fn a_fresh_node_with_no_qc_recovers_to_view_one() {
+        let state = PersistedSafetyState {
+            highest_committed_height: 0,
+            high_qc: None,
+            locked_view: 0,
+        };
+        let report = recover_from_failure(&state, |_| true);
+        assert_eq!(report.chain_state.current_view, 1);
+        assert!(report.missing_heights.is_empty());
+    }
+}
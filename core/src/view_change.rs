@@ -0,0 +1,191 @@
+//! Per the HotStuff-2 paper, a new leader after a view change must re-propose
+//! the block certified by the highest QC it knows about rather than minting a
+//! fresh one, so an in-flight block isn't silently abandoned (which used to
+//! leave gaps in committed height).
+//!
+//! A Byzantine former leader can get two conflicting blocks each certified by
+//! a distinct sub-quorum at the same QC view — safety only says the highest
+//! *view* wins, it's silent on which block when several sit at that same
+//! highest view. `propose_block_among_candidates` lets the embedding
+//! application break that tie with a `ForkChoiceHint`, but the hint only ever
+//! sees the already-safety-filtered tied set: it can prefer, say, the
+//! candidate carrying a specific operator transaction, but it can never make
+//! the leader carry over a block certified at a lower view than another
+//! candidate on hand.
+
+use hotstuff2_types::{Block, QuorumCertificate};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProposalSource {
+    /// Re-proposing the block certified by the highest known QC.
+    CarriedOver { qc_view: u64 },
+    /// Multiple blocks were certified at the same highest QC view; the
+    /// application's `ForkChoiceHint` picked among them.
+    CarriedOverByHint { qc_view: u64 },
+    /// No uncommitted certified block exists; safe to mint a fresh one.
+    Fresh,
+}
+
+/// Called only among candidates already tied for the highest QC view, to
+/// pick which one the leader re-proposes. Must return an index into
+/// `candidates`; an out-of-range index is treated as "no preference" and
+/// falls back to the first candidate.
+pub trait ForkChoiceHint {
+    fn choose(&self, candidates: &[(QuorumCertificate, Block)]) -> usize;
+}
+
+impl<F: Fn(&[(QuorumCertificate, Block)]) -> usize> ForkChoiceHint for F {
+    fn choose(&self, candidates: &[(QuorumCertificate, Block)]) -> usize {
+        self(candidates)
+    }
+}
+
+/// Decides what the new leader should propose for `new_view`. Returns the
+/// carried-over block (unchanged) plus which path was taken, so callers can
+/// log/metric the fast-path vs. carry-over distinction the request asks for.
+pub fn propose_block(
+    new_view: u64,
+    highest_qc: Option<&QuorumCertificate>,
+    highest_qc_block: Option<&Block>,
+    mint_fresh: impl FnOnce(u64) -> Block,
+) -> (Block, ProposalSource) {
+    match (highest_qc, highest_qc_block) {
+        (Some(qc), Some(block)) if block.hash() == qc.block_hash => {
+            let mut carried = block.clone();
+            carried.view = new_view;
+            (carried, ProposalSource::CarriedOver { qc_view: qc.view })
+        }
+        _ => (mint_fresh(new_view), ProposalSource::Fresh),
+    }
+}
+
+/// Like `propose_block`, but for the case where the leader knows of several
+/// `(QuorumCertificate, Block)` pairs — each block matching its QC's
+/// `block_hash` — rather than a single highest one. Narrows to whichever
+/// have the maximum `qc.view` first (the only safety-relevant ordering);
+/// if more than one remains tied, `hint` (when given) picks among exactly
+/// that tied set, never among the full candidate list.
+pub fn propose_block_among_candidates(
+    new_view: u64,
+    candidates: &[(QuorumCertificate, Block)],
+    hint: Option<&dyn ForkChoiceHint>,
+    mint_fresh: impl FnOnce(u64) -> Block,
+) -> (Block, ProposalSource) {
+    let Some(highest_view) = candidates.iter().map(|(qc, _)| qc.view).max() else {
+        return (mint_fresh(new_view), ProposalSource::Fresh);
+    };
+    let tied: Vec<(QuorumCertificate, Block)> =
+        candidates.iter().filter(|(qc, _)| qc.view == highest_view).cloned().collect();
+
+    let (chosen_block, source) = if tied.len() == 1 {
+        let (qc, block) = tied.into_iter().next().unwrap();
+        (block, ProposalSource::CarriedOver { qc_view: qc.view })
+    } else {
+        let index = hint.map(|h| h.choose(&tied)).unwrap_or(0);
+        let index = if index < tied.len() { index } else { 0 };
+        let (qc, block) = tied[index].clone();
+        (block, ProposalSource::CarriedOverByHint { qc_view: qc.view })
+    };
+
+    let mut carried = chosen_block;
+    carried.view = new_view;
+    (carried, source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::Transaction;
+
+    fn block(view: u64, txs: Vec<Transaction>) -> Block {
+        Block {
+            parent_hash: [0u8; 32],
+            height: 1,
+            view,
+            transactions: txs,
+        }
+    }
+
+    #[test]
+    fn carries_over_the_highest_qc_block_instead_of_minting_fresh() {
+        let uncommitted = block(3, vec![Transaction { id: [9u8; 32], payload: vec![1, 2, 3], weight: 1, valid_until: None }]);
+        let qc = QuorumCertificate {
+            block_hash: uncommitted.hash(),
+            view: 3,
+            signers: vec![1, 2, 3],
+        };
+
+        let (proposed, source) = propose_block(4, Some(&qc), Some(&uncommitted), |v| block(v, vec![]));
+
+        assert_eq!(source, ProposalSource::CarriedOver { qc_view: 3 });
+        assert_eq!(proposed.transactions, uncommitted.transactions);
+        assert_eq!(proposed.view, 4);
+    }
+
+    #[test]
+    fn mints_a_fresh_block_when_there_is_no_certified_block() {
+        let (proposed, source) = propose_block(1, None, None, |v| block(v, vec![]));
+        assert_eq!(source, ProposalSource::Fresh);
+        assert_eq!(proposed.view, 1);
+    }
+
+    #[test]
+    fn mints_fresh_when_the_qc_does_not_match_the_cached_block() {
+        let stale_cached = block(2, vec![]);
+        let qc = QuorumCertificate {
+            block_hash: [42u8; 32], // does not match stale_cached.hash()
+            view: 2,
+            signers: vec![1, 2, 3],
+        };
+        let (_, source) = propose_block(3, Some(&qc), Some(&stale_cached), |v| block(v, vec![]));
+        assert_eq!(source, ProposalSource::Fresh);
+    }
+
+    fn candidate(view: u64, marker: u8) -> (QuorumCertificate, Block) {
+        let b = block(view, vec![Transaction { id: [marker; 32], payload: vec![marker], weight: 1, valid_until: None }]);
+        let qc = QuorumCertificate { block_hash: b.hash(), view, signers: vec![1, 2, 3] };
+        (qc, b)
+    }
+
+    #[test]
+    fn a_single_highest_view_candidate_is_carried_over_without_consulting_a_hint() {
+        let candidates = vec![candidate(2, 1), candidate(5, 2)];
+        let (proposed, source) = propose_block_among_candidates(6, &candidates, None, |v| block(v, vec![]));
+        assert_eq!(source, ProposalSource::CarriedOver { qc_view: 5 });
+        assert_eq!(proposed.transactions[0].payload, vec![2]);
+    }
+
+    #[test]
+    fn a_tie_at_the_highest_view_without_a_hint_defaults_to_the_first_candidate() {
+        let candidates = vec![candidate(5, 1), candidate(5, 2)];
+        let (proposed, source) = propose_block_among_candidates(6, &candidates, None, |v| block(v, vec![]));
+        assert_eq!(source, ProposalSource::CarriedOverByHint { qc_view: 5 });
+        assert_eq!(proposed.transactions[0].payload, vec![1]);
+    }
+
+    #[test]
+    fn a_hint_breaks_a_tie_among_only_the_highest_view_candidates() {
+        let candidates = vec![candidate(2, 9), candidate(5, 1), candidate(5, 2)];
+        let hint = |tied: &[(QuorumCertificate, Block)]| -> usize {
+            tied.iter().position(|(_, b)| b.transactions[0].payload == vec![2]).unwrap()
+        };
+        let (proposed, source) = propose_block_among_candidates(6, &candidates, Some(&hint), |v| block(v, vec![]));
+        assert_eq!(source, ProposalSource::CarriedOverByHint { qc_view: 5 });
+        assert_eq!(proposed.transactions[0].payload, vec![2]);
+    }
+
+    #[test]
+    fn an_out_of_range_hint_choice_falls_back_to_the_first_tied_candidate() {
+        let candidates = vec![candidate(5, 1), candidate(5, 2)];
+        let hint = |_: &[(QuorumCertificate, Block)]| -> usize { 99 };
+        let (proposed, _) = propose_block_among_candidates(6, &candidates, Some(&hint), |v| block(v, vec![]));
+        assert_eq!(proposed.transactions[0].payload, vec![1]);
+    }
+
+    #[test]
+    fn no_candidates_mints_fresh() {
+        let (proposed, source) = propose_block_among_candidates(1, &[], None, |v| block(v, vec![]));
+        assert_eq!(source, ProposalSource::Fresh);
+        assert_eq!(proposed.view, 1);
+    }
+}
@@ -0,0 +1,76 @@
+//! `LeaderSchedule` rotates leaders round-robin, so anyone who knows the
+//! validator list can compute every future leader arbitrarily far ahead —
+//! enough lead time for an attacker to stage a targeted DoS against next
+//! epoch's leader before it's ever proposed anything. `VrfLeaderSchedule`
+//! instead selects the leader for view `v` by evaluating `hotstuff2_crypto`'s
+//! VRF over `(epoch, v)` for every validator and picking the lowest output,
+//! so the schedule can't be computed by anyone without every validator's
+//! key (see `hotstuff2_crypto::vrf` for the exact disclosed limitation of
+//! that hash-based VRF stand-in).
+
+use hotstuff2_crypto::vrf::{evaluate, VrfKey};
+use hotstuff2_types::ValidatorId;
+
+pub struct VrfLeaderSchedule {
+    /// Each validator's VRF key, in the same order as `validators`.
+    validators: Vec<(ValidatorId, VrfKey)>,
+    epoch: u64,
+}
+
+impl VrfLeaderSchedule {
+    pub fn new(validators: Vec<(ValidatorId, VrfKey)>, epoch: u64) -> Self {
+        assert!(!validators.is_empty(), "VRF leader schedule requires at least one validator");
+        Self { validators, epoch }
+    }
+
+    /// The leader for `view`: the validator whose VRF output over
+    /// `(epoch, view)` is lowest. Every honest node with every validator's
+    /// key computes the same answer; nobody without those keys can predict
+    /// it ahead of time.
+    pub fn leader_for_view(&self, view: u64) -> ValidatorId {
+        self.validators
+            .iter()
+            .map(|(id, key)| (*id, evaluate(key, self.epoch, view).value))
+            .min_by_key(|(_, value)| *value)
+            .map(|(id, _)| id)
+            .expect("validators is non-empty")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> VrfLeaderSchedule {
+        VrfLeaderSchedule::new(vec![(1, [1u8; 32]), (2, [2u8; 32]), (3, [3u8; 32])], 7)
+    }
+
+    #[test]
+    fn the_same_view_always_selects_the_same_leader() {
+        let schedule = schedule();
+        assert_eq!(schedule.leader_for_view(10), schedule.leader_for_view(10));
+    }
+
+    #[test]
+    fn different_views_can_select_different_leaders() {
+        let schedule = schedule();
+        let leaders: std::collections::HashSet<_> = (0..20).map(|v| schedule.leader_for_view(v)).collect();
+        assert!(leaders.len() > 1, "expected the schedule to vary across views, got {:?}", leaders);
+    }
+
+    #[test]
+    fn a_different_epoch_can_change_the_selected_leader() {
+        let a = VrfLeaderSchedule::new(vec![(1, [1u8; 32]), (2, [2u8; 32]), (3, [3u8; 32])], 1);
+        let b = VrfLeaderSchedule::new(vec![(1, [1u8; 32]), (2, [2u8; 32]), (3, [3u8; 32])], 2);
+        let leaders_a: Vec<_> = (0..10).map(|v| a.leader_for_view(v)).collect();
+        let leaders_b: Vec<_> = (0..10).map(|v| b.leader_for_view(v)).collect();
+        assert_ne!(leaders_a, leaders_b);
+    }
+
+    #[test]
+    fn a_single_validator_is_always_its_own_leader() {
+        let schedule = VrfLeaderSchedule::new(vec![(9, [5u8; 32])], 3);
+        assert_eq!(schedule.leader_for_view(0), 9);
+        assert_eq!(schedule.leader_for_view(100), 9);
+    }
+}
@@ -0,0 +1,126 @@
+//! There's no real BLS/Ed25519 dependency in this workspace, so
+//! `HashBasedSigner`'s signatures are already a hash-based stand-in (see
+//! `types::ValidatorSet::verify_threshold`'s doc comment) rather than a real
+//! signature scheme. But that stand-in still does real, nonzero hashing
+//! work per call, which shows up in throughput profiles the same way real
+//! signing cost would — making it impossible to tell whether a benchmark's
+//! bottleneck is signing or the surrounding protocol/IO path. The
+//! `no-crypto` feature swaps in `NoOpSigner`, which does no work at all, so
+//! `default_signer()` lets a benchmark isolate one cost from the other by
+//! toggling a `--features` flag instead of hand-patching call sites.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+pub trait KeySigner {
+    fn generate_keypair(&self, seed: u64) -> [u8; 32];
+    fn sign(&self, key: &[u8; 32], message: &[u8]) -> [u8; 64];
+    fn verify(&self, key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool;
+}
+
+/// Rounds of hashing `sign`/`verify` perform, standing in for the
+/// nontrivial CPU cost a real signature scheme would spend per operation.
+const SIMULATED_WORK_ROUNDS: u32 = 64;
+
+fn hash_rounds(seed: u64, rounds: u32) -> u64 {
+    let mut digest = seed;
+    for _ in 0..rounds {
+        let mut hasher = DefaultHasher::new();
+        digest.hash(&mut hasher);
+        digest = hasher.finish();
+    }
+    digest
+}
+
+/// The workspace's default hash-based signature stand-in; does real
+/// (if cheap) per-call work, unlike `NoOpSigner`.
+pub struct HashBasedSigner;
+
+impl KeySigner for HashBasedSigner {
+    fn generate_keypair(&self, seed: u64) -> [u8; 32] {
+        let digest = hash_rounds(seed, SIMULATED_WORK_ROUNDS);
+        let mut key = [0u8; 32];
+        key[..8].copy_from_slice(&digest.to_le_bytes());
+        key
+    }
+
+    fn sign(&self, key: &[u8; 32], message: &[u8]) -> [u8; 64] {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        message.hash(&mut hasher);
+        let digest = hash_rounds(hasher.finish(), SIMULATED_WORK_ROUNDS);
+        let mut signature = [0u8; 64];
+        signature[..8].copy_from_slice(&digest.to_le_bytes());
+        signature
+    }
+
+    fn verify(&self, key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+        self.sign(key, message) == *signature
+    }
+}
+
+/// Does no work at all: fixed keys, fixed signatures, verification always
+/// succeeds. Only meaningful for isolating non-crypto cost in benchmarks —
+/// never enable the `no-crypto` feature in a real deployment.
+pub struct NoOpSigner;
+
+impl KeySigner for NoOpSigner {
+    fn generate_keypair(&self, _seed: u64) -> [u8; 32] {
+        [0u8; 32]
+    }
+
+    fn sign(&self, _key: &[u8; 32], _message: &[u8]) -> [u8; 64] {
+        [0u8; 64]
+    }
+
+    fn verify(&self, _key: &[u8; 32], _message: &[u8], _signature: &[u8; 64]) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "no-crypto")]
+pub fn default_signer() -> Box<dyn KeySigner> {
+    Box::new(NoOpSigner)
+}
+
+#[cfg(not(feature = "no-crypto"))]
+pub fn default_signer() -> Box<dyn KeySigner> {
+    Box::new(HashBasedSigner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_based_signer_produces_a_verifiable_signature() {
+        let signer = HashBasedSigner;
+        let key = signer.generate_keypair(1);
+        let signature = signer.sign(&key, b"hello");
+        assert!(signer.verify(&key, b"hello", &signature));
+    }
+
+    #[test]
+    fn hash_based_signer_rejects_a_tampered_message() {
+        let signer = HashBasedSigner;
+        let key = signer.generate_keypair(1);
+        let signature = signer.sign(&key, b"hello");
+        assert!(!signer.verify(&key, b"goodbye", &signature));
+    }
+
+    #[test]
+    fn no_op_signer_always_verifies() {
+        let signer = NoOpSigner;
+        let key = signer.generate_keypair(1);
+        let signature = signer.sign(&key, b"anything");
+        assert!(signer.verify(&key, b"anything else entirely", &signature));
+    }
+
+    #[test]
+    fn default_signer_returns_a_usable_signer() {
+        let signer = default_signer();
+        let key = signer.generate_keypair(7);
+        let signature = signer.sign(&key, b"payload");
+        assert!(signer.verify(&key, b"payload", &signature));
+    }
+}
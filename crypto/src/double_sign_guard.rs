@@ -0,0 +1,243 @@
+//! `RemoteSigner`'s double-sign protection (see `remote_signer`) only
+//! guards a single in-process signer whose history the caller remembers to
+//! restore; nothing stops a second node process from starting against the
+//! same key directory and equivocating, since the two processes don't share
+//! memory. `FileDoubleSignGuard` is the missing cross-process guard,
+//! modeled on Tendermint's `priv_validator_state.json`: an exclusive lock
+//! file taken for the life of the process (so a second instance against the
+//! same directory fails to start instead of signing), plus a small on-disk
+//! record of the last height/view/phase/block signed, checked before every
+//! signature.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use hotstuff2_types::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignPhase {
+    Proposal,
+    Vote,
+}
+
+impl SignPhase {
+    pub(crate) fn to_byte(self) -> u8 {
+        match self {
+            SignPhase::Proposal => 0,
+            SignPhase::Vote => 1,
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(SignPhase::Proposal),
+            1 => Some(SignPhase::Vote),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignerState {
+    pub height: u64,
+    pub view: u64,
+    pub phase: SignPhase,
+    pub block_hash: Hash,
+}
+
+const STATE_RECORD_LEN: usize = 8 + 8 + 1 + 32;
+
+fn encode_state(state: &SignerState) -> [u8; STATE_RECORD_LEN] {
+    let mut buf = [0u8; STATE_RECORD_LEN];
+    buf[0..8].copy_from_slice(&state.height.to_le_bytes());
+    buf[8..16].copy_from_slice(&state.view.to_le_bytes());
+    buf[16] = state.phase.to_byte();
+    buf[17..49].copy_from_slice(&state.block_hash);
+    buf
+}
+
+fn decode_state(buf: &[u8]) -> Option<SignerState> {
+    if buf.len() != STATE_RECORD_LEN {
+        return None;
+    }
+    let height = u64::from_le_bytes(buf[0..8].try_into().unwrap());
+    let view = u64::from_le_bytes(buf[8..16].try_into().unwrap());
+    let phase = SignPhase::from_byte(buf[16])?;
+    let mut block_hash = [0u8; 32];
+    block_hash.copy_from_slice(&buf[17..49]);
+    Some(SignerState { height, view, phase, block_hash })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GuardError {
+    /// Another process already holds the lock on this key directory —
+    /// refuse to start rather than risk signing alongside it.
+    AlreadyLocked,
+    /// `candidate` is for an earlier height/view than the last state this
+    /// guard persisted; signing it back out of order risks re-voting on an
+    /// abandoned fork.
+    Regression { last: SignerState },
+    /// `candidate` matches the last signed height/view/phase but for a
+    /// different block — exactly the equivocation this guard exists to stop.
+    Equivocation { last: SignerState },
+}
+
+/// Holds an exclusive lock on `dir` for its lifetime and persists the last
+/// signed state there, so a signature request is checked against durable
+/// state instead of only in-memory history.
+pub struct FileDoubleSignGuard {
+    lock_path: PathBuf,
+    state_path: PathBuf,
+    last: Option<SignerState>,
+}
+
+impl FileDoubleSignGuard {
+    /// Takes the lock on `dir`, creating it if needed, and loads any
+    /// previously persisted state. Fails with `AlreadyLocked` if another
+    /// live guard already holds this directory.
+    pub fn acquire(dir: &Path) -> Result<Self, GuardError> {
+        fs::create_dir_all(dir).map_err(|_| GuardError::AlreadyLocked)?;
+        let lock_path = dir.join("priv_validator.lock");
+        OpenOptions::new().write(true).create_new(true).open(&lock_path).map_err(|_| GuardError::AlreadyLocked)?;
+
+        let state_path = dir.join("priv_validator_state.bin");
+        let last = read_state(&state_path).unwrap_or(None);
+        Ok(Self { lock_path, state_path, last })
+    }
+
+    pub fn last_state(&self) -> Option<SignerState> {
+        self.last
+    }
+
+    /// Checked before every signature: rejects a `candidate` that would
+    /// regress behind the last persisted height/view, or that equivocates
+    /// at the same height/view/phase. On success, persists `candidate` as
+    /// the new last-signed state before returning.
+    pub fn check_and_record(&mut self, candidate: SignerState) -> Result<(), GuardError> {
+        if let Some(last) = self.last {
+            if (candidate.height, candidate.view) < (last.height, last.view) {
+                return Err(GuardError::Regression { last });
+            }
+            if candidate.height == last.height
+                && candidate.view == last.view
+                && candidate.phase == last.phase
+                && candidate.block_hash != last.block_hash
+            {
+                return Err(GuardError::Equivocation { last });
+            }
+        }
+        write_state(&self.state_path, &candidate).expect("priv_validator_state write must succeed");
+        self.last = Some(candidate);
+        Ok(())
+    }
+}
+
+impl Drop for FileDoubleSignGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.lock_path);
+    }
+}
+
+fn read_state(path: &Path) -> io::Result<Option<SignerState>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut buf = Vec::new();
+    fs::File::open(path)?.read_to_end(&mut buf)?;
+    Ok(decode_state(&buf))
+}
+
+fn write_state(path: &Path, state: &SignerState) -> io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&encode_state(state))?;
+    }
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("hotstuff2_double_sign_guard_{name}_{unique}"))
+    }
+
+    fn state(height: u64, view: u64, phase: SignPhase, block: u8) -> SignerState {
+        SignerState { height, view, phase, block_hash: [block; 32] }
+    }
+
+    #[test]
+    fn a_fresh_directory_has_no_prior_state() {
+        let dir = temp_dir("fresh");
+        let guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+        assert_eq!(guard.last_state(), None);
+        drop(guard);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn a_second_instance_against_the_same_directory_fails_to_acquire() {
+        let dir = temp_dir("second_instance");
+        let _first = FileDoubleSignGuard::acquire(&dir).unwrap();
+        assert_eq!(FileDoubleSignGuard::acquire(&dir).err(), Some(GuardError::AlreadyLocked));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn releasing_the_guard_lets_a_new_instance_acquire_the_directory() {
+        let dir = temp_dir("release");
+        let first = FileDoubleSignGuard::acquire(&dir).unwrap();
+        drop(first);
+        assert!(FileDoubleSignGuard::acquire(&dir).is_ok());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn monotonic_progress_is_recorded_and_visible_via_last_state() {
+        let dir = temp_dir("progress");
+        let mut guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+        guard.check_and_record(state(1, 1, SignPhase::Vote, 1)).unwrap();
+        guard.check_and_record(state(2, 1, SignPhase::Vote, 2)).unwrap();
+        assert_eq!(guard.last_state(), Some(state(2, 1, SignPhase::Vote, 2)));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn a_regression_to_an_earlier_height_is_rejected() {
+        let dir = temp_dir("regression");
+        let mut guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+        guard.check_and_record(state(5, 1, SignPhase::Vote, 1)).unwrap();
+        let result = guard.check_and_record(state(4, 1, SignPhase::Vote, 1));
+        assert_eq!(result, Err(GuardError::Regression { last: state(5, 1, SignPhase::Vote, 1) }));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn signing_a_different_block_at_the_same_height_view_and_phase_is_equivocation() {
+        let dir = temp_dir("equivocation");
+        let mut guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+        guard.check_and_record(state(5, 1, SignPhase::Vote, 1)).unwrap();
+        let result = guard.check_and_record(state(5, 1, SignPhase::Vote, 2));
+        assert_eq!(result, Err(GuardError::Equivocation { last: state(5, 1, SignPhase::Vote, 1) }));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn persisted_state_survives_a_restart_across_separate_guard_instances() {
+        let dir = temp_dir("restart");
+        {
+            let mut guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+            guard.check_and_record(state(5, 1, SignPhase::Vote, 1)).unwrap();
+        }
+        let guard = FileDoubleSignGuard::acquire(&dir).unwrap();
+        assert_eq!(guard.last_state(), Some(state(5, 1, SignPhase::Vote, 1)));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
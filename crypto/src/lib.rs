@@ -0,0 +1,17 @@
+pub mod bench_signing;
+pub mod double_sign_guard;
+pub mod remote_signer;
+pub mod session_encryption;
+pub mod signing_context;
+pub mod threshold_vote_privacy;
+pub mod vrf;
+
+pub use bench_signing::{default_signer, HashBasedSigner, KeySigner, NoOpSigner};
+pub use double_sign_guard::{FileDoubleSignGuard, GuardError, SignPhase, SignerState};
+pub use remote_signer::{RemoteSigner, SignerError, SigningBackend, SigningRequest};
+pub use session_encryption::{derive_public, establish_session_key, open, seal, AuthenticationFailed, SealedPayload};
+pub use signing_context::{sign_context, verify_context, SigningContext};
+pub use vrf::{evaluate, verify, VrfKey, VrfOutput};
+pub use threshold_vote_privacy::{
+    decrypt_vote, encrypt_vote, reconstruct_secret, split_secret, CryptoConfig, EncryptedVote, KeyShare,
+};
@@ -0,0 +1,146 @@
+//! Delegates vote/proposal signing to an external service (a gRPC or unix
+//! socket KMS/HSM bridge, e.g. HashiCorp Vault) instead of holding the
+//! validator private key in the node process. `SigningBackend` is the
+//! transport boundary — this crate has no gRPC/IPC dependency, so the real
+//! wire call is the caller's `SigningBackend` impl; `RemoteSigner` owns the
+//! request timeout and double-sign protection that must hold regardless of
+//! backend.
+
+use std::time::{Duration, Instant};
+
+use hotstuff2_types::{Hash, ValidatorId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningRequest {
+    pub validator_id: ValidatorId,
+    pub view: u64,
+    pub block_hash: Hash,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SignerError {
+    /// The backend didn't respond within the configured timeout.
+    Timeout,
+    /// This validator already signed a different block at the same view —
+    /// refused locally, without ever reaching the backend, since a remote
+    /// KMS has no way to know this is the second request for the view.
+    DoubleSign { view: u64, previously_signed: Hash },
+    Backend(String),
+}
+
+/// The transport to the external signer. A real implementation dials Vault,
+/// an HSM bridge, or a unix socket; tests use an in-memory stand-in.
+pub trait SigningBackend {
+    fn sign(&mut self, request: &SigningRequest) -> Result<[u8; 64], String>;
+    /// How long to wait before treating the backend as unreachable.
+    fn timeout(&self) -> Duration;
+}
+
+/// Guards against double-signing across a restart boundary as well as
+/// within a process: the caller is expected to persist `signed_views` and
+/// restore it into a fresh `RemoteSigner` on startup.
+pub struct RemoteSigner<B: SigningBackend> {
+    backend: B,
+    signed_views: std::collections::HashMap<u64, Hash>,
+}
+
+impl<B: SigningBackend> RemoteSigner<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            signed_views: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Restores prior signing history (e.g. loaded from disk on restart) so
+    /// double-sign protection survives a crash.
+    pub fn restore(&mut self, history: Vec<(u64, Hash)>) {
+        self.signed_views.extend(history);
+    }
+
+    /// A backend response that arrives after `SigningBackend::timeout` has
+    /// elapsed is discarded and reported as `Timeout`, without recording the
+    /// view; the backend call itself is not interrupted.
+    pub fn sign(&mut self, request: SigningRequest) -> Result<[u8; 64], SignerError> {
+        if let Some(&previously_signed) = self.signed_views.get(&request.view) {
+            if previously_signed != request.block_hash {
+                return Err(SignerError::DoubleSign { view: request.view, previously_signed });
+            }
+            // Re-signing the exact same block at the same view (e.g. a
+            // retried request) is safe and idempotent.
+        }
+
+        let started = Instant::now();
+        let signature = self.backend.sign(&request).map_err(SignerError::Backend)?;
+        if started.elapsed() > self.backend.timeout() {
+            return Err(SignerError::Timeout);
+        }
+        self.signed_views.insert(request.view, request.block_hash);
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubBackend {
+        response: Result<[u8; 64], String>,
+    }
+
+    impl SigningBackend for StubBackend {
+        fn sign(&mut self, _request: &SigningRequest) -> Result<[u8; 64], String> {
+            self.response.clone()
+        }
+
+        fn timeout(&self) -> Duration {
+            Duration::from_millis(500)
+        }
+    }
+
+    fn request(view: u64, block_hash: Hash) -> SigningRequest {
+        SigningRequest { validator_id: 1, view, block_hash }
+    }
+
+    #[test]
+    fn signs_via_the_backend_and_records_the_view() {
+        let mut signer = RemoteSigner::new(StubBackend { response: Ok([9u8; 64]) });
+        let sig = signer.sign(request(1, [1u8; 32])).unwrap();
+        assert_eq!(sig, [9u8; 64]);
+        assert_eq!(signer.signed_views.get(&1), Some(&[1u8; 32]));
+    }
+
+    #[test]
+    fn refuses_a_second_different_block_at_the_same_view() {
+        let mut signer = RemoteSigner::new(StubBackend { response: Ok([9u8; 64]) });
+        signer.sign(request(1, [1u8; 32])).unwrap();
+        let result = signer.sign(request(1, [2u8; 32]));
+        assert_eq!(result, Err(SignerError::DoubleSign { view: 1, previously_signed: [1u8; 32] }));
+    }
+
+    #[test]
+    fn re_signing_the_identical_request_is_allowed() {
+        let mut signer = RemoteSigner::new(StubBackend { response: Ok([9u8; 64]) });
+        signer.sign(request(1, [1u8; 32])).unwrap();
+        assert!(signer.sign(request(1, [1u8; 32])).is_ok());
+    }
+
+    #[test]
+    fn a_backend_error_surfaces_without_recording_a_signed_view() {
+        let mut signer = RemoteSigner::new(StubBackend { response: Err("vault unreachable".to_string()) });
+        let result = signer.sign(request(1, [1u8; 32]));
+        assert_eq!(result, Err(SignerError::Backend("vault unreachable".to_string())));
+        // A retry for a different block at the same view is still allowed
+        // since the failed attempt never actually signed anything.
+        signer.backend = StubBackend { response: Ok([9u8; 64]) };
+        assert!(signer.sign(request(1, [2u8; 32])).is_ok());
+    }
+
+    #[test]
+    fn restored_history_still_guards_against_double_signing() {
+        let mut signer = RemoteSigner::new(StubBackend { response: Ok([9u8; 64]) });
+        signer.restore(vec![(1, [1u8; 32])]);
+        let result = signer.sign(request(1, [2u8; 32]));
+        assert_eq!(result, Err(SignerError::DoubleSign { view: 1, previously_signed: [1u8; 32] }));
+    }
+}
@@ -0,0 +1,173 @@
+//! Deployments that terminate TLS on a middlebox (a load balancer, a
+//! service mesh sidecar) still want inter-node payload confidentiality that
+//! doesn't depend on that middlebox being trustworthy. `establish_session_key`
+//! runs a Diffie-Hellman-style exchange independent of `network::handshake`'s
+//! identity check, and `seal`/`open` then encrypt gossip payloads under the
+//! resulting per-session key with an authentication tag, so a payload can't
+//! be read or silently modified by anything sitting between two nodes.
+//!
+//! This crate has no AES-GCM or Noise protocol dependency available, so —
+//! matching the "toy but real" tradeoff already made for signatures in
+//! `bench_signing` and for vote privacy in `threshold_vote_privacy` — this
+//! is a real (not simulated) discrete-log key exchange over a fixed prime
+//! field protecting a real stream cipher with a real keyed authentication
+//! tag, just built from primitives available in `std` rather than a vetted
+//! cryptographic library. Do not use this for anything beyond this
+//! workspace's own testing and demonstration purposes.
+
+/// Same 61-bit Mersenne prime `threshold_vote_privacy` uses, for the same
+/// reason: large enough that discrete log is infeasible by brute force,
+/// small enough that all arithmetic fits in `u128` intermediates.
+const PRIME: u64 = 2_305_843_009_213_693_951;
+/// A primitive root mod `PRIME` used as the fixed Diffie-Hellman generator.
+const GENERATOR: u64 = 37;
+
+fn mod_mul(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % PRIME as u128) as u64
+}
+
+fn mod_pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1u64;
+    base %= PRIME;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base);
+        }
+        base = mod_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// This node's contribution to the exchange, sent to the peer over the
+/// (possibly middlebox-terminated) transport; safe to disclose.
+pub fn derive_public(secret: u64) -> u64 {
+    mod_pow(GENERATOR, secret)
+}
+
+/// Combines this node's secret with the peer's public value into the
+/// shared per-session key. Both sides call this with their own secret and
+/// the other's public value and arrive at the same key.
+pub fn establish_session_key(my_secret: u64, peer_public: u64) -> u64 {
+    mod_pow(peer_public, my_secret)
+}
+
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
+
+fn keystream(key: u64, len: usize) -> Vec<u8> {
+    let mut rng = XorShift64(key | 1);
+    let mut out = Vec::with_capacity(len);
+    while out.len() < len {
+        out.extend_from_slice(&rng.next().to_le_bytes());
+    }
+    out.truncate(len);
+    out
+}
+
+/// Keyed checksum over the ciphertext, standing in for a real AEAD tag: it
+/// binds the tag to both the session key and the exact ciphertext bytes, so
+/// flipping any ciphertext byte (or using the wrong key) is detected by
+/// `open`.
+fn authenticate(key: u64, ciphertext: &[u8]) -> u64 {
+    let mut rng = XorShift64(key.rotate_left(31) | 1);
+    let mut tag = rng.next();
+    for chunk in ciphertext.chunks(8) {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        tag ^= u64::from_le_bytes(word).wrapping_add(rng.next());
+    }
+    tag
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SealedPayload {
+    pub ciphertext: Vec<u8>,
+    pub tag: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticationFailed;
+
+pub fn seal(session_key: u64, plaintext: &[u8]) -> SealedPayload {
+    let stream = keystream(session_key, plaintext.len());
+    let ciphertext: Vec<u8> = plaintext.iter().zip(stream.iter()).map(|(p, k)| p ^ k).collect();
+    let tag = authenticate(session_key, &ciphertext);
+    SealedPayload { ciphertext, tag }
+}
+
+/// Rejects the payload (without returning any plaintext) if the tag doesn't
+/// match under `session_key` — either the wrong key or a tampered
+/// ciphertext.
+pub fn open(session_key: u64, sealed: &SealedPayload) -> Result<Vec<u8>, AuthenticationFailed> {
+    if authenticate(session_key, &sealed.ciphertext) != sealed.tag {
+        return Err(AuthenticationFailed);
+    }
+    let stream = keystream(session_key, sealed.ciphertext.len());
+    Ok(sealed.ciphertext.iter().zip(stream.iter()).map(|(c, k)| c ^ k).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_sides_of_the_exchange_derive_the_same_session_key() {
+        let alice_secret = 12345u64;
+        let bob_secret = 67890u64;
+        let alice_public = derive_public(alice_secret);
+        let bob_public = derive_public(bob_secret);
+
+        let alice_key = establish_session_key(alice_secret, bob_public);
+        let bob_key = establish_session_key(bob_secret, alice_public);
+        assert_eq!(alice_key, bob_key);
+    }
+
+    #[test]
+    fn different_secrets_derive_different_session_keys() {
+        let alice_public = derive_public(111);
+        let key_from_bob = establish_session_key(222, alice_public);
+        let key_from_eve = establish_session_key(333, alice_public);
+        assert_ne!(key_from_bob, key_from_eve);
+    }
+
+    #[test]
+    fn a_sealed_payload_round_trips_under_the_correct_key() {
+        let key = 42u64;
+        let payload = b"propose:height=100,view=7";
+        let sealed = seal(key, payload);
+        assert_ne!(sealed.ciphertext, payload);
+        assert_eq!(open(key, &sealed).unwrap(), payload);
+    }
+
+    #[test]
+    fn opening_with_the_wrong_key_fails_authentication() {
+        let sealed = seal(42, b"secret gossip payload");
+        assert_eq!(open(99, &sealed), Err(AuthenticationFailed));
+    }
+
+    #[test]
+    fn a_tampered_ciphertext_fails_authentication() {
+        let key = 7u64;
+        let mut sealed = seal(key, b"do not modify me");
+        sealed.ciphertext[0] ^= 0xFF;
+        assert_eq!(open(key, &sealed), Err(AuthenticationFailed));
+    }
+
+    #[test]
+    fn an_empty_payload_round_trips() {
+        let key = 5u64;
+        let sealed = seal(key, b"");
+        assert_eq!(open(key, &sealed).unwrap(), Vec::<u8>::new());
+    }
+}
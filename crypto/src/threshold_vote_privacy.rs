@@ -0,0 +1,190 @@
+//! Experimental vote-privacy mode: votes are encrypted to a threshold key so
+//! no single node (including a Byzantine leader watching votes arrive) can
+//! read a vote until 2f+1 shares of the decryption key are available,
+//! mitigating late-voting / adaptive attacks like the `LateVoting`
+//! Byzantine pattern where an adversary waits to see votes before casting
+//! its own. Feature-gated via `CryptoConfig::vote_privacy_enabled`, since it
+//! adds a round of share exchange most deployments don't need.
+//!
+//! This crate has no pairing-friendly-curve dependency, so the threshold
+//! scheme is real Shamir secret sharing over a prime field (not BLS
+//! threshold encryption) protecting a symmetric session key, which then
+//! XOR-encrypts the vote — the same "toy but real" tradeoff this crate
+//! already makes for signatures in `bench_signing`.
+
+/// A 61-bit Mersenne prime; large enough that reconstructing the key from
+/// fewer than `threshold` shares is infeasible by brute force, small enough
+/// that all arithmetic fits in `u128` intermediates without an external
+/// bignum crate.
+const PRIME: u64 = 2_305_843_009_213_693_951;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CryptoConfig {
+    pub vote_privacy_enabled: bool,
+}
+
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x % PRIME
+    }
+}
+
+fn mod_add(a: u64, b: u64) -> u64 {
+    ((a as u128 + b as u128) % PRIME as u128) as u64
+}
+
+fn mod_mul(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) % PRIME as u128) as u64
+}
+
+fn mod_pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1u64;
+    base %= PRIME;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base);
+        }
+        base = mod_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+fn mod_inv(a: u64) -> u64 {
+    // Fermat's little theorem: a^(p-2) is a's inverse mod a prime p.
+    mod_pow(a, PRIME - 2)
+}
+
+fn mod_sub(a: u64, b: u64) -> u64 {
+    ((a as i128 - b as i128).rem_euclid(PRIME as i128)) as u64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyShare {
+    pub validator_index: u64,
+    pub value: u64,
+}
+
+/// Splits `secret` into `total_shares` Shamir shares such that any
+/// `threshold` of them reconstruct it, using a degree-`(threshold - 1)`
+/// polynomial with `secret` as the constant term. `seed` makes splitting
+/// deterministic for testing; a real deployment seeds from a CSPRNG.
+pub fn split_secret(secret: u64, threshold: usize, total_shares: usize, seed: u64) -> Vec<KeyShare> {
+    assert!(threshold >= 1 && threshold <= total_shares);
+    let mut rng = XorShift64(seed | 1);
+    let mut coefficients = vec![secret % PRIME];
+    for _ in 1..threshold {
+        coefficients.push(rng.next());
+    }
+
+    (1..=total_shares as u64)
+        .map(|x| {
+            let mut value = 0u64;
+            let mut x_power = 1u64;
+            for &coeff in &coefficients {
+                value = mod_add(value, mod_mul(coeff, x_power));
+                x_power = mod_mul(x_power, x);
+            }
+            KeyShare { validator_index: x, value }
+        })
+        .collect()
+}
+
+/// Reconstructs the secret via Lagrange interpolation at x=0. Returns the
+/// wrong value (not an error) if fewer than the original `threshold` shares
+/// are supplied, exactly like real Shamir secret sharing — the caller is
+/// responsible for only calling this once it has collected 2f+1 shares.
+pub fn reconstruct_secret(shares: &[KeyShare]) -> u64 {
+    let mut secret = 0u64;
+    for (i, share_i) in shares.iter().enumerate() {
+        let mut numerator = 1u64;
+        let mut denominator = 1u64;
+        for (j, share_j) in shares.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            numerator = mod_mul(numerator, share_j.validator_index);
+            denominator = mod_mul(denominator, mod_sub(share_j.validator_index, share_i.validator_index));
+        }
+        let lagrange_coeff = mod_mul(numerator, mod_inv(denominator));
+        secret = mod_add(secret, mod_mul(share_i.value, lagrange_coeff));
+    }
+    secret
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedVote {
+    pub ciphertext: Vec<u8>,
+}
+
+fn keystream(key: u64, len: usize) -> Vec<u8> {
+    let mut rng = XorShift64(key | 1);
+    let mut out = Vec::with_capacity(len);
+    while out.len() < len {
+        out.extend_from_slice(&rng.next().to_le_bytes());
+    }
+    out.truncate(len);
+    out
+}
+
+pub fn encrypt_vote(plaintext: &[u8], session_key: u64) -> EncryptedVote {
+    let stream = keystream(session_key, plaintext.len());
+    let ciphertext = plaintext.iter().zip(stream.iter()).map(|(p, k)| p ^ k).collect();
+    EncryptedVote { ciphertext }
+}
+
+pub fn decrypt_vote(encrypted: &EncryptedVote, session_key: u64) -> Vec<u8> {
+    let stream = keystream(session_key, encrypted.ciphertext.len());
+    encrypted.ciphertext.iter().zip(stream.iter()).map(|(c, k)| c ^ k).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_shares_reconstruct_the_session_key() {
+        let secret = 424242u64;
+        let shares = split_secret(secret, 3, 4, 7);
+        let reconstructed = reconstruct_secret(&shares[0..3]);
+        assert_eq!(reconstructed, secret);
+    }
+
+    #[test]
+    fn any_subset_of_size_threshold_reconstructs_the_same_key() {
+        let secret = 99u64;
+        let shares = split_secret(secret, 3, 5, 11);
+        let a = reconstruct_secret(&[shares[0], shares[1], shares[2]]);
+        let b = reconstruct_secret(&[shares[1], shares[3], shares[4]]);
+        assert_eq!(a, secret);
+        assert_eq!(b, secret);
+    }
+
+    #[test]
+    fn fewer_than_threshold_shares_do_not_reconstruct_the_key() {
+        let secret = 555u64;
+        let shares = split_secret(secret, 3, 5, 3);
+        let wrong = reconstruct_secret(&shares[0..2]);
+        assert_ne!(wrong, secret);
+    }
+
+    #[test]
+    fn a_vote_round_trips_through_encrypt_and_decrypt_once_the_key_is_recovered() {
+        let session_key = 13579u64;
+        let vote = b"vote:height=10,view=3,accept";
+        let encrypted = encrypt_vote(vote, session_key);
+        assert_ne!(encrypted.ciphertext, vote);
+
+        let shares = split_secret(session_key, 3, 4, 21);
+        let recovered_key = reconstruct_secret(&shares[0..3]);
+        let decrypted = decrypt_vote(&encrypted, recovered_key);
+        assert_eq!(decrypted, vote);
+    }
+}
@@ -0,0 +1,101 @@
+//! Round-robin and stickiness-based leader schedules (`core::leader_election`)
+//! are fully predictable from the validator list alone, letting an attacker
+//! plan a DoS against next epoch's leader well ahead of time. A VRF lets each
+//! validator prove, only once it actually needs to, that it holds the
+//! lowest (or otherwise selected) output for a given `(epoch, view)` —
+//! nobody can predict who that will be without already holding every
+//! validator's key.
+//!
+//! This workspace has no elliptic-curve VRF (e.g. ECVRF-EDWARDS25519) or
+//! pairing-friendly-curve dependency available, so this is the same
+//! symmetric-key, hash-based stand-in already disclosed in
+//! `bench_signing::HashBasedSigner`: `evaluate` and `verify` both take the
+//! same key, unlike a real VRF where verification only needs the public
+//! key. That means the leader-election caller using this must have each
+//! validator's key on hand to verify anyone's claim — a real VRF wouldn't
+//! need that — but the unpredictability-until-evaluated property this
+//! feature actually wants still holds against anyone who *doesn't* hold
+//! the relevant key.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+pub type VrfKey = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfOutput {
+    /// The pseudorandom value used to rank/compare across validators.
+    pub value: u64,
+    /// Binds `value` to the exact `(key, epoch, view)` it was evaluated for,
+    /// so a claimed output can be checked without re-deriving it from
+    /// scratch (though `verify` does exactly that under this hash-based
+    /// scheme, same as `HashBasedSigner::verify`).
+    pub proof: u64,
+}
+
+fn message(epoch: u64, view: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    epoch.hash(&mut hasher);
+    view.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Evaluates the VRF for `(epoch, view)` under `key`.
+pub fn evaluate(key: &VrfKey, epoch: u64, view: u64) -> VrfOutput {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    message(epoch, view).hash(&mut hasher);
+    let proof = hasher.finish();
+    VrfOutput { value: proof, proof }
+}
+
+/// Recomputes the VRF under `key` for `(epoch, view)` and checks it matches
+/// `output`.
+pub fn verify(key: &VrfKey, epoch: u64, view: u64, output: &VrfOutput) -> bool {
+    evaluate(key, epoch, view) == *output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_key_and_input_always_evaluate_to_the_same_output() {
+        let key = [1u8; 32];
+        assert_eq!(evaluate(&key, 3, 10), evaluate(&key, 3, 10));
+    }
+
+    #[test]
+    fn different_keys_evaluate_to_different_outputs() {
+        let a = evaluate(&[1u8; 32], 3, 10);
+        let b = evaluate(&[2u8; 32], 3, 10);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn different_views_evaluate_to_different_outputs() {
+        let key = [1u8; 32];
+        assert_ne!(evaluate(&key, 3, 10), evaluate(&key, 3, 11));
+    }
+
+    #[test]
+    fn a_genuine_output_verifies() {
+        let key = [7u8; 32];
+        let output = evaluate(&key, 1, 5);
+        assert!(verify(&key, 1, 5, &output));
+    }
+
+    #[test]
+    fn a_forged_output_does_not_verify() {
+        let key = [7u8; 32];
+        let forged = VrfOutput { value: 0, proof: 0 };
+        assert!(!verify(&key, 1, 5, &forged));
+    }
+
+    #[test]
+    fn an_output_does_not_verify_against_the_wrong_view() {
+        let key = [7u8; 32];
+        let output = evaluate(&key, 1, 5);
+        assert!(!verify(&key, 1, 6, &output));
+    }
+}
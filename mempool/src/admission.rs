@@ -0,0 +1,260 @@
+//! Admission-time transaction validation. The embedding application
+//! registers a `TxValidator` on `ProductionTxPool` (e.g. checking account
+//! balances or signatures) so obviously invalid transactions are rejected
+//! before they take up pool space or a slot in a block, instead of only
+//! being caught during execution.
+//!
+//! The request describes this as an "async" callback; this crate has no
+//! async runtime dependency, so `TxValidator::admit` is synchronous. An
+//! application whose checks are actually async (e.g. an RPC call to a
+//! balance service) is expected to block on its own runtime inside `admit`
+//! or pre-fetch the state it needs before calling `insert`.
+//!
+//! There is no workspace-wide error enum in this tree (each crate defines
+//! its own error type for its own boundary, e.g. `ValidationError` in
+//! `hotstuff2-consensus`, `ForkAuditError` there too), so admission failures
+//! are `AdmissionError` rather than a global `HotStuffError`. Under
+//! sustained load, `try_insert` distinguishes `MempoolFull` (a capacity
+//! problem the caller should back off and retry) from `Rejected` (the
+//! transaction itself is invalid and retrying unchanged won't help) instead
+//! of collapsing both into one generic error — a well-behaved client can
+//! use `retry_after_ms` as a backoff hint. There is no RPC layer in this
+//! workspace to translate this into a wire response; that mapping is left
+//! to whatever transport an embedding application adds.
+
+use hotstuff2_types::Transaction;
+
+use crate::ordering::{OrderingPolicy, PendingTx, ProductionTxPool};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AdmissionRejected {
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AdmissionError {
+    /// The pool is at capacity; the transaction itself may be fine.
+    MempoolFull { retry_after_ms: u64 },
+    /// The registered validator rejected the transaction itself.
+    Rejected(AdmissionRejected),
+}
+
+impl From<AdmissionRejected> for AdmissionError {
+    fn from(rejected: AdmissionRejected) -> Self {
+        AdmissionError::Rejected(rejected)
+    }
+}
+
+pub trait TxValidator {
+    fn admit(&self, tx: &Transaction) -> Result<(), AdmissionRejected>;
+}
+
+/// A validator built from a closure, for callers who don't need a full
+/// `TxValidator` impl (e.g. a balance-check lambda in a test or a small
+/// embedding app).
+pub struct FnValidator<F>(pub F);
+
+impl<F> TxValidator for FnValidator<F>
+where
+    F: Fn(&Transaction) -> Result<(), AdmissionRejected>,
+{
+    fn admit(&self, tx: &Transaction) -> Result<(), AdmissionRejected> {
+        (self.0)(tx)
+    }
+}
+
+/// Initial backoff hint of a new pool; `with_capacity`, the only way to make
+/// a pool return `MempoolFull`, replaces it with its own hint.
+const DEFAULT_RETRY_AFTER_MS: u64 = 500;
+
+pub struct AdmissionControlledPool {
+    pool: ProductionTxPool,
+    validator: Option<Box<dyn TxValidator>>,
+    capacity: Option<usize>,
+    retry_after_ms: u64,
+}
+
+impl AdmissionControlledPool {
+    pub fn new(ordering_policy: OrderingPolicy) -> Self {
+        Self {
+            pool: ProductionTxPool::new(ordering_policy),
+            validator: None,
+            capacity: None,
+            retry_after_ms: DEFAULT_RETRY_AFTER_MS,
+        }
+    }
+
+    /// Bounds the pool at `capacity` pending transactions; past that,
+    /// `try_insert` sheds load with `AdmissionError::MempoolFull` instead of
+    /// growing unbounded. `retry_after_ms` is the backoff hint handed back
+    /// to the caller.
+    pub fn with_capacity(mut self, capacity: usize, retry_after_ms: u64) -> Self {
+        self.capacity = Some(capacity);
+        self.retry_after_ms = retry_after_ms;
+        self
+    }
+
+    pub fn set_validator(&mut self, validator: Box<dyn TxValidator>) {
+        self.validator = Some(validator);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+
+    /// Sheds load when the pool is at capacity, then runs the registered
+    /// validator (if any) before admitting `tx`; a pool with no validator
+    /// registered and no capacity configured admits everything, matching
+    /// the pre-admission-control default.
+    pub fn try_insert(&mut self, pending: PendingTx) -> Result<(), AdmissionError> {
+        if let Some(capacity) = self.capacity {
+            if self.pool.len() >= capacity {
+                return Err(AdmissionError::MempoolFull { retry_after_ms: self.retry_after_ms });
+            }
+        }
+        if let Some(validator) = &self.validator {
+            validator.admit(&pending.tx)?;
+        }
+        self.pool.insert(pending);
+        Ok(())
+    }
+
+    /// All-or-nothing admission for a batch of transactions: every
+    /// transaction is checked against capacity and the registered validator
+    /// before any of them are inserted, so a high-throughput producer (e.g.
+    /// a bridge or sequencer submitting many transactions per call) doesn't
+    /// pay per-transaction round trips and doesn't end up with a partially
+    /// admitted batch. There is no `Node` facade in this workspace for this
+    /// to sit behind (no RPC layer either); this is the batch admission
+    /// primitive such a facade would delegate to. On failure, returns the
+    /// index of the first rejected transaction and why; nothing in the
+    /// batch is admitted.
+    pub fn try_insert_batch(&mut self, pending: Vec<PendingTx>) -> Result<(), (usize, AdmissionError)> {
+        let projected_len = self.pool.len() + pending.len();
+        if let Some(capacity) = self.capacity {
+            if projected_len > capacity {
+                return Err((0, AdmissionError::MempoolFull { retry_after_ms: self.retry_after_ms }));
+            }
+        }
+        if let Some(validator) = &self.validator {
+            for (index, item) in pending.iter().enumerate() {
+                if let Err(rejected) = validator.admit(&item.tx) {
+                    return Err((index, AdmissionError::Rejected(rejected)));
+                }
+            }
+        }
+        for item in pending {
+            self.pool.insert(item);
+        }
+        Ok(())
+    }
+
+    pub fn ordered(&self) -> Vec<[u8; 32]> {
+        self.pool.ordered()
+    }
+
+    pub fn pack_for_block(&self, max_block_size: u64) -> Vec<Transaction> {
+        self.pool.pack_for_block(max_block_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::ValidatorId;
+
+    fn tx(id: u8) -> PendingTx {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        PendingTx {
+            tx: Transaction { id: hash, payload: vec![], weight: 1, valid_until: None },
+            fee: 0,
+            sender: 1 as ValidatorId,
+        }
+    }
+
+    #[test]
+    fn with_no_validator_registered_every_transaction_is_admitted() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo);
+        assert!(pool.try_insert(tx(1)).is_ok());
+        let mut expected = [0u8; 32];
+        expected[0] = 1;
+        assert_eq!(pool.ordered(), vec![expected]);
+    }
+
+    #[test]
+    fn a_registered_validator_can_reject_a_transaction() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo);
+        pool.set_validator(Box::new(FnValidator(|_tx: &Transaction| {
+            Err(AdmissionRejected { reason: "insufficient balance".to_string() })
+        })));
+        let result = pool.try_insert(tx(1));
+        assert_eq!(
+            result,
+            Err(AdmissionError::Rejected(AdmissionRejected { reason: "insufficient balance".to_string() }))
+        );
+        assert!(pool.ordered().is_empty());
+    }
+
+    #[test]
+    fn a_pool_at_capacity_sheds_load_with_a_retry_after_hint() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo).with_capacity(1, 250);
+        pool.try_insert(tx(1)).unwrap();
+        assert_eq!(pool.try_insert(tx(2)), Err(AdmissionError::MempoolFull { retry_after_ms: 250 }));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn a_full_pool_sheds_load_before_running_the_validator() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo).with_capacity(0, 100);
+        pool.set_validator(Box::new(FnValidator(|_tx: &Transaction| Ok(()))));
+        assert_eq!(pool.try_insert(tx(1)), Err(AdmissionError::MempoolFull { retry_after_ms: 100 }));
+    }
+
+    #[test]
+    fn an_admitted_transaction_is_visible_in_ordering() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo);
+        pool.set_validator(Box::new(FnValidator(|_tx: &Transaction| Ok(()))));
+        pool.try_insert(tx(1)).unwrap();
+        let mut expected = [0u8; 32];
+        expected[0] = 1;
+        assert_eq!(pool.ordered(), vec![expected]);
+    }
+
+    #[test]
+    fn a_batch_that_fully_fits_is_admitted_atomically() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo);
+        pool.try_insert_batch(vec![tx(1), tx(2), tx(3)]).unwrap();
+        assert_eq!(pool.len(), 3);
+    }
+
+    #[test]
+    fn a_batch_with_one_rejected_transaction_admits_none_of_it() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo);
+        pool.set_validator(Box::new(FnValidator(|tx: &Transaction| {
+            if tx.id[0] == 2 {
+                Err(AdmissionRejected { reason: "bad tx".to_string() })
+            } else {
+                Ok(())
+            }
+        })));
+        let result = pool.try_insert_batch(vec![tx(1), tx(2), tx(3)]);
+        assert_eq!(
+            result,
+            Err((1, AdmissionError::Rejected(AdmissionRejected { reason: "bad tx".to_string() })))
+        );
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn a_batch_that_would_overflow_capacity_is_rejected_atomically() {
+        let mut pool = AdmissionControlledPool::new(OrderingPolicy::Fifo).with_capacity(2, 100);
+        let result = pool.try_insert_batch(vec![tx(1), tx(2), tx(3)]);
+        assert_eq!(result, Err((0, AdmissionError::MempoolFull { retry_after_ms: 100 })));
+        assert!(pool.is_empty());
+    }
+}
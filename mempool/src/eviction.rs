@@ -0,0 +1,315 @@
+//! `ProductionTxPool` and `AdmissionControlledPool` only ever grow: nothing
+//! ever leaves once inserted, so a burst of transactions that are never
+//! included in a block (fee too low, sender went offline) wedges the pool
+//! under sustained load. `BoundedTxPool` adds TTL-based expiry and
+//! size-based eviction on top of a `ProductionTxPool`, honoring a
+//! configurable `EvictionPolicy`.
+//!
+//! Timestamps are caller-supplied milliseconds rather than read from the
+//! system clock internally, matching the convention already used for
+//! timing checks in `consensus::byzantine_detector` — it keeps eviction
+//! deterministic and testable without a clock dependency.
+
+use std::collections::BTreeMap;
+
+use hotstuff2_types::{Hash, ValidatorId};
+
+use crate::ordering::{OrderingPolicy, PendingTx, ProductionTxPool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Evict the least-recently-inserted transaction first.
+    Lru,
+    /// Evict the lowest-fee transaction first.
+    LowestFeeFirst,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MempoolConfig {
+    pub max_size: usize,
+    pub ttl_ms: u64,
+    pub eviction_policy: EvictionPolicy,
+}
+
+/// Point-in-time snapshot of pool contents for debugging a stuck pipeline:
+/// how many pending transactions per sender, how stale the oldest one is,
+/// and the fee distribution (sorted ascending) across everything pending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolInspection {
+    pub total_pending: usize,
+    pub pending_by_sender: BTreeMap<ValidatorId, usize>,
+    pub oldest_age_ms: Option<u64>,
+    pub fee_distribution: Vec<u64>,
+}
+
+struct TrackedTx {
+    pending: PendingTx,
+    inserted_at_ms: u64,
+}
+
+/// Wraps a `ProductionTxPool`'s ordering policy with TTL expiry and a
+/// hard size cap enforced by `eviction_policy`.
+pub struct BoundedTxPool {
+    config: MempoolConfig,
+    entries: Vec<TrackedTx>,
+    ordering_policy: OrderingPolicy,
+    evicted_count: u64,
+}
+
+impl BoundedTxPool {
+    pub fn new(config: MempoolConfig, ordering_policy: OrderingPolicy) -> Self {
+        Self { config, entries: Vec::new(), ordering_policy, evicted_count: 0 }
+    }
+
+    /// Drops any transaction older than `ttl_ms`, then inserts `tx`, then
+    /// evicts by `eviction_policy` until the pool is back at `max_size`.
+    pub fn insert(&mut self, tx: PendingTx, now_ms: u64) {
+        self.evict_expired(now_ms);
+        self.entries.push(TrackedTx { pending: tx, inserted_at_ms: now_ms });
+        while self.entries.len() > self.config.max_size {
+            let evict_index = match self.config.eviction_policy {
+                EvictionPolicy::Lru => self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, e)| e.inserted_at_ms)
+                    .map(|(i, _)| i),
+                EvictionPolicy::LowestFeeFirst => self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, e)| e.pending.fee)
+                    .map(|(i, _)| i),
+            };
+            if let Some(index) = evict_index {
+                self.entries.remove(index);
+                self.evicted_count += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Removes every transaction whose age exceeds `ttl_ms`, counting each
+    /// as an eviction.
+    pub fn evict_expired(&mut self, now_ms: u64) {
+        let ttl_ms = self.config.ttl_ms;
+        let before = self.entries.len();
+        self.entries.retain(|e| now_ms.saturating_sub(e.inserted_at_ms) <= ttl_ms);
+        self.evicted_count += (before - self.entries.len()) as u64;
+    }
+
+    /// Removes every transaction whose client-supplied `valid_until` height
+    /// has already passed at `current_height`, counting each as an eviction.
+    /// Complements `evict_expired`'s TTL-based check: a transaction can be
+    /// dropped for being stale in wall-clock time, in chain height, or both.
+    pub fn evict_expired_by_height(&mut self, current_height: u64) {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.pending.tx.valid_until.is_none_or(|h| h >= current_height));
+        self.evicted_count += (before - self.entries.len()) as u64;
+    }
+
+    pub fn get_evicted_count(&self) -> u64 {
+        self.evicted_count
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_sender(&self, sender: ValidatorId) -> bool {
+        self.entries.iter().any(|e| e.pending.sender == sender)
+    }
+
+    /// There is no RPC/admin API layer in this workspace (see the same note
+    /// in `admission`) to expose this over the wire; `inspect` is the data
+    /// such an endpoint would serialize, kept here so it stays in sync with
+    /// `entries` instead of a second copy of pool state living behind an
+    /// RPC handler.
+    pub fn inspect(&self, now_ms: u64) -> MempoolInspection {
+        let mut pending_by_sender: BTreeMap<ValidatorId, usize> = BTreeMap::new();
+        let mut fee_distribution: Vec<u64> = Vec::with_capacity(self.entries.len());
+        let mut oldest_age_ms = None;
+        for entry in &self.entries {
+            *pending_by_sender.entry(entry.pending.sender).or_insert(0) += 1;
+            fee_distribution.push(entry.pending.fee);
+            let age_ms = now_ms.saturating_sub(entry.inserted_at_ms);
+            oldest_age_ms = Some(oldest_age_ms.map_or(age_ms, |max: u64| max.max(age_ms)));
+        }
+        fee_distribution.sort_unstable();
+        MempoolInspection { total_pending: self.entries.len(), pending_by_sender, oldest_age_ms, fee_distribution }
+    }
+
+    /// Operator action to evict one specific transaction by id, e.g. to
+    /// unstick a pipeline wedged behind a transaction that will never be
+    /// included. Returns whether a matching transaction was found.
+    pub fn evict_tx(&mut self, id: Hash) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.pending.tx.id != id);
+        let evicted = self.entries.len() < before;
+        if evicted {
+            self.evicted_count += 1;
+        }
+        evicted
+    }
+
+    /// Snapshots the surviving transactions into a plain `ProductionTxPool`
+    /// for ordering/packing, so `BoundedTxPool` doesn't need to duplicate
+    /// `ordered`/`pack_for_block` logic.
+    pub fn to_ordered_pool(&self) -> ProductionTxPool {
+        let mut pool = ProductionTxPool::new(self.ordering_policy);
+        for entry in &self.entries {
+            pool.insert(PendingTx {
+                tx: entry.pending.tx.clone(),
+                fee: entry.pending.fee,
+                sender: entry.pending.sender,
+            });
+        }
+        pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::Transaction;
+
+    fn tx(id: u8, fee: u64, sender: ValidatorId) -> PendingTx {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        PendingTx { tx: Transaction { id: hash, payload: vec![], weight: 1, valid_until: None }, fee, sender }
+    }
+
+    fn tx_with_expiry(id: u8, fee: u64, sender: ValidatorId, valid_until: u64) -> PendingTx {
+        let mut pending = tx(id, fee, sender);
+        pending.tx.valid_until = Some(valid_until);
+        pending
+    }
+
+    #[test]
+    fn a_transaction_past_its_valid_until_height_is_expired() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx_with_expiry(1, 5, 1, 10), 0);
+        pool.evict_expired_by_height(11);
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.get_evicted_count(), 1);
+    }
+
+    #[test]
+    fn a_transaction_still_within_its_valid_until_height_is_kept() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx_with_expiry(1, 5, 1, 10), 0);
+        pool.evict_expired_by_height(10);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.get_evicted_count(), 0);
+    }
+
+    #[test]
+    fn a_transaction_with_no_valid_until_is_never_expired_by_height() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 5, 1), 0);
+        pool.evict_expired_by_height(u64::MAX);
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn transactions_within_ttl_are_kept() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 1000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 5, 1), 0);
+        pool.evict_expired(500);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.get_evicted_count(), 0);
+    }
+
+    #[test]
+    fn transactions_past_ttl_are_expired() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 1000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 5, 1), 0);
+        pool.evict_expired(1500);
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.get_evicted_count(), 1);
+    }
+
+    #[test]
+    fn lru_policy_evicts_the_oldest_transaction_when_over_capacity() {
+        let config = MempoolConfig { max_size: 2, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 5, 1), 0);
+        pool.insert(tx(2, 5, 1), 10);
+        pool.insert(tx(3, 5, 1), 20);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get_evicted_count(), 1);
+        assert!(!pool.to_ordered_pool().ordered().iter().any(|id| id[0] == 1));
+    }
+
+    #[test]
+    fn lowest_fee_first_policy_evicts_the_cheapest_transaction_when_over_capacity() {
+        let config = MempoolConfig { max_size: 2, ttl_ms: 100_000, eviction_policy: EvictionPolicy::LowestFeeFirst };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 50, 1), 0);
+        pool.insert(tx(2, 5, 1), 10);
+        pool.insert(tx(3, 30, 1), 20);
+        let ids: Vec<u8> = pool.to_ordered_pool().ordered().iter().map(|id| id[0]).collect();
+        assert!(!ids.contains(&2));
+        assert_eq!(pool.get_evicted_count(), 1);
+    }
+
+    #[test]
+    fn inspect_reports_per_sender_counts_oldest_age_and_fee_distribution() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 50, 1), 0);
+        pool.insert(tx(2, 10, 1), 100);
+        pool.insert(tx(3, 30, 2), 200);
+
+        let snapshot = pool.inspect(300);
+        assert_eq!(snapshot.total_pending, 3);
+        assert_eq!(snapshot.pending_by_sender.get(&1), Some(&2));
+        assert_eq!(snapshot.pending_by_sender.get(&2), Some(&1));
+        assert_eq!(snapshot.oldest_age_ms, Some(300));
+        assert_eq!(snapshot.fee_distribution, vec![10, 30, 50]);
+    }
+
+    #[test]
+    fn inspect_on_an_empty_pool_reports_no_oldest_age() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        let snapshot = pool.inspect(1_000);
+        assert_eq!(snapshot.total_pending, 0);
+        assert_eq!(snapshot.oldest_age_ms, None);
+    }
+
+    #[test]
+    fn evict_tx_removes_a_specific_transaction_and_counts_it() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        let stuck = tx(1, 5, 1);
+        let stuck_id = stuck.tx.id;
+        pool.insert(stuck, 0);
+        pool.insert(tx(2, 5, 1), 10);
+
+        assert!(pool.evict_tx(stuck_id));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.get_evicted_count(), 1);
+        assert!(!pool.to_ordered_pool().ordered().contains(&stuck_id));
+    }
+
+    #[test]
+    fn evict_tx_on_an_unknown_id_does_nothing() {
+        let config = MempoolConfig { max_size: 10, ttl_ms: 100_000, eviction_policy: EvictionPolicy::Lru };
+        let mut pool = BoundedTxPool::new(config, OrderingPolicy::Fifo);
+        pool.insert(tx(1, 5, 1), 0);
+        assert!(!pool.evict_tx([9u8; 32]));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.get_evicted_count(), 0);
+    }
+}
@@ -0,0 +1,154 @@
+//! `MempoolSource` lets the leader pull a block's worth of transactions
+//! from whatever is holding them, instead of hard-wiring `ProductionTxPool`
+//! into the block-building path. A real deployment that separates ordering
+//! from collection would implement this over gRPC; no gRPC (or any RPC)
+//! crate is available in this workspace, so `ChannelMempoolSource` is a
+//! dependency-free stand-in that plays the same role over an
+//! `std::sync::mpsc` channel — an external process feeds it batches, and
+//! the leader pulls from it exactly like it would pull from a gRPC client.
+//! `ProductionTxPoolSource` adapts the existing in-process pool to the same
+//! trait, so leader code written against `MempoolSource` doesn't care which
+//! backend it's talking to.
+
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use hotstuff2_types::Transaction;
+
+use crate::ordering::ProductionTxPool;
+
+/// Anything the leader can pull a block's worth of transactions from.
+pub trait MempoolSource {
+    /// Returns transactions to include in the next block, honoring
+    /// `max_block_size` the same way `ProductionTxPool::pack_for_block`
+    /// does (cumulative `Transaction::weight` budget).
+    fn pull_batch(&mut self, max_block_size: u64) -> Vec<Transaction>;
+}
+
+impl MempoolSource for ProductionTxPool {
+    fn pull_batch(&mut self, max_block_size: u64) -> Vec<Transaction> {
+        self.pack_for_block(max_block_size)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExternalSourceError {
+    /// The external mempool process disconnected (its `Sender` was
+    /// dropped); the leader should fail over to a different source rather
+    /// than pulling silently-empty batches forever.
+    Disconnected,
+}
+
+/// Pulls whole batches sent by an external mempool process over a channel,
+/// buffering anything received but not yet consumed by `pull_batch`.
+pub struct ChannelMempoolSource {
+    inbox: Receiver<Vec<Transaction>>,
+    buffered: Vec<Transaction>,
+    disconnected: bool,
+}
+
+impl ChannelMempoolSource {
+    pub fn new(inbox: Receiver<Vec<Transaction>>) -> Self {
+        Self { inbox, buffered: Vec::new(), disconnected: false }
+    }
+
+    /// Drains every batch currently waiting on the channel into `buffered`
+    /// without blocking, recording whether the sender side has hung up.
+    fn drain_available(&mut self) {
+        loop {
+            match self.inbox.try_recv() {
+                Ok(batch) => self.buffered.extend(batch),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.disconnected = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+}
+
+impl MempoolSource for ChannelMempoolSource {
+    fn pull_batch(&mut self, max_block_size: u64) -> Vec<Transaction> {
+        self.drain_available();
+        let ordered = self.buffered.iter().collect::<Vec<_>>();
+        let (packed, remaining_len) = pack_by_weight(&ordered, max_block_size);
+        self.buffered.drain(0..self.buffered.len() - remaining_len);
+        packed
+    }
+}
+
+/// Packs `ordered` by cumulative weight, stopping at the first transaction
+/// that would exceed `max_block_size` (a hard stop, unlike
+/// `ordering::pack_ordered`'s skip-and-continue: an external source's order
+/// is not this crate's to reshuffle). Returns the packed transactions and
+/// how many trailing (unpacked) items remain.
+fn pack_by_weight(ordered: &[&Transaction], max_block_size: u64) -> (Vec<Transaction>, usize) {
+    let mut packed = Vec::new();
+    let mut cumulative_weight = 0u64;
+    for (i, tx) in ordered.iter().enumerate() {
+        let next_weight = cumulative_weight.saturating_add(tx.weight);
+        if next_weight > max_block_size {
+            return (packed, ordered.len() - i);
+        }
+        cumulative_weight = next_weight;
+        packed.push((*tx).clone());
+    }
+    (packed, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordering::OrderingPolicy;
+    use std::sync::mpsc::channel;
+
+    fn tx(id: u8, weight: u64) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction { id: hash, payload: vec![], weight, valid_until: None }
+    }
+
+    #[test]
+    fn a_production_tx_pool_is_usable_as_a_mempool_source() {
+        let mut pool = ProductionTxPool::new(OrderingPolicy::Fifo);
+        pool.insert(crate::ordering::PendingTx { tx: tx(1, 5), fee: 1, sender: 1 });
+        let batch = MempoolSource::pull_batch(&mut pool, 10);
+        assert_eq!(batch.len(), 1);
+    }
+
+    #[test]
+    fn a_channel_source_pulls_batches_sent_by_an_external_process() {
+        let (sender, receiver) = channel();
+        sender.send(vec![tx(1, 3), tx(2, 3)]).unwrap();
+        let mut source = ChannelMempoolSource::new(receiver);
+        let batch = source.pull_batch(10);
+        assert_eq!(batch.len(), 2);
+    }
+
+    #[test]
+    fn a_channel_source_stops_packing_at_the_weight_budget_and_keeps_the_rest_buffered() {
+        let (sender, receiver) = channel();
+        sender.send(vec![tx(1, 6), tx(2, 6)]).unwrap();
+        let mut source = ChannelMempoolSource::new(receiver);
+        let first = source.pull_batch(10);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].id[0], 1);
+
+        let second = source.pull_batch(10);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].id[0], 2);
+    }
+
+    #[test]
+    fn a_channel_source_reports_disconnection_once_the_sender_is_dropped() {
+        let (sender, receiver) = channel();
+        drop(sender);
+        let mut source = ChannelMempoolSource::new(receiver);
+        assert!(source.pull_batch(10).is_empty());
+        assert!(source.is_disconnected());
+    }
+}
@@ -0,0 +1,131 @@
+//! Fee-based ordering alone lets a flood of ordinary user transactions
+//! crowd out protocol-critical ones (reconfiguration, slashing evidence)
+//! that may carry no fee at all. `LanedTxPool` keeps a separate queue per
+//! `Lane` and always packs the system lane first, so critical transactions
+//! are never starved by user traffic. `LaneQuotas` caps how much of a
+//! block's weight budget the system lane may consume, so the reverse
+//! failure — a compromised or buggy system-lane producer flooding every
+//! block and starving user transactions entirely — is bounded too.
+//!
+//! There is no `ConsensusConfig` dependency from this crate (this crate has
+//! no reason to grow a dependency on `hotstuff2-core` just for one config
+//! struct); `LaneQuotas`
+//! is passed into `pack_for_block` by whatever embeds this pool, the same
+//! way `max_block_size` already is.
+
+use hotstuff2_types::Transaction;
+
+use crate::ordering::{order_pending, pack_ordered, OrderingPolicy, PendingTx};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    /// Reconfiguration, slashing evidence, and other protocol-critical
+    /// transactions; always packed ahead of `User`.
+    System,
+    User,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LaneQuotas {
+    /// Caps how much of a block's weight budget the system lane may
+    /// consume; `None` means uncapped (system transactions always win, up
+    /// to the full block budget, before user transactions get anything).
+    pub max_system_weight: Option<u64>,
+}
+
+pub struct LanedTxPool {
+    system: Vec<PendingTx>,
+    user: Vec<PendingTx>,
+    ordering_policy: OrderingPolicy,
+}
+
+impl LanedTxPool {
+    pub fn new(ordering_policy: OrderingPolicy) -> Self {
+        Self { system: Vec::new(), user: Vec::new(), ordering_policy }
+    }
+
+    pub fn insert(&mut self, lane: Lane, tx: PendingTx) {
+        match lane {
+            Lane::System => self.system.push(tx),
+            Lane::User => self.user.push(tx),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.system.len() + self.user.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Packs the system lane first (capped by `quotas.max_system_weight`,
+    /// if set), then fills whatever weight budget remains with the user
+    /// lane. Both lanes are ordered independently by `ordering_policy`.
+    pub fn pack_for_block(&self, max_block_size: u64, quotas: LaneQuotas) -> Vec<Transaction> {
+        let system_budget = match quotas.max_system_weight {
+            Some(cap) => cap.min(max_block_size),
+            None => max_block_size,
+        };
+        let system_ordered = order_pending(self.system.iter().collect(), self.ordering_policy);
+        let mut packed = pack_ordered(system_ordered, system_budget);
+
+        let used_weight: u64 = packed.iter().map(|tx| tx.weight).sum();
+        let remaining_budget = max_block_size.saturating_sub(used_weight);
+        let user_ordered = order_pending(self.user.iter().collect(), self.ordering_policy);
+        packed.extend(pack_ordered(user_ordered, remaining_budget));
+        packed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::ValidatorId;
+
+    fn weighted_tx(id: u8, fee: u64, weight: u64) -> PendingTx {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        PendingTx { tx: Transaction { id: hash, payload: vec![], weight, valid_until: None }, fee, sender: 1 as ValidatorId }
+    }
+
+    #[test]
+    fn system_lane_transactions_are_packed_ahead_of_user_ones() {
+        let mut pool = LanedTxPool::new(OrderingPolicy::Fifo);
+        pool.insert(Lane::User, weighted_tx(1, 0, 1));
+        pool.insert(Lane::System, weighted_tx(2, 0, 1));
+        let packed = pool.pack_for_block(10, LaneQuotas::default());
+        assert_eq!(packed[0].id[0], 2);
+        assert_eq!(packed[1].id[0], 1);
+    }
+
+    #[test]
+    fn with_no_quota_a_full_system_lane_can_starve_user_transactions() {
+        let mut pool = LanedTxPool::new(OrderingPolicy::Fifo);
+        pool.insert(Lane::System, weighted_tx(1, 0, 10));
+        pool.insert(Lane::User, weighted_tx(2, 0, 1));
+        let packed = pool.pack_for_block(10, LaneQuotas::default());
+        assert_eq!(packed.len(), 1);
+        assert_eq!(packed[0].id[0], 1);
+    }
+
+    #[test]
+    fn a_system_quota_reserves_room_for_user_transactions() {
+        let mut pool = LanedTxPool::new(OrderingPolicy::Fifo);
+        pool.insert(Lane::System, weighted_tx(1, 0, 5));
+        pool.insert(Lane::System, weighted_tx(2, 0, 5));
+        pool.insert(Lane::User, weighted_tx(3, 0, 3));
+        let packed = pool.pack_for_block(10, LaneQuotas { max_system_weight: Some(5) });
+        let ids: Vec<u8> = packed.iter().map(|tx| tx.id[0]).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn an_empty_system_lane_lets_user_transactions_use_the_whole_budget() {
+        let mut pool = LanedTxPool::new(OrderingPolicy::Fee);
+        pool.insert(Lane::User, weighted_tx(1, 50, 1));
+        pool.insert(Lane::User, weighted_tx(2, 10, 1));
+        let packed = pool.pack_for_block(10, LaneQuotas::default());
+        assert_eq!(packed.len(), 2);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod admission;
+pub mod eviction;
+pub mod external_source;
+pub mod lanes;
+pub mod ordering;
+pub mod sharded;
+
+pub use admission::{AdmissionControlledPool, AdmissionError, AdmissionRejected, FnValidator, TxValidator};
+pub use eviction::{BoundedTxPool, EvictionPolicy, MempoolConfig, MempoolInspection};
+pub use external_source::{ChannelMempoolSource, ExternalSourceError, MempoolSource};
+pub use lanes::{Lane, LaneQuotas, LanedTxPool};
+pub use ordering::{OrderingPolicy, PendingTx, ProductionTxPool};
+pub use sharded::ShardedTxPool;
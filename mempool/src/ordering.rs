@@ -0,0 +1,220 @@
+//! Selectable transaction ordering for `ProductionTxPool`. Fee-only ordering
+//! is trivially front-runnable for some applications, so FIFO, seeded
+//! random, and sender-fair round-robin are offered as alternatives.
+
+use hotstuff2_types::{Transaction, ValidatorId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderingPolicy {
+    Fee,
+    Fifo,
+    /// Deterministic per-block shuffle, seeded so the block is reproducible.
+    Random { seed: u64 },
+    SenderFairRoundRobin,
+}
+
+/// Minimal xorshift64 PRNG: no external dependency, deterministic for a
+/// given seed so `Random` ordering is reproducible per block.
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
+
+#[derive(Clone)]
+pub struct PendingTx {
+    pub tx: Transaction,
+    pub fee: u64,
+    pub sender: ValidatorId,
+}
+
+pub struct ProductionTxPool {
+    pending: Vec<PendingTx>,
+    pub ordering_policy: OrderingPolicy,
+}
+
+impl ProductionTxPool {
+    pub fn new(ordering_policy: OrderingPolicy) -> Self {
+        Self { pending: Vec::new(), ordering_policy }
+    }
+
+    pub fn insert(&mut self, tx: PendingTx) {
+        self.pending.push(tx);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns transaction ids in the order the current policy prescribes.
+    pub fn ordered(&self) -> Vec<[u8; 32]> {
+        self.ordered_pending().into_iter().map(|p| p.tx.id).collect()
+    }
+
+    /// Packs transactions for a block by cumulative `Transaction::weight`
+    /// against `max_block_size`, walking the pool in the current ordering
+    /// policy's order rather than packing by raw transaction count. A
+    /// transaction that would push the running total over budget is
+    /// skipped (not a hard stop), so a lighter transaction further down
+    /// the order can still fit in the remaining budget.
+    pub fn pack_for_block(&self, max_block_size: u64) -> Vec<Transaction> {
+        pack_ordered(self.ordered_pending(), max_block_size)
+    }
+
+    fn ordered_pending(&self) -> Vec<&PendingTx> {
+        order_pending(self.pending.iter().collect(), self.ordering_policy)
+    }
+}
+
+/// Reorders `items` per `policy`. Factored out of `ProductionTxPool` so
+/// `ShardedTxPool` can apply the same policy across items merged from
+/// several shards instead of duplicating the ordering logic per shard.
+pub(crate) fn order_pending(mut items: Vec<&PendingTx>, policy: OrderingPolicy) -> Vec<&PendingTx> {
+    match policy {
+        OrderingPolicy::Fee => {
+            items.sort_by_key(|p| std::cmp::Reverse(p.fee));
+            items
+        }
+        OrderingPolicy::Fifo => items,
+        OrderingPolicy::Random { seed } => {
+            let mut rng = XorShift64(seed | 1); // seed must be nonzero for xorshift
+            for i in (1..items.len()).rev() {
+                let j = (rng.next() as usize) % (i + 1);
+                items.swap(i, j);
+            }
+            items
+        }
+        OrderingPolicy::SenderFairRoundRobin => {
+            let mut by_sender: std::collections::BTreeMap<ValidatorId, Vec<&PendingTx>> = std::collections::BTreeMap::new();
+            for p in items {
+                by_sender.entry(p.sender).or_default().push(p);
+            }
+            let mut out = Vec::new();
+            loop {
+                let mut progressed = false;
+                for queue in by_sender.values_mut() {
+                    if !queue.is_empty() {
+                        out.push(queue.remove(0));
+                        progressed = true;
+                    }
+                }
+                if !progressed {
+                    break;
+                }
+            }
+            out
+        }
+    }
+}
+
+/// Packs `ordered` transactions by cumulative weight against
+/// `max_block_size`; a transaction that would push the running total over
+/// budget is skipped (not a hard stop), so a lighter transaction further
+/// down the order can still fit in the remaining budget.
+pub(crate) fn pack_ordered(ordered: Vec<&PendingTx>, max_block_size: u64) -> Vec<Transaction> {
+    let mut packed = Vec::new();
+    let mut cumulative_weight = 0u64;
+    for p in ordered {
+        let next_weight = cumulative_weight.saturating_add(p.tx.weight);
+        if next_weight > max_block_size {
+            continue;
+        }
+        cumulative_weight = next_weight;
+        packed.push(p.tx.clone());
+    }
+    packed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(id: u8, fee: u64, sender: ValidatorId) -> PendingTx {
+        weighted_tx(id, fee, sender, 1)
+    }
+
+    fn weighted_tx(id: u8, fee: u64, sender: ValidatorId, weight: u64) -> PendingTx {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        PendingTx {
+            tx: Transaction { id: hash, payload: vec![], weight, valid_until: None },
+            fee,
+            sender,
+        }
+    }
+
+    #[test]
+    fn fee_policy_orders_highest_fee_first() {
+        let mut pool = ProductionTxPool::new(OrderingPolicy::Fee);
+        pool.insert(tx(1, 5, 1));
+        pool.insert(tx(2, 50, 1));
+        let ordered = pool.ordered();
+        assert_eq!(ordered[0][0], 2);
+    }
+
+    #[test]
+    fn fifo_policy_preserves_insertion_order() {
+        let mut pool = ProductionTxPool::new(OrderingPolicy::Fifo);
+        pool.insert(tx(1, 50, 1));
+        pool.insert(tx(2, 5, 1));
+        let ordered = pool.ordered();
+        assert_eq!(ordered[0][0], 1);
+        assert_eq!(ordered[1][0], 2);
+    }
+
+    #[test]
+    fn random_policy_is_deterministic_for_a_given_seed() {
+        let mut a = ProductionTxPool::new(OrderingPolicy::Random { seed: 42 });
+        let mut b = ProductionTxPool::new(OrderingPolicy::Random { seed: 42 });
+        for i in 0..10 {
+            a.insert(tx(i, i as u64, 1));
+            b.insert(tx(i, i as u64, 1));
+        }
+        assert_eq!(a.ordered(), b.ordered());
+    }
+
+    #[test]
+    fn sender_fair_round_robin_interleaves_senders() {
+        let mut pool = ProductionTxPool::new(OrderingPolicy::SenderFairRoundRobin);
+        pool.insert(tx(1, 0, 1));
+        pool.insert(tx(2, 0, 1));
+        pool.insert(tx(3, 0, 2));
+        let ordered = pool.ordered();
+        // Sender 1's two txs shouldn't both come before sender 2's single tx.
+        let sender2_pos = ordered.iter().position(|id| id[0] == 3).unwrap();
+        assert!(sender2_pos < 2);
+    }
+
+    #[test]
+    fn pack_for_block_stops_at_the_cumulative_weight_budget() {
+        let mut pool = ProductionTxPool::new(OrderingPolicy::Fee);
+        pool.insert(weighted_tx(1, 50, 1, 6));
+        pool.insert(weighted_tx(2, 40, 1, 6));
+        pool.insert(weighted_tx(3, 30, 1, 6));
+        let packed = pool.pack_for_block(10);
+        assert_eq!(packed.len(), 1);
+        assert_eq!(packed[0].id[0], 1);
+    }
+
+    #[test]
+    fn pack_for_block_skips_an_over_budget_transaction_to_fit_a_lighter_one() {
+        let mut pool = ProductionTxPool::new(OrderingPolicy::Fee);
+        pool.insert(weighted_tx(1, 50, 1, 9)); // highest fee, but too heavy alone with tx 2
+        pool.insert(weighted_tx(2, 40, 1, 9));
+        pool.insert(weighted_tx(3, 30, 1, 1)); // fits after tx 1 is taken
+        let packed = pool.pack_for_block(10);
+        let ids: Vec<u8> = packed.iter().map(|t| t.id[0]).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+}
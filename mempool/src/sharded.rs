@@ -0,0 +1,158 @@
+//! `ProductionTxPool` behind a single lock caps submission throughput at
+//! high TPS: every insert serializes on the same mutex regardless of which
+//! transactions are actually contending. `ShardedTxPool` partitions pending
+//! transactions across N independently-locked shards by transaction id, so
+//! inserts to different shards proceed without blocking each other, and
+//! only `ordered`/`pack_for_block` — which need a global view to apply the
+//! ordering policy — pay the cost of locking every shard.
+
+use std::sync::Mutex;
+
+use hotstuff2_types::Transaction;
+
+use crate::ordering::{order_pending, pack_ordered, OrderingPolicy, PendingTx};
+
+pub struct ShardedTxPool {
+    shards: Vec<Mutex<Vec<PendingTx>>>,
+    ordering_policy: OrderingPolicy,
+}
+
+impl ShardedTxPool {
+    /// `shard_count` is clamped to at least 1.
+    pub fn new(shard_count: usize, ordering_policy: OrderingPolicy) -> Self {
+        let shard_count = shard_count.max(1);
+        let shards = (0..shard_count).map(|_| Mutex::new(Vec::new())).collect();
+        Self { shards, ordering_policy }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard_index(&self, id: &[u8; 32]) -> usize {
+        let mut low_bytes = [0u8; 8];
+        low_bytes.copy_from_slice(&id[0..8]);
+        (u64::from_le_bytes(low_bytes) as usize) % self.shards.len()
+    }
+
+    /// Locks only the shard `tx` hashes into, leaving every other shard
+    /// free for concurrent inserts.
+    pub fn insert(&self, tx: PendingTx) {
+        let index = self.shard_index(&tx.tx.id);
+        self.shards[index].lock().unwrap().push(tx);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().unwrap().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Merges every shard under lock and applies the pool's ordering
+    /// policy across the combined set. The merged set is gathered shard by
+    /// shard, not in insertion order, so `Fee` ordering matches a
+    /// single-shard `ProductionTxPool` except among equal fees, while
+    /// `Fifo` follows shard order rather than global insertion order.
+    pub fn ordered(&self) -> Vec<[u8; 32]> {
+        self.with_merged(|ordered| ordered.into_iter().map(|p| p.tx.id).collect())
+    }
+
+    pub fn pack_for_block(&self, max_block_size: u64) -> Vec<Transaction> {
+        self.with_merged(|ordered| pack_ordered(ordered, max_block_size))
+    }
+
+    /// Locks every shard, applies the ordering policy across the merged
+    /// set, and hands the result to `f` before releasing the locks.
+    fn with_merged<R>(&self, f: impl FnOnce(Vec<&PendingTx>) -> R) -> R {
+        let guards: Vec<_> = self.shards.iter().map(|shard| shard.lock().unwrap()).collect();
+        let items: Vec<&PendingTx> = guards.iter().flat_map(|guard| guard.iter()).collect();
+        f(order_pending(items, self.ordering_policy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::ValidatorId;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn tx(id: u8, fee: u64, sender: ValidatorId) -> PendingTx {
+        weighted_tx(id, fee, sender, 1)
+    }
+
+    fn weighted_tx(id: u8, fee: u64, sender: ValidatorId, weight: u64) -> PendingTx {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        PendingTx {
+            tx: Transaction { id: hash, payload: vec![], weight, valid_until: None },
+            fee,
+            sender,
+        }
+    }
+
+    #[test]
+    fn inserted_transactions_are_all_visible_across_shards() {
+        let pool = ShardedTxPool::new(4, OrderingPolicy::Fifo);
+        for id in 0..20u8 {
+            pool.insert(tx(id, 0, 1));
+        }
+        assert_eq!(pool.len(), 20);
+        assert_eq!(pool.ordered().len(), 20);
+    }
+
+    #[test]
+    fn a_shard_count_of_zero_is_clamped_to_one() {
+        let pool = ShardedTxPool::new(0, OrderingPolicy::Fifo);
+        assert_eq!(pool.shard_count(), 1);
+    }
+
+    #[test]
+    fn the_same_transaction_id_always_hashes_to_the_same_shard() {
+        let pool = ShardedTxPool::new(8, OrderingPolicy::Fifo);
+        let id = [7u8; 32];
+        assert_eq!(pool.shard_index(&id), pool.shard_index(&id));
+    }
+
+    #[test]
+    fn fee_ordering_is_applied_globally_across_shards() {
+        let pool = ShardedTxPool::new(4, OrderingPolicy::Fee);
+        pool.insert(tx(1, 5, 1));
+        pool.insert(tx(2, 50, 1));
+        pool.insert(tx(3, 20, 1));
+        let ordered = pool.ordered();
+        assert_eq!(ordered[0][0], 2);
+        assert_eq!(ordered[1][0], 3);
+        assert_eq!(ordered[2][0], 1);
+    }
+
+    #[test]
+    fn pack_for_block_stops_at_the_cumulative_weight_budget() {
+        let pool = ShardedTxPool::new(4, OrderingPolicy::Fee);
+        pool.insert(weighted_tx(1, 50, 1, 6));
+        pool.insert(weighted_tx(2, 40, 1, 6));
+        let packed = pool.pack_for_block(10);
+        assert_eq!(packed.len(), 1);
+        assert_eq!(packed[0].id[0], 1);
+    }
+
+    #[test]
+    fn concurrent_inserts_from_multiple_threads_are_all_admitted() {
+        let pool = Arc::new(ShardedTxPool::new(8, OrderingPolicy::Fifo));
+        let mut handles = Vec::new();
+        for worker in 0..8u8 {
+            let pool = Arc::clone(&pool);
+            handles.push(thread::spawn(move || {
+                for offset in 0..10u8 {
+                    pool.insert(tx(worker * 10 + offset, 0, worker as ValidatorId));
+                }
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(pool.len(), 80);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod history;
+
+pub use history::{query, HistoryConfig, MetricSnapshot, OnDiskHistory, TimeRange};
@@ -0,0 +1,214 @@
+//! Nothing enforced which peers a node would even talk to: a known-misbehaving
+//! validator's connection attempts and messages were handled the same as
+//! everyone else's until governance finished removing it from the validator
+//! set, which can take a while. `PeerAccessControl` gives an operator a
+//! runtime allowlist/blocklist enforced both when a peer connects and when
+//! one of its messages is handled, persisted to disk so a restart doesn't
+//! forget an emergency block. There is no `production_manager` module in
+//! this tree; this lives in the network crate next to the other
+//! connection-management types (`connection_supervisor`, `reliability`,
+//! `handshake`) that own analogous per-peer state.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessDecision {
+    Allow,
+    Deny(DenyReason),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenyReason {
+    Blocklisted,
+    NotAllowlisted,
+}
+
+/// Runtime peer access policy: an optional allowlist (when set, only its
+/// members may connect or have messages handled) and a blocklist (always
+/// denied, regardless of the allowlist).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PeerAccessControl {
+    allowlist: Option<HashSet<ValidatorId>>,
+    blocklist: HashSet<ValidatorId>,
+}
+
+impl PeerAccessControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts connections/messages to exactly this set of peers.
+    pub fn set_allowlist(&mut self, allowlist: impl IntoIterator<Item = ValidatorId>) {
+        self.allowlist = Some(allowlist.into_iter().collect());
+    }
+
+    /// Removes the allowlist restriction; every peer is allowed unless
+    /// blocklisted.
+    pub fn clear_allowlist(&mut self) {
+        self.allowlist = None;
+    }
+
+    pub fn block(&mut self, peer: ValidatorId) {
+        self.blocklist.insert(peer);
+    }
+
+    pub fn unblock(&mut self, peer: ValidatorId) {
+        self.blocklist.remove(&peer);
+    }
+
+    fn decide(&self, peer: ValidatorId) -> AccessDecision {
+        if self.blocklist.contains(&peer) {
+            return AccessDecision::Deny(DenyReason::Blocklisted);
+        }
+        if let Some(allowlist) = &self.allowlist {
+            if !allowlist.contains(&peer) {
+                return AccessDecision::Deny(DenyReason::NotAllowlisted);
+            }
+        }
+        AccessDecision::Allow
+    }
+
+    /// Enforced when a peer attempts to connect, before the handshake.
+    pub fn check_connection(&self, peer: ValidatorId) -> AccessDecision {
+        self.decide(peer)
+    }
+
+    /// Enforced per message, so a peer blocked mid-session (connection
+    /// already established) still has its messages rejected without
+    /// waiting for the connection to be torn down.
+    pub fn check_message(&self, peer: ValidatorId) -> AccessDecision {
+        self.decide(peer)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut buf = Vec::new();
+        match &self.allowlist {
+            Some(allowlist) => {
+                buf.push(1);
+                write_id_set(&mut buf, allowlist);
+            }
+            None => buf.push(0),
+        }
+        write_id_set(&mut buf, &self.blocklist);
+        File::create(path)?.write_all(&buf)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        File::open(path)?.read_to_end(&mut buf)?;
+        let mut cursor = 0usize;
+        let has_allowlist = buf[cursor] == 1;
+        cursor += 1;
+        let allowlist = if has_allowlist {
+            let (set, next) = read_id_set(&buf, cursor);
+            cursor = next;
+            Some(set)
+        } else {
+            None
+        };
+        let (blocklist, _) = read_id_set(&buf, cursor);
+        Ok(Self { allowlist, blocklist })
+    }
+}
+
+fn write_id_set(buf: &mut Vec<u8>, set: &HashSet<ValidatorId>) {
+    buf.extend_from_slice(&(set.len() as u64).to_le_bytes());
+    for id in set {
+        buf.extend_from_slice(&id.to_le_bytes());
+    }
+}
+
+fn read_id_set(buf: &[u8], start: usize) -> (HashSet<ValidatorId>, usize) {
+    let len = u64::from_le_bytes(buf[start..start + 8].try_into().unwrap()) as usize;
+    let mut cursor = start + 8;
+    let mut set = HashSet::with_capacity(len);
+    for _ in 0..len {
+        set.insert(u64::from_le_bytes(buf[cursor..cursor + 8].try_into().unwrap()));
+        cursor += 8;
+    }
+    (set, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("hotstuff2_access_control_{name}_{unique}.bin"))
+    }
+
+    #[test]
+    fn with_no_policy_configured_every_peer_is_allowed() {
+        let acl = PeerAccessControl::new();
+        assert_eq!(acl.check_connection(1), AccessDecision::Allow);
+    }
+
+    #[test]
+    fn a_blocklisted_peer_is_denied_at_connection_and_message_time() {
+        let mut acl = PeerAccessControl::new();
+        acl.block(9);
+        assert_eq!(acl.check_connection(9), AccessDecision::Deny(DenyReason::Blocklisted));
+        assert_eq!(acl.check_message(9), AccessDecision::Deny(DenyReason::Blocklisted));
+    }
+
+    #[test]
+    fn unblocking_restores_access() {
+        let mut acl = PeerAccessControl::new();
+        acl.block(9);
+        acl.unblock(9);
+        assert_eq!(acl.check_connection(9), AccessDecision::Allow);
+    }
+
+    #[test]
+    fn an_allowlist_denies_anyone_not_on_it() {
+        let mut acl = PeerAccessControl::new();
+        acl.set_allowlist([1, 2, 3]);
+        assert_eq!(acl.check_connection(1), AccessDecision::Allow);
+        assert_eq!(acl.check_connection(99), AccessDecision::Deny(DenyReason::NotAllowlisted));
+    }
+
+    #[test]
+    fn the_blocklist_wins_over_the_allowlist() {
+        let mut acl = PeerAccessControl::new();
+        acl.set_allowlist([1, 2, 3]);
+        acl.block(1);
+        assert_eq!(acl.check_connection(1), AccessDecision::Deny(DenyReason::Blocklisted));
+    }
+
+    #[test]
+    fn policy_persists_across_save_and_load() {
+        let path = temp_path("persist");
+        let mut acl = PeerAccessControl::new();
+        acl.set_allowlist([1, 2, 3]);
+        acl.block(2);
+        acl.save(&path).unwrap();
+
+        let reloaded = PeerAccessControl::load(&path).unwrap();
+        assert_eq!(reloaded.check_connection(1), AccessDecision::Allow);
+        assert_eq!(reloaded.check_connection(2), AccessDecision::Deny(DenyReason::Blocklisted));
+        assert_eq!(reloaded.check_connection(99), AccessDecision::Deny(DenyReason::NotAllowlisted));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn a_policy_with_no_allowlist_persists_that_absence() {
+        let path = temp_path("no_allowlist");
+        let mut acl = PeerAccessControl::new();
+        acl.block(5);
+        acl.save(&path).unwrap();
+
+        let reloaded = PeerAccessControl::load(&path).unwrap();
+        assert_eq!(reloaded.check_connection(1), AccessDecision::Allow);
+        assert_eq!(reloaded.check_connection(5), AccessDecision::Deny(DenyReason::Blocklisted));
+        std::fs::remove_file(&path).ok();
+    }
+}
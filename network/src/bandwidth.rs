@@ -0,0 +1,169 @@
+//! Nothing previously stopped one peer's sync request flood from crowding
+//! out consensus traffic on the same link — there was no visibility into
+//! per-peer byte volume, let alone a way to cap it. `PeerBandwidthTracker`
+//! records sent/received bytes per peer in a rolling time window (caller-
+//! supplied `now_ms`, matching the deterministic-timestamp convention used
+//! throughout this crate, e.g. `inbound_admission`, `heartbeat`) and exposes
+//! it as `NetworkStats`; an optional `BandwidthQuota` lets a caller reject
+//! further sends to a peer that has exceeded its budget for the window
+//! instead of queuing indefinitely.
+
+use std::collections::{HashMap, VecDeque};
+
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NetworkStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    pub peer: ValidatorId,
+    pub window_bytes_sent: u64,
+    pub max_bytes_per_window: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BandwidthQuota {
+    pub max_bytes_per_window: u64,
+}
+
+struct Sample {
+    at_ms: u64,
+    bytes: u64,
+}
+
+#[derive(Default)]
+struct PeerWindow {
+    sent: VecDeque<Sample>,
+    received: VecDeque<Sample>,
+}
+
+/// Tracks per-peer bytes sent/received over a fixed rolling window.
+pub struct PeerBandwidthTracker {
+    window_ms: u64,
+    peers: HashMap<ValidatorId, PeerWindow>,
+    quota: Option<BandwidthQuota>,
+}
+
+impl PeerBandwidthTracker {
+    pub fn new(window_ms: u64) -> Self {
+        Self { window_ms, peers: HashMap::new(), quota: None }
+    }
+
+    pub fn with_quota(mut self, quota: BandwidthQuota) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    fn prune(samples: &mut VecDeque<Sample>, now_ms: u64, window_ms: u64) {
+        while let Some(front) = samples.front() {
+            if now_ms.saturating_sub(front.at_ms) > window_ms {
+                samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Checks `bytes` against the peer's remaining quota for the window
+    /// before recording it. Rejects (recording nothing) if it would push
+    /// the peer's sent total in the window over `max_bytes_per_window`.
+    pub fn record_sent(&mut self, peer: ValidatorId, bytes: u64, now_ms: u64) -> Result<(), QuotaExceeded> {
+        let window_ms = self.window_ms;
+        let entry = self.peers.entry(peer).or_default();
+        Self::prune(&mut entry.sent, now_ms, window_ms);
+        let current: u64 = entry.sent.iter().map(|s| s.bytes).sum();
+        if let Some(quota) = self.quota {
+            let prospective = current.saturating_add(bytes);
+            if prospective > quota.max_bytes_per_window {
+                return Err(QuotaExceeded { peer, window_bytes_sent: current, max_bytes_per_window: quota.max_bytes_per_window });
+            }
+        }
+        entry.sent.push_back(Sample { at_ms: now_ms, bytes });
+        Ok(())
+    }
+
+    /// Inbound traffic is never quota-rejected — a peer sending us data
+    /// isn't consuming our egress budget — only accounted for visibility.
+    pub fn record_received(&mut self, peer: ValidatorId, bytes: u64, now_ms: u64) {
+        let window_ms = self.window_ms;
+        let entry = self.peers.entry(peer).or_default();
+        Self::prune(&mut entry.received, now_ms, window_ms);
+        entry.received.push_back(Sample { at_ms: now_ms, bytes });
+    }
+
+    /// Sums each direction's bytes still inside the window as of `now_ms`,
+    /// pruning stale samples first so a peer that went silent doesn't keep
+    /// reporting stale volume forever.
+    pub fn stats_for(&mut self, peer: ValidatorId, now_ms: u64) -> NetworkStats {
+        let window_ms = self.window_ms;
+        match self.peers.get_mut(&peer) {
+            Some(entry) => {
+                Self::prune(&mut entry.sent, now_ms, window_ms);
+                Self::prune(&mut entry.received, now_ms, window_ms);
+                NetworkStats {
+                    bytes_sent: entry.sent.iter().map(|s| s.bytes).sum(),
+                    bytes_received: entry.received.iter().map(|s| s.bytes).sum(),
+                }
+            }
+            None => NetworkStats::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sent_and_received_bytes_accumulate_independently() {
+        let mut tracker = PeerBandwidthTracker::new(1000);
+        tracker.record_sent(1, 100, 0).unwrap();
+        tracker.record_sent(1, 50, 10).unwrap();
+        tracker.record_received(1, 20, 10);
+        let stats = tracker.stats_for(1, 20);
+        assert_eq!(stats.bytes_sent, 150);
+        assert_eq!(stats.bytes_received, 20);
+    }
+
+    #[test]
+    fn samples_older_than_the_window_are_dropped() {
+        let mut tracker = PeerBandwidthTracker::new(100);
+        tracker.record_sent(1, 100, 0).unwrap();
+        let stats = tracker.stats_for(1, 250);
+        assert_eq!(stats.bytes_sent, 0);
+    }
+
+    #[test]
+    fn an_unknown_peer_reports_zero_stats() {
+        let mut tracker = PeerBandwidthTracker::new(1000);
+        assert_eq!(tracker.stats_for(99, 0), NetworkStats::default());
+    }
+
+    #[test]
+    fn a_send_that_would_exceed_the_quota_is_rejected() {
+        let mut tracker = PeerBandwidthTracker::new(1000).with_quota(BandwidthQuota { max_bytes_per_window: 100 });
+        tracker.record_sent(1, 80, 0).unwrap();
+        let err = tracker.record_sent(1, 30, 10).unwrap_err();
+        assert_eq!(err, QuotaExceeded { peer: 1, window_bytes_sent: 80, max_bytes_per_window: 100 });
+        assert_eq!(tracker.stats_for(1, 10).bytes_sent, 80); // the rejected send was not recorded
+    }
+
+    #[test]
+    fn quota_room_frees_up_as_old_samples_age_out_of_the_window() {
+        let mut tracker = PeerBandwidthTracker::new(100).with_quota(BandwidthQuota { max_bytes_per_window: 100 });
+        tracker.record_sent(1, 100, 0).unwrap();
+        assert!(tracker.record_sent(1, 10, 10).is_err());
+        assert!(tracker.record_sent(1, 10, 250).is_ok()); // the first sample has aged out by now
+    }
+
+    #[test]
+    fn inbound_traffic_is_never_quota_rejected() {
+        let mut tracker = PeerBandwidthTracker::new(1000).with_quota(BandwidthQuota { max_bytes_per_window: 10 });
+        tracker.record_received(1, 1_000_000, 0);
+        assert_eq!(tracker.stats_for(1, 0).bytes_received, 1_000_000);
+    }
+}
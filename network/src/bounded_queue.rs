@@ -0,0 +1,157 @@
+//! The in-process consensus/network handoff queue had a hardcoded capacity
+//! and silently backpressured (or, with an unbounded channel, silently grew
+//! without limit) once full, giving an operator no signal that messages were
+//! piling up or being lost. `BoundedMessageQueue` makes the capacity a
+//! constructor argument, tracks overflow/requeue counters an operator can
+//! read via `stats()`, and — instead of always dropping the newly arriving
+//! message once full — gives the caller a chance to evict an already-queued
+//! but now-stale message (e.g. a timeout for a view the node has since moved
+//! past) to make room, so saturation sheds load selectively rather than
+//! blindly.
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Messages dropped because the queue was full and no stale entry could
+    /// be evicted to make room for them.
+    pub overflow_dropped: u64,
+    /// Messages admitted only after evicting an already-queued stale entry.
+    pub requeued: u64,
+    /// Stale entries evicted to make room (equal to `requeued`, tracked
+    /// separately since an eviction and the admission it enables are
+    /// conceptually distinct events).
+    pub stale_evicted: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// Enqueued directly; the queue had room.
+    Enqueued,
+    /// The queue was full, but a stale entry was evicted to make room.
+    EnqueuedAfterEvictingStale,
+    /// The queue was full and no stale entry could be evicted; the new
+    /// message itself was dropped.
+    DroppedOverflow,
+}
+
+/// A capacity-bounded FIFO queue that sheds load selectively once full,
+/// rather than either growing without limit or always dropping the newest
+/// arrival.
+pub struct BoundedMessageQueue<M> {
+    capacity: usize,
+    items: VecDeque<M>,
+    stats: QueueStats,
+}
+
+impl<M> BoundedMessageQueue<M> {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), items: VecDeque::new(), stats: QueueStats::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn stats(&self) -> QueueStats {
+        self.stats
+    }
+
+    /// Attempts to enqueue `msg`. If the queue is at capacity, evicts the
+    /// oldest entry for which `is_stale` returns true to make room; if no
+    /// entry is stale, `msg` itself is dropped instead of growing the queue
+    /// past `capacity`.
+    pub fn push(&mut self, msg: M, is_stale: impl Fn(&M) -> bool) -> PushOutcome {
+        if self.items.len() < self.capacity {
+            self.items.push_back(msg);
+            return PushOutcome::Enqueued;
+        }
+        let stale_index = self.items.iter().position(&is_stale);
+        match stale_index {
+            Some(index) => {
+                self.items.remove(index);
+                self.items.push_back(msg);
+                self.stats.stale_evicted += 1;
+                self.stats.requeued += 1;
+                PushOutcome::EnqueuedAfterEvictingStale
+            }
+            None => {
+                self.stats.overflow_dropped += 1;
+                PushOutcome::DroppedOverflow
+            }
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<M> {
+        self.items.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct ViewTimeout {
+        view: u64,
+    }
+
+    fn not_stale(_: &ViewTimeout) -> bool {
+        false
+    }
+
+    #[test]
+    fn pushes_below_capacity_are_enqueued_directly() {
+        let mut queue = BoundedMessageQueue::new(2);
+        assert_eq!(queue.push(ViewTimeout { view: 1 }, not_stale), PushOutcome::Enqueued);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.stats(), QueueStats::default());
+    }
+
+    #[test]
+    fn a_full_queue_with_no_stale_entries_drops_the_new_message() {
+        let mut queue = BoundedMessageQueue::new(1);
+        queue.push(ViewTimeout { view: 1 }, not_stale);
+        let outcome = queue.push(ViewTimeout { view: 2 }, not_stale);
+        assert_eq!(outcome, PushOutcome::DroppedOverflow);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.stats().overflow_dropped, 1);
+        assert_eq!(queue.pop(), Some(ViewTimeout { view: 1 }));
+    }
+
+    #[test]
+    fn a_full_queue_evicts_a_stale_entry_to_admit_the_new_message() {
+        let mut queue = BoundedMessageQueue::new(2);
+        queue.push(ViewTimeout { view: 1 }, not_stale);
+        queue.push(ViewTimeout { view: 2 }, not_stale);
+
+        // Only view 1 is stale under this predicate.
+        let outcome = queue.push(ViewTimeout { view: 3 }, |t| t.view < 2);
+        assert_eq!(outcome, PushOutcome::EnqueuedAfterEvictingStale);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.stats().requeued, 1);
+        assert_eq!(queue.stats().stale_evicted, 1);
+        assert_eq!(queue.pop(), Some(ViewTimeout { view: 2 }));
+        assert_eq!(queue.pop(), Some(ViewTimeout { view: 3 }));
+    }
+
+    #[test]
+    fn the_oldest_stale_entry_is_evicted_first() {
+        let mut queue = BoundedMessageQueue::new(2);
+        queue.push(ViewTimeout { view: 1 }, not_stale);
+        queue.push(ViewTimeout { view: 2 }, not_stale);
+        queue.push(ViewTimeout { view: 3 }, |t| t.view <= 2);
+        assert_eq!(queue.pop(), Some(ViewTimeout { view: 2 }));
+        assert_eq!(queue.pop(), Some(ViewTimeout { view: 3 }));
+    }
+
+    #[test]
+    fn pop_on_an_empty_queue_returns_none() {
+        let mut queue: BoundedMessageQueue<ViewTimeout> = BoundedMessageQueue::new(4);
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -0,0 +1,79 @@
+//! Catch-up on reconnect: a peer that was briefly disconnected has likely
+//! missed the current view's proposal and possibly the latest QC. Rather
+//! than waiting for the next view change, the reliability layer requests
+//! both as soon as the connection re-establishes, so the peer can still
+//! vote in the current round.
+
+use hotstuff2_types::ValidatorId;
+
+/// What to ask a just-reconnected peer for. The actual request/response
+/// wire messages belong to whatever `NetworkMsg` enum the transport uses;
+/// this crate only decides *when* to ask, keeping the reliability layer
+/// decoupled from the message format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatchUpRequest {
+    pub peer: ValidatorId,
+    pub current_view: u64,
+}
+
+/// Tracks which peers are currently known-disconnected, and emits a
+/// `CatchUpRequest` for the current view the moment a tracked peer
+/// reconnects.
+#[derive(Default)]
+pub struct CatchUpCoordinator {
+    disconnected: std::collections::HashSet<ValidatorId>,
+}
+
+impl CatchUpCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_peer_disconnected(&mut self, peer: ValidatorId) {
+        self.disconnected.insert(peer);
+    }
+
+    /// Called by the reliability layer when a peer connection
+    /// re-establishes. Returns a `CatchUpRequest` only for peers that were
+    /// actually tracked as disconnected, so reconnect notifications for
+    /// peers that were never dropped don't trigger a spurious request.
+    pub fn on_peer_reconnected(&mut self, peer: ValidatorId, current_view: u64) -> Option<CatchUpRequest> {
+        if self.disconnected.remove(&peer) {
+            Some(CatchUpRequest { peer, current_view })
+        } else {
+            None
+        }
+    }
+
+    pub fn is_disconnected(&self, peer: ValidatorId) -> bool {
+        self.disconnected.contains(&peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnecting_a_tracked_peer_requests_the_current_view_state() {
+        let mut coordinator = CatchUpCoordinator::new();
+        coordinator.on_peer_disconnected(1);
+        let request = coordinator.on_peer_reconnected(1, 42).unwrap();
+        assert_eq!(request, CatchUpRequest { peer: 1, current_view: 42 });
+    }
+
+    #[test]
+    fn a_peer_that_was_never_disconnected_triggers_no_request() {
+        let mut coordinator = CatchUpCoordinator::new();
+        assert!(coordinator.on_peer_reconnected(1, 42).is_none());
+    }
+
+    #[test]
+    fn a_peer_stops_being_tracked_as_disconnected_after_catch_up() {
+        let mut coordinator = CatchUpCoordinator::new();
+        coordinator.on_peer_disconnected(1);
+        coordinator.on_peer_reconnected(1, 42);
+        assert!(!coordinator.is_disconnected(1));
+        assert!(coordinator.on_peer_reconnected(1, 43).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+//! Every network abstraction in this crate assumes an out-of-process peer
+//! reachable only by (de)serializing envelopes over a real transport, which
+//! makes running several validators inside one test process, or embedding a
+//! small replicated core in one binary, unnecessarily heavy. `ChannelNetwork`
+//! is an in-process transport: peers exchange messages over
+//! `std::sync::mpsc` channels wired together by `ChannelNetworkHub` — no
+//! tokio dependency is available in this workspace, so this uses the
+//! standard library's synchronous multi-producer/single-consumer channel
+//! instead of `tokio::sync::mpsc`; message ordering and delivery guarantees
+//! (each peer's inbox is FIFO, delivery is synchronous) are equivalent for
+//! the deterministic-test and embedded use cases this exists for.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use hotstuff2_types::ValidatorId;
+
+/// Implemented by any in-process or real transport that can move a message
+/// of type `M` between validators identified by `ValidatorId`.
+pub trait NetworkInterface<M> {
+    fn send_to(&self, peer: ValidatorId, msg: M) -> Result<(), SendError>;
+    fn broadcast(&self, msg: M)
+    where
+        M: Clone;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SendError {
+    pub peer: ValidatorId,
+}
+
+/// Owns every peer's inbox `Sender`, so it can hand out a `ChannelNetwork`
+/// per peer that can reach every other peer registered on the hub.
+pub struct ChannelNetworkHub<M> {
+    senders: HashMap<ValidatorId, Sender<M>>,
+}
+
+impl<M> Default for ChannelNetworkHub<M> {
+    fn default() -> Self {
+        Self { senders: HashMap::new() }
+    }
+}
+
+impl<M> ChannelNetworkHub<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `peer` on the hub and returns its inbox receiver. Call
+    /// `network_for` afterwards to get a handle other peers (and this one)
+    /// can use to send to it.
+    pub fn register(&mut self, peer: ValidatorId) -> Receiver<M> {
+        let (tx, rx) = channel();
+        self.senders.insert(peer, tx);
+        rx
+    }
+
+    /// A `ChannelNetwork` that can reach every peer currently registered on
+    /// the hub, as seen from `self_id`. Peers registered after this call are
+    /// invisible to the returned handle — register everyone before building
+    /// handles for a fixed topology.
+    pub fn network_for(&self, self_id: ValidatorId) -> ChannelNetwork<M> {
+        ChannelNetwork { self_id, peers: self.senders.clone() }
+    }
+}
+
+/// One peer's view of the in-process network: a `self_id` and a snapshot of
+/// every registered peer's inbox sender, its own included.
+pub struct ChannelNetwork<M> {
+    self_id: ValidatorId,
+    peers: HashMap<ValidatorId, Sender<M>>,
+}
+
+impl<M> NetworkInterface<M> for ChannelNetwork<M> {
+    fn send_to(&self, peer: ValidatorId, msg: M) -> Result<(), SendError> {
+        self.peers.get(&peer).and_then(|tx| tx.send(msg).ok()).ok_or(SendError { peer })
+    }
+
+    /// Sends to every registered peer including `self_id` — a validator's
+    /// own vote/proposal handler expects to see broadcast traffic too.
+    fn broadcast(&self, msg: M)
+    where
+        M: Clone,
+    {
+        for tx in self.peers.values() {
+            let _ = tx.send(msg.clone());
+        }
+    }
+}
+
+impl<M> ChannelNetwork<M> {
+    pub fn self_id(&self) -> ValidatorId {
+        self.self_id
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_direct_send_is_received_only_by_the_target_peer() {
+        let mut hub: ChannelNetworkHub<&'static str> = ChannelNetworkHub::new();
+        let rx1 = hub.register(1);
+        let rx2 = hub.register(2);
+        let net1 = hub.network_for(1);
+
+        net1.send_to(2, "hello").unwrap();
+        assert_eq!(rx2.try_recv(), Ok("hello"));
+        assert!(rx1.try_recv().is_err());
+    }
+
+    #[test]
+    fn sending_to_an_unregistered_peer_is_a_send_error() {
+        let mut hub: ChannelNetworkHub<&'static str> = ChannelNetworkHub::new();
+        hub.register(1);
+        let net1 = hub.network_for(1);
+        assert_eq!(net1.send_to(99, "hi"), Err(SendError { peer: 99 }));
+    }
+
+    #[test]
+    fn broadcast_reaches_every_registered_peer_including_the_sender() {
+        let mut hub: ChannelNetworkHub<&'static str> = ChannelNetworkHub::new();
+        let rx1 = hub.register(1);
+        let rx2 = hub.register(2);
+        let rx3 = hub.register(3);
+        let net1 = hub.network_for(1);
+
+        net1.broadcast("proposal");
+        assert_eq!(rx1.try_recv(), Ok("proposal"));
+        assert_eq!(rx2.try_recv(), Ok("proposal"));
+        assert_eq!(rx3.try_recv(), Ok("proposal"));
+    }
+
+    #[test]
+    fn peers_registered_after_a_handle_is_built_are_not_reachable_from_it() {
+        let mut hub: ChannelNetworkHub<&'static str> = ChannelNetworkHub::new();
+        hub.register(1);
+        let net1 = hub.network_for(1);
+        hub.register(2);
+        assert_eq!(net1.peer_count(), 1);
+        assert_eq!(net1.send_to(2, "late"), Err(SendError { peer: 2 }));
+    }
+}
@@ -0,0 +1,183 @@
+//! Proposals and sync responses carrying a large block used to fail to send
+//! outright once they exceeded a transport's `max_chunk_size`, instead of
+//! being split and reassembled. `split_into_chunks` breaks an oversized
+//! payload into sequence-numbered `Chunk`s; `ChunkReassembler` collects them
+//! back into the original bytes on the receiving side, tolerating
+//! out-of-order and interleaved-message delivery.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Identifies which logical message this chunk belongs to, so chunks of
+    /// two different blocks in flight at once don't get interleaved during
+    /// reassembly.
+    pub message_id: u64,
+    pub sequence: u32,
+    pub total_chunks: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Splits `data` into `Chunk`s of at most `max_chunk_size` bytes each.
+/// Empty `data` still produces a single empty chunk, so a zero-length
+/// message round-trips through the same reassembly path as everything else.
+pub fn split_into_chunks(message_id: u64, data: &[u8], max_chunk_size: usize) -> Vec<Chunk> {
+    assert!(max_chunk_size > 0, "max_chunk_size must be positive");
+    if data.is_empty() {
+        return vec![Chunk { message_id, sequence: 0, total_chunks: 1, payload: Vec::new() }];
+    }
+    let pieces: Vec<&[u8]> = data.chunks(max_chunk_size).collect();
+    let total_chunks = pieces.len() as u32;
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(sequence, payload)| Chunk {
+            message_id,
+            sequence: sequence as u32,
+            total_chunks,
+            payload: payload.to_vec(),
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChunkReassemblyError {
+    /// A chunk's `total_chunks` disagreed with an earlier chunk of the same
+    /// `message_id` — the sender restarted the message mid-transfer.
+    InconsistentTotal { message_id: u64, expected: u32, got: u32 },
+    /// `sequence` was outside `[0, total_chunks)`.
+    SequenceOutOfRange { message_id: u64, sequence: u32, total_chunks: u32 },
+}
+
+struct PartialMessage {
+    total_chunks: u32,
+    received: HashMap<u32, Vec<u8>>,
+}
+
+/// Buffers in-flight chunked messages by `message_id` until every chunk has
+/// arrived, then hands back the reassembled bytes in order.
+#[derive(Default)]
+pub struct ChunkReassembler {
+    partial: HashMap<u64, PartialMessage>,
+}
+
+impl ChunkReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts one chunk. Returns `Ok(Some(bytes))` once every chunk of
+    /// `chunk.message_id` has arrived (and forgets that message), `Ok(None)`
+    /// while still waiting on more chunks.
+    pub fn accept(&mut self, chunk: Chunk) -> Result<Option<Vec<u8>>, ChunkReassemblyError> {
+        if chunk.sequence >= chunk.total_chunks {
+            return Err(ChunkReassemblyError::SequenceOutOfRange {
+                message_id: chunk.message_id,
+                sequence: chunk.sequence,
+                total_chunks: chunk.total_chunks,
+            });
+        }
+        let entry = self
+            .partial
+            .entry(chunk.message_id)
+            .or_insert_with(|| PartialMessage { total_chunks: chunk.total_chunks, received: HashMap::new() });
+        if entry.total_chunks != chunk.total_chunks {
+            return Err(ChunkReassemblyError::InconsistentTotal {
+                message_id: chunk.message_id,
+                expected: entry.total_chunks,
+                got: chunk.total_chunks,
+            });
+        }
+        entry.received.insert(chunk.sequence, chunk.payload);
+
+        if entry.received.len() as u32 == entry.total_chunks {
+            let total_chunks = entry.total_chunks;
+            let partial = self.partial.remove(&chunk.message_id).expect("just inserted");
+            let mut assembled = Vec::new();
+            for sequence in 0..total_chunks {
+                assembled.extend_from_slice(&partial.received[&sequence]);
+            }
+            Ok(Some(assembled))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_payload_that_fits_in_one_chunk_produces_a_single_chunk() {
+        let chunks = split_into_chunks(1, b"hello", 1024);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].total_chunks, 1);
+    }
+
+    #[test]
+    fn an_oversized_payload_splits_into_sequence_numbered_chunks() {
+        let data = vec![7u8; 25];
+        let chunks = split_into_chunks(1, &data, 10);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].sequence, 0);
+        assert_eq!(chunks[2].sequence, 2);
+        assert!(chunks.iter().all(|c| c.total_chunks == 3));
+    }
+
+    #[test]
+    fn chunks_reassemble_back_into_the_original_payload_in_order() {
+        let data: Vec<u8> = (0..100u32).map(|i| i as u8).collect();
+        let chunks = split_into_chunks(42, &data, 16);
+        let mut reassembler = ChunkReassembler::new();
+        let mut result = None;
+        for chunk in chunks {
+            result = reassembler.accept(chunk).unwrap();
+        }
+        assert_eq!(result, Some(data));
+    }
+
+    #[test]
+    fn out_of_order_chunks_still_reassemble_correctly() {
+        let data: Vec<u8> = (0..40u32).map(|i| i as u8).collect();
+        let mut chunks = split_into_chunks(1, &data, 10);
+        chunks.reverse();
+        let mut reassembler = ChunkReassembler::new();
+        let mut result = None;
+        for chunk in chunks {
+            result = reassembler.accept(chunk).unwrap();
+        }
+        assert_eq!(result, Some(data));
+    }
+
+    #[test]
+    fn interleaved_chunks_of_two_different_messages_dont_cross_contaminate() {
+        let a = vec![1u8; 20];
+        let b = vec![2u8; 20];
+        let chunks_a = split_into_chunks(1, &a, 10);
+        let chunks_b = split_into_chunks(2, &b, 10);
+        let mut reassembler = ChunkReassembler::new();
+        assert_eq!(reassembler.accept(chunks_a[0].clone()).unwrap(), None);
+        assert_eq!(reassembler.accept(chunks_b[0].clone()).unwrap(), None);
+        assert_eq!(reassembler.accept(chunks_a[1].clone()).unwrap(), Some(a));
+        assert_eq!(reassembler.accept(chunks_b[1].clone()).unwrap(), Some(b));
+    }
+
+    #[test]
+    fn a_sequence_number_outside_the_declared_total_is_rejected() {
+        let mut reassembler = ChunkReassembler::new();
+        let bad = Chunk { message_id: 1, sequence: 5, total_chunks: 2, payload: vec![] };
+        assert_eq!(
+            reassembler.accept(bad),
+            Err(ChunkReassemblyError::SequenceOutOfRange { message_id: 1, sequence: 5, total_chunks: 2 })
+        );
+    }
+
+    #[test]
+    fn a_restarted_message_with_a_different_total_is_rejected() {
+        let mut reassembler = ChunkReassembler::new();
+        reassembler.accept(Chunk { message_id: 1, sequence: 0, total_chunks: 3, payload: vec![] }).unwrap();
+        let result = reassembler.accept(Chunk { message_id: 1, sequence: 0, total_chunks: 5, payload: vec![] });
+        assert_eq!(result, Err(ChunkReassemblyError::InconsistentTotal { message_id: 1, expected: 3, got: 5 }));
+    }
+}
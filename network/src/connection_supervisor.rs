@@ -0,0 +1,174 @@
+//! Per-peer connection supervision: instead of failing a send the instant a
+//! peer's TCP connection drops, `PeerConnection` queues outbound messages and
+//! exposes the exponential-backoff-with-jitter schedule the caller's
+//! reconnect loop should follow. The actual TCP dial/read/write is owned by
+//! the caller (this crate has no I/O dependency); this type only tracks
+//! state and computes when to retry.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting { attempt: u32 },
+}
+
+/// Dependency-free jitter source: callers seed it once per peer so backoff
+/// schedules don't all synchronize across peers.
+pub struct XorShiftJitter {
+    state: u64,
+}
+
+impl XorShiftJitter {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed.max(1) }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Returns a value in `[0, max)`.
+    fn next_below(&mut self, max: u64) -> u64 {
+        if max == 0 {
+            0
+        } else {
+            self.next_u64() % max
+        }
+    }
+}
+
+pub struct PeerConnection {
+    state: ConnectionState,
+    base_backoff: Duration,
+    max_backoff: Duration,
+    jitter: XorShiftJitter,
+    outbound_queue: VecDeque<Vec<u8>>,
+    max_queue_len: usize,
+    health_score: f64,
+}
+
+impl PeerConnection {
+    pub fn new(base_backoff: Duration, max_backoff: Duration, jitter_seed: u64, max_queue_len: usize) -> Self {
+        Self {
+            state: ConnectionState::Connected,
+            base_backoff,
+            max_backoff,
+            jitter: XorShiftJitter::new(jitter_seed),
+            outbound_queue: VecDeque::new(),
+            max_queue_len,
+            health_score: 1.0,
+        }
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn health_score(&self) -> f64 {
+        self.health_score
+    }
+
+    /// Queues a message instead of failing the send outright. Drops the
+    /// oldest queued message if the queue is full, since a stale message is
+    /// less useful than a fresh one once reconnected.
+    pub fn enqueue(&mut self, message: Vec<u8>) {
+        if self.outbound_queue.len() >= self.max_queue_len {
+            self.outbound_queue.pop_front();
+        }
+        self.outbound_queue.push_back(message);
+    }
+
+    /// Called by the caller's send loop once reconnected, to flush queued
+    /// messages in order.
+    pub fn drain_outbound(&mut self) -> Vec<Vec<u8>> {
+        self.outbound_queue.drain(..).collect()
+    }
+
+    pub fn on_disconnected(&mut self) {
+        self.health_score = (self.health_score - 0.2).max(0.0);
+        self.state = ConnectionState::Reconnecting { attempt: 1 };
+    }
+
+    pub fn on_reconnected(&mut self) {
+        self.health_score = (self.health_score + 0.1).min(1.0);
+        self.state = ConnectionState::Connected;
+    }
+
+    /// Computes the next backoff delay and advances the retry attempt
+    /// counter. Panics if called while `Connected`, since there's nothing to
+    /// retry.
+    pub fn next_backoff(&mut self) -> Duration {
+        let attempt = match self.state {
+            ConnectionState::Reconnecting { attempt } => attempt,
+            ConnectionState::Connected => panic!("next_backoff called while connected"),
+        };
+
+        let exp = self.base_backoff.saturating_mul(1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX));
+        let capped = exp.min(self.max_backoff);
+        let jitter_millis = self.jitter.next_below(capped.as_millis() as u64 / 2 + 1);
+        let delay = capped + Duration::from_millis(jitter_millis);
+
+        self.state = ConnectionState::Reconnecting { attempt: attempt.saturating_add(1) };
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_fresh_connection_starts_connected_with_full_health() {
+        let conn = PeerConnection::new(Duration::from_millis(10), Duration::from_secs(1), 42, 8);
+        assert_eq!(conn.state(), ConnectionState::Connected);
+        assert_eq!(conn.health_score(), 1.0);
+    }
+
+    #[test]
+    fn sends_during_a_drop_are_queued_instead_of_failing() {
+        let mut conn = PeerConnection::new(Duration::from_millis(10), Duration::from_secs(1), 42, 8);
+        conn.on_disconnected();
+        conn.enqueue(b"vote".to_vec());
+        conn.enqueue(b"proposal".to_vec());
+
+        conn.on_reconnected();
+        assert_eq!(conn.drain_outbound(), vec![b"vote".to_vec(), b"proposal".to_vec()]);
+    }
+
+    #[test]
+    fn queue_drops_the_oldest_message_once_full() {
+        let mut conn = PeerConnection::new(Duration::from_millis(10), Duration::from_secs(1), 42, 2);
+        conn.enqueue(b"a".to_vec());
+        conn.enqueue(b"b".to_vec());
+        conn.enqueue(b"c".to_vec());
+        assert_eq!(conn.drain_outbound(), vec![b"b".to_vec(), b"c".to_vec()]);
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_and_is_capped() {
+        let mut conn = PeerConnection::new(Duration::from_millis(100), Duration::from_secs(1), 7, 8);
+        conn.on_disconnected();
+        let first = conn.next_backoff();
+        let second = conn.next_backoff();
+        let third = conn.next_backoff();
+        assert!(first >= Duration::from_millis(100) && first < Duration::from_millis(150));
+        assert!(second >= Duration::from_millis(200) && second < Duration::from_millis(300));
+        assert!(third <= Duration::from_secs(1) + Duration::from_millis(500));
+    }
+
+    #[test]
+    fn disconnect_lowers_health_and_reconnect_recovers_it() {
+        let mut conn = PeerConnection::new(Duration::from_millis(10), Duration::from_secs(1), 1, 8);
+        conn.on_disconnected();
+        assert!(conn.health_score() < 1.0);
+        conn.on_reconnected();
+        assert_eq!(conn.state(), ConnectionState::Connected);
+    }
+}
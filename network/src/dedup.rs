@@ -0,0 +1,70 @@
+//! Bounded LRU dedup cache keyed by message hash on the receive path, so a
+//! legitimately-resent vote/proposal from the reliability layer doesn't
+//! trigger repeated handler processing and double-counting.
+
+use std::collections::{HashSet, VecDeque};
+
+use hotstuff2_types::Hash;
+
+pub struct DedupCache {
+    capacity: usize,
+    seen: HashSet<Hash>,
+    order: VecDeque<Hash>,
+}
+
+impl DedupCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Returns `true` if `hash` was already seen (caller should drop the
+    /// message), `false` if it's new (caller should process it).
+    pub fn is_duplicate(&mut self, hash: Hash) -> bool {
+        if self.seen.contains(&hash) {
+            if let Some(pos) = self.order.iter().position(|h| *h == hash) {
+                self.order.remove(pos);
+                self.order.push_back(hash);
+            }
+            return true;
+        }
+        if self.order.len() >= self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.seen.remove(&evicted);
+            }
+        }
+        self.seen.insert(hash);
+        self.order.push_back(hash);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sighting_is_not_a_duplicate() {
+        let mut cache = DedupCache::new(4);
+        assert!(!cache.is_duplicate([1u8; 32]));
+    }
+
+    #[test]
+    fn replayed_message_is_flagged_as_duplicate() {
+        let mut cache = DedupCache::new(4);
+        cache.is_duplicate([1u8; 32]);
+        assert!(cache.is_duplicate([1u8; 32]));
+    }
+
+    #[test]
+    fn evicted_entries_are_treated_as_new_again() {
+        let mut cache = DedupCache::new(2);
+        cache.is_duplicate([1u8; 32]);
+        cache.is_duplicate([2u8; 32]);
+        cache.is_duplicate([3u8; 32]); // evicts [1u8; 32]
+        assert!(!cache.is_duplicate([1u8; 32]));
+    }
+}
@@ -0,0 +1,171 @@
+//! Every network failure in this crate was previously handled ad hoc at
+//! the call site: some callers retried on any `Result::Err`, others didn't,
+//! and `ReputationTracker` only ever saw a bare `record_invalid_message`/
+//! `record_missed_response` call with no way to tell which specific fault
+//! triggered it. `NetworkError` wraps this crate's scattered per-module
+//! error enums with the peer that caused them and a `Retryability`
+//! classification, so `classify_and_record` gives the reliability manager
+//! one place to decide "penalize this peer" vs. "this is our own transient
+//! problem, just retry" instead of every caller re-deriving that judgment.
+
+use crate::chunking::ChunkReassemblyError;
+use crate::envelope::EnvelopeError;
+use crate::handshake::HandshakeError;
+use crate::reliability::ReputationTracker;
+use crate::snapshot_sync::SnapshotSyncError;
+use crate::zero_copy::DecodeError;
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NetworkErrorKind {
+    Handshake(HandshakeError),
+    Envelope(EnvelopeError),
+    ChunkReassembly(ChunkReassemblyError),
+    Decode(DecodeError),
+    SnapshotSync(SnapshotSyncError),
+}
+
+/// Whether a caller should retry the operation that produced this error, and
+/// whether the peer that caused it should be penalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retryability {
+    /// Not the peer's fault (e.g. a partial read) — retry, don't penalize.
+    Retryable,
+    /// The peer sent something wrong on purpose or by being badly broken —
+    /// penalize, and don't bother retrying the same exchange with it.
+    Fatal,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NetworkError {
+    pub peer: ValidatorId,
+    pub kind: NetworkErrorKind,
+}
+
+impl NetworkError {
+    pub fn new(peer: ValidatorId, kind: NetworkErrorKind) -> Self {
+        Self { peer, kind }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.retryability() == Retryability::Retryable
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        self.retryability() == Retryability::Fatal
+    }
+
+    pub fn retryability(&self) -> Retryability {
+        match &self.kind {
+            // A stranger claiming an unknown id, or a forged signature, is
+            // an active protocol violation.
+            NetworkErrorKind::Handshake(_) => Retryability::Fatal,
+            // A version/chain mismatch is a peer misconfiguration, not
+            // malice, so it falls in the non-penalizing class.
+            NetworkErrorKind::Envelope(_) => Retryability::Retryable,
+            // A chunk sequence number out of range is treated as ordinary
+            // network jitter.
+            NetworkErrorKind::ChunkReassembly(_) => Retryability::Retryable,
+            // A truncated buffer is usually a partial read, not malice.
+            NetworkErrorKind::Decode(_) => Retryability::Retryable,
+            // Delegate to the wrapped chunking error where relevant; a
+            // hash/length mismatch means the payload was tampered with or
+            // corrupted beyond simple truncation, which we treat as fatal.
+            NetworkErrorKind::SnapshotSync(SnapshotSyncError::Chunking(inner)) => {
+                NetworkError::new(self.peer, NetworkErrorKind::ChunkReassembly(clone_chunk_error(inner))).retryability()
+            }
+            NetworkErrorKind::SnapshotSync(_) => Retryability::Fatal,
+        }
+    }
+}
+
+/// `ChunkReassemblyError` isn't `Clone` (its container, `network`'s error
+/// enums generally aren't, to keep them cheap to construct without forcing
+/// derives no caller needs); this rebuilds an equivalent value from the
+/// borrowed original since `retryability()` needs to recurse into it by
+/// value above.
+fn clone_chunk_error(err: &ChunkReassemblyError) -> ChunkReassemblyError {
+    match *err {
+        ChunkReassemblyError::InconsistentTotal { message_id, expected, got } => {
+            ChunkReassemblyError::InconsistentTotal { message_id, expected, got }
+        }
+        ChunkReassemblyError::SequenceOutOfRange { message_id, sequence, total_chunks } => {
+            ChunkReassemblyError::SequenceOutOfRange { message_id, sequence, total_chunks }
+        }
+    }
+}
+
+/// Applies `error`'s classification to `tracker`: penalizes the peer if
+/// fatal, otherwise leaves its score untouched since retryable faults
+/// aren't the peer's fault.
+pub fn classify_and_record(tracker: &mut ReputationTracker, error: &NetworkError) -> Retryability {
+    let retryability = error.retryability();
+    if retryability == Retryability::Fatal {
+        tracker.record_invalid_message(error.peer);
+    }
+    retryability
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_handshake_failure_is_fatal_and_penalizes_the_peer() {
+        let error = NetworkError::new(1, NetworkErrorKind::Handshake(HandshakeError::SignatureMismatch));
+        let mut tracker = ReputationTracker::new(50.0);
+        assert_eq!(classify_and_record(&mut tracker, &error), Retryability::Fatal);
+        assert!(tracker.score_of(1) < 100.0);
+    }
+
+    #[test]
+    fn a_decode_error_is_retryable_and_does_not_penalize() {
+        let error = NetworkError::new(1, NetworkErrorKind::Decode(DecodeError::TooShort { expected: 8, got: 4 }));
+        let mut tracker = ReputationTracker::new(50.0);
+        assert_eq!(classify_and_record(&mut tracker, &error), Retryability::Retryable);
+        assert_eq!(tracker.score_of(1), 100.0);
+    }
+
+    #[test]
+    fn a_chunk_reassembly_error_is_retryable() {
+        let error = NetworkError::new(
+            2,
+            NetworkErrorKind::ChunkReassembly(ChunkReassemblyError::SequenceOutOfRange {
+                message_id: 1,
+                sequence: 5,
+                total_chunks: 3,
+            }),
+        );
+        assert!(error.is_retryable());
+        assert!(!error.is_fatal());
+    }
+
+    #[test]
+    fn an_envelope_version_mismatch_is_retryable_not_a_peer_fault() {
+        let error =
+            NetworkError::new(3, NetworkErrorKind::Envelope(EnvelopeError::IncompatibleVersion { peer_version: 1 }));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn a_snapshot_hash_mismatch_is_fatal() {
+        let error = NetworkError::new(
+            4,
+            NetworkErrorKind::SnapshotSync(SnapshotSyncError::HashMismatch { expected: [0u8; 32], actual: [1u8; 32] }),
+        );
+        assert!(error.is_fatal());
+    }
+
+    #[test]
+    fn a_snapshot_sync_chunking_failure_inherits_the_chunk_errors_retryability() {
+        let error = NetworkError::new(
+            5,
+            NetworkErrorKind::SnapshotSync(SnapshotSyncError::Chunking(ChunkReassemblyError::InconsistentTotal {
+                message_id: 1,
+                expected: 4,
+                got: 5,
+            })),
+        );
+        assert!(error.is_retryable());
+    }
+}
@@ -0,0 +1,119 @@
+//! Detects loss of quorum connectivity and switches the node into a safe
+//! mode: stop proposing and widen timeouts until connectivity is restored.
+//! `NetworkFaultDetector` doesn't own the transport; the caller reports
+//! connectivity changes via `on_peer_connected`/`on_peer_disconnected` and
+//! polls `should_enter_safe_mode` / `mode` to react.
+
+use std::collections::HashSet;
+
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkMode {
+    Normal,
+    /// Quorum connectivity was lost: the caller should stop proposing and
+    /// widen its view-change timeout until this reverts to `Normal`.
+    SafeMode,
+}
+
+/// One entry per mode transition, so the caller can emit alerts/metrics
+/// without this crate depending on a specific metrics backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeTransition {
+    pub from: NetworkMode,
+    pub to: NetworkMode,
+    pub connected_count: usize,
+}
+
+pub struct NetworkFaultDetector {
+    quorum_size: usize,
+    connected: HashSet<ValidatorId>,
+    mode: NetworkMode,
+}
+
+impl NetworkFaultDetector {
+    pub fn new(quorum_size: usize) -> Self {
+        Self {
+            quorum_size,
+            connected: HashSet::new(),
+            mode: NetworkMode::Normal,
+        }
+    }
+
+    pub fn mode(&self) -> NetworkMode {
+        self.mode
+    }
+
+    pub fn connected_count(&self) -> usize {
+        self.connected.len()
+    }
+
+    pub fn on_peer_connected(&mut self, peer: ValidatorId) -> Option<ModeTransition> {
+        self.connected.insert(peer);
+        self.recompute()
+    }
+
+    pub fn on_peer_disconnected(&mut self, peer: ValidatorId) -> Option<ModeTransition> {
+        self.connected.remove(&peer);
+        self.recompute()
+    }
+
+    fn recompute(&mut self) -> Option<ModeTransition> {
+        let has_quorum = self.connected.len() >= self.quorum_size;
+        let next = if has_quorum { NetworkMode::Normal } else { NetworkMode::SafeMode };
+        if next == self.mode {
+            return None;
+        }
+        let transition = ModeTransition {
+            from: self.mode,
+            to: next,
+            connected_count: self.connected.len(),
+        };
+        self.mode = next;
+        Some(transition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_in_normal_mode() {
+        let detector = NetworkFaultDetector::new(3);
+        assert_eq!(detector.mode(), NetworkMode::Normal);
+    }
+
+    #[test]
+    fn losing_quorum_connectivity_enters_safe_mode() {
+        let mut detector = NetworkFaultDetector::new(3);
+        detector.on_peer_connected(1);
+        detector.on_peer_connected(2);
+        detector.on_peer_connected(3);
+        assert_eq!(detector.mode(), NetworkMode::Normal);
+
+        let transition = detector.on_peer_disconnected(1).unwrap();
+        assert_eq!(transition.to, NetworkMode::SafeMode);
+        assert_eq!(detector.mode(), NetworkMode::SafeMode);
+    }
+
+    #[test]
+    fn regaining_quorum_connectivity_resumes_normal_mode() {
+        let mut detector = NetworkFaultDetector::new(3);
+        detector.on_peer_connected(1);
+        detector.on_peer_disconnected(1);
+        assert_eq!(detector.mode(), NetworkMode::SafeMode);
+
+        detector.on_peer_connected(1);
+        detector.on_peer_connected(2);
+        detector.on_peer_connected(3);
+        assert_eq!(detector.mode(), NetworkMode::Normal);
+    }
+
+    #[test]
+    fn does_not_report_a_transition_when_mode_is_unchanged() {
+        let mut detector = NetworkFaultDetector::new(3);
+        detector.on_peer_connected(1);
+        assert!(detector.on_peer_connected(1).is_none());
+    }
+}
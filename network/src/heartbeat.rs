@@ -0,0 +1,182 @@
+//! Signed liveness attestations. A bare "I'm alive" heartbeat can't tell a
+//! peer that's alive but stuck several views behind from one that's simply
+//! unreachable, and an unsigned one can be forged by anyone on the wire.
+//! `HeartbeatAttestation` carries the sender's current view, its highest
+//! known QC hash, and a signature over both; `LivenessTracker` combines
+//! attestation recency with the view carried inside to classify each peer,
+//! and `build_network_health_check` surfaces the per-peer lag the way
+//! `NetworkFaultDetector` already surfaces aggregate connectivity.
+//!
+//! Signature verification is behind an `AttestationVerifier` trait rather
+//! than a concrete scheme, the same boundary `crypto::RemoteSigner` draws
+//! for signing — the caller supplies whatever verification the embedding
+//! node's signing backend implies.
+
+use std::collections::HashMap;
+
+use hotstuff2_types::{Hash, ValidatorId};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatAttestation {
+    pub sender: ValidatorId,
+    pub view: u64,
+    pub highest_qc_hash: Hash,
+    pub signature: Vec<u8>,
+}
+
+pub trait AttestationVerifier {
+    fn verify(&self, attestation: &HeartbeatAttestation) -> bool;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidAttestation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerLivenessStatus {
+    /// A recent, verified heartbeat within `lag_threshold` views of local.
+    Live { view: u64 },
+    /// A recent, verified heartbeat, but far enough behind to suspect the
+    /// peer is struggling to keep up rather than merely slow to report.
+    Behind { view: u64, lag: u64 },
+    /// No verified heartbeat within `stale_after_ms`.
+    Unreachable,
+}
+
+/// Tracks the most recent verified heartbeat per peer.
+pub struct LivenessTracker {
+    stale_after_ms: u64,
+    lag_threshold: u64,
+    last_seen: HashMap<ValidatorId, (u64, u64)>, // (view, timestamp_ms)
+}
+
+impl LivenessTracker {
+    pub fn new(stale_after_ms: u64, lag_threshold: u64) -> Self {
+        Self { stale_after_ms, lag_threshold, last_seen: HashMap::new() }
+    }
+
+    /// Verifies `attestation` and, if valid, records it as the peer's most
+    /// recent known state.
+    pub fn on_heartbeat(
+        &mut self,
+        attestation: &HeartbeatAttestation,
+        verifier: &dyn AttestationVerifier,
+        now_ms: u64,
+    ) -> Result<(), InvalidAttestation> {
+        if !verifier.verify(attestation) {
+            return Err(InvalidAttestation);
+        }
+        self.last_seen.insert(attestation.sender, (attestation.view, now_ms));
+        Ok(())
+    }
+
+    pub fn status(&self, peer: ValidatorId, local_view: u64, now_ms: u64) -> PeerLivenessStatus {
+        match self.last_seen.get(&peer) {
+            None => PeerLivenessStatus::Unreachable,
+            Some(&(view, seen_at_ms)) => {
+                if now_ms.saturating_sub(seen_at_ms) > self.stale_after_ms {
+                    return PeerLivenessStatus::Unreachable;
+                }
+                let lag = local_view.saturating_sub(view);
+                if lag > self.lag_threshold {
+                    PeerLivenessStatus::Behind { view, lag }
+                } else {
+                    PeerLivenessStatus::Live { view }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerLagReport {
+    pub peer: ValidatorId,
+    pub status: PeerLivenessStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkHealthCheck {
+    pub peers: Vec<PeerLagReport>,
+}
+
+/// Assembles a health check document across `peers`, mirroring
+/// `node::dashboard::build_dashboard_status`'s plain-function shape rather
+/// than a method on a node-wide type that doesn't exist in this crate.
+pub fn build_network_health_check(
+    tracker: &LivenessTracker,
+    peers: &[ValidatorId],
+    local_view: u64,
+    now_ms: u64,
+) -> NetworkHealthCheck {
+    NetworkHealthCheck {
+        peers: peers
+            .iter()
+            .map(|&peer| PeerLagReport { peer, status: tracker.status(peer, local_view, now_ms) })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysValid;
+    impl AttestationVerifier for AlwaysValid {
+        fn verify(&self, _attestation: &HeartbeatAttestation) -> bool {
+            true
+        }
+    }
+
+    struct AlwaysInvalid;
+    impl AttestationVerifier for AlwaysInvalid {
+        fn verify(&self, _attestation: &HeartbeatAttestation) -> bool {
+            false
+        }
+    }
+
+    fn attestation(sender: ValidatorId, view: u64) -> HeartbeatAttestation {
+        HeartbeatAttestation { sender, view, highest_qc_hash: [0u8; 32], signature: vec![1, 2, 3] }
+    }
+
+    #[test]
+    fn an_unattested_peer_is_unreachable() {
+        let tracker = LivenessTracker::new(1000, 2);
+        assert_eq!(tracker.status(1, 10, 0), PeerLivenessStatus::Unreachable);
+    }
+
+    #[test]
+    fn a_recent_heartbeat_within_the_lag_threshold_is_live() {
+        let mut tracker = LivenessTracker::new(1000, 2);
+        tracker.on_heartbeat(&attestation(1, 9), &AlwaysValid, 0).unwrap();
+        assert_eq!(tracker.status(1, 10, 100), PeerLivenessStatus::Live { view: 9 });
+    }
+
+    #[test]
+    fn a_recent_heartbeat_far_behind_local_view_is_behind() {
+        let mut tracker = LivenessTracker::new(1000, 2);
+        tracker.on_heartbeat(&attestation(1, 3), &AlwaysValid, 0).unwrap();
+        assert_eq!(tracker.status(1, 10, 100), PeerLivenessStatus::Behind { view: 3, lag: 7 });
+    }
+
+    #[test]
+    fn a_stale_heartbeat_is_treated_as_unreachable() {
+        let mut tracker = LivenessTracker::new(1000, 2);
+        tracker.on_heartbeat(&attestation(1, 9), &AlwaysValid, 0).unwrap();
+        assert_eq!(tracker.status(1, 10, 5000), PeerLivenessStatus::Unreachable);
+    }
+
+    #[test]
+    fn an_invalid_signature_is_rejected_and_not_recorded() {
+        let mut tracker = LivenessTracker::new(1000, 2);
+        assert_eq!(tracker.on_heartbeat(&attestation(1, 9), &AlwaysInvalid, 0), Err(InvalidAttestation));
+        assert_eq!(tracker.status(1, 10, 0), PeerLivenessStatus::Unreachable);
+    }
+
+    #[test]
+    fn the_health_check_reports_a_status_per_peer() {
+        let mut tracker = LivenessTracker::new(1000, 2);
+        tracker.on_heartbeat(&attestation(1, 9), &AlwaysValid, 0).unwrap();
+        let check = build_network_health_check(&tracker, &[1, 2], 10, 100);
+        assert_eq!(check.peers[0], PeerLagReport { peer: 1, status: PeerLivenessStatus::Live { view: 9 } });
+        assert_eq!(check.peers[1], PeerLagReport { peer: 2, status: PeerLivenessStatus::Unreachable });
+    }
+}
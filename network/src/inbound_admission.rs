@@ -0,0 +1,200 @@
+//! Inbound handshakes were admitted unconditionally: a flood of connection
+//! attempts (a SYN-flood, or just a misconfigured client retrying in a tight
+//! loop) could hold open unboundedly many in-progress handshakes and exhaust
+//! file descriptors before governance or an operator could react. There is
+//! no `production_tcp` module in this tree — this crate has no socket I/O
+//! dependency at all — so `InboundAdmissionController` only tracks admission
+//! decisions; the caller's listener loop calls `try_admit` before accepting
+//! the socket, `complete` once the handshake finishes (success or failure),
+//! and `expire_stale_handshakes` on its own timer to reclaim slots from
+//! peers that started a handshake and went silent. This lives next to
+//! `connection_supervisor` and `access_control`, the other connection-state
+//! types with no I/O of their own.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+#[derive(Debug, Clone, Copy)]
+pub struct InboundLimits {
+    pub max_concurrent_handshakes: usize,
+    pub max_connections_per_ip: u32,
+    pub handshake_timeout_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionReason {
+    TooManyConcurrentHandshakes,
+    PerIpLimitExceeded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionTicket {
+    id: u64,
+}
+
+struct InFlightHandshake {
+    id: u64,
+    ip: IpAddr,
+    started_at_ms: u64,
+}
+
+pub struct InboundAdmissionController {
+    limits: InboundLimits,
+    per_ip_counts: HashMap<IpAddr, u32>,
+    in_flight: Vec<InFlightHandshake>,
+    next_id: u64,
+    rejected_total: u64,
+    timed_out_total: u64,
+}
+
+impl InboundAdmissionController {
+    pub fn new(limits: InboundLimits) -> Self {
+        Self {
+            limits,
+            per_ip_counts: HashMap::new(),
+            in_flight: Vec::new(),
+            next_id: 0,
+            rejected_total: 0,
+            timed_out_total: 0,
+        }
+    }
+
+    /// Call before accepting the socket. On `Ok`, hold the returned ticket
+    /// until the handshake resolves and pass it to `complete`.
+    pub fn try_admit(&mut self, ip: IpAddr, now_ms: u64) -> Result<ConnectionTicket, RejectionReason> {
+        if self.in_flight.len() >= self.limits.max_concurrent_handshakes {
+            self.rejected_total += 1;
+            return Err(RejectionReason::TooManyConcurrentHandshakes);
+        }
+        let count = self.per_ip_counts.get(&ip).copied().unwrap_or(0);
+        if count >= self.limits.max_connections_per_ip {
+            self.rejected_total += 1;
+            return Err(RejectionReason::PerIpLimitExceeded);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.in_flight.push(InFlightHandshake { id, ip, started_at_ms: now_ms });
+        *self.per_ip_counts.entry(ip).or_insert(0) += 1;
+        Ok(ConnectionTicket { id })
+    }
+
+    /// Frees the slot `ticket` holds. Safe to call at most once per ticket;
+    /// a ticket already completed or expired is silently ignored.
+    pub fn complete(&mut self, ticket: ConnectionTicket) {
+        if let Some(pos) = self.in_flight.iter().position(|h| h.id == ticket.id) {
+            let handshake = self.in_flight.remove(pos);
+            self.release_ip_slot(handshake.ip);
+        }
+    }
+
+    /// Reclaims every in-flight handshake that has run for at least
+    /// `handshake_timeout_ms`, returning how many were reclaimed.
+    pub fn expire_stale_handshakes(&mut self, now_ms: u64) -> usize {
+        let timeout = self.limits.handshake_timeout_ms;
+        let mut expired_ips = Vec::new();
+        self.in_flight.retain(|h| {
+            let stale = now_ms.saturating_sub(h.started_at_ms) >= timeout;
+            if stale {
+                expired_ips.push(h.ip);
+            }
+            !stale
+        });
+        for ip in &expired_ips {
+            self.release_ip_slot(*ip);
+        }
+        self.timed_out_total += expired_ips.len() as u64;
+        expired_ips.len()
+    }
+
+    fn release_ip_slot(&mut self, ip: IpAddr) {
+        if let Some(count) = self.per_ip_counts.get_mut(&ip) {
+            *count -= 1;
+            if *count == 0 {
+                self.per_ip_counts.remove(&ip);
+            }
+        }
+    }
+
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    pub fn rejected_total(&self) -> u64 {
+        self.rejected_total
+    }
+
+    pub fn timed_out_total(&self) -> u64 {
+        self.timed_out_total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn limits() -> InboundLimits {
+        InboundLimits { max_concurrent_handshakes: 3, max_connections_per_ip: 2, handshake_timeout_ms: 5_000 }
+    }
+
+    fn ip(last_octet: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last_octet))
+    }
+
+    #[test]
+    fn admits_up_to_the_concurrent_handshake_limit_then_rejects() {
+        let mut controller = InboundAdmissionController::new(limits());
+        assert!(controller.try_admit(ip(1), 0).is_ok());
+        assert!(controller.try_admit(ip(2), 0).is_ok());
+        assert!(controller.try_admit(ip(3), 0).is_ok());
+        assert_eq!(controller.try_admit(ip(4), 0), Err(RejectionReason::TooManyConcurrentHandshakes));
+        assert_eq!(controller.rejected_total(), 1);
+    }
+
+    #[test]
+    fn a_single_ip_is_capped_independently_of_the_global_limit() {
+        let mut controller = InboundAdmissionController::new(limits());
+        assert!(controller.try_admit(ip(1), 0).is_ok());
+        assert!(controller.try_admit(ip(1), 0).is_ok());
+        assert_eq!(controller.try_admit(ip(1), 0), Err(RejectionReason::PerIpLimitExceeded));
+    }
+
+    #[test]
+    fn completing_a_handshake_frees_both_the_global_and_per_ip_slot() {
+        let mut controller = InboundAdmissionController::new(limits());
+        let a = controller.try_admit(ip(1), 0).unwrap();
+        controller.try_admit(ip(1), 0).unwrap();
+        controller.complete(a);
+        assert_eq!(controller.in_flight_count(), 1);
+        assert!(controller.try_admit(ip(1), 0).is_ok());
+    }
+
+    #[test]
+    fn a_stale_handshake_past_the_timeout_is_expired_and_frees_its_slot() {
+        let mut controller = InboundAdmissionController::new(limits());
+        controller.try_admit(ip(1), 0).unwrap();
+        assert_eq!(controller.expire_stale_handshakes(4_999), 0);
+        assert_eq!(controller.expire_stale_handshakes(5_000), 1);
+        assert_eq!(controller.in_flight_count(), 0);
+        assert_eq!(controller.timed_out_total(), 1);
+        assert!(controller.try_admit(ip(1), 5_000).is_ok());
+    }
+
+    #[test]
+    fn completing_an_already_expired_ticket_is_a_no_op() {
+        let mut controller = InboundAdmissionController::new(limits());
+        let a = controller.try_admit(ip(1), 0).unwrap();
+        controller.expire_stale_handshakes(10_000);
+        controller.complete(a);
+        assert_eq!(controller.in_flight_count(), 0);
+    }
+
+    #[test]
+    fn distinct_ips_do_not_share_a_per_ip_limit() {
+        let mut controller = InboundAdmissionController::new(limits());
+        assert!(controller.try_admit(ip(1), 0).is_ok());
+        assert!(controller.try_admit(ip(1), 0).is_ok());
+        assert!(controller.try_admit(ip(2), 0).is_ok());
+    }
+}
@@ -0,0 +1,51 @@
+pub mod access_control;
+pub mod bandwidth;
+pub mod bounded_queue;
+pub mod catch_up;
+pub mod channel_network;
+pub mod chunking;
+pub mod compression;
+pub mod connection_supervisor;
+pub mod dedup;
+pub mod envelope;
+pub mod fault_classification;
+pub mod fault_detector;
+pub mod handshake;
+pub mod heartbeat;
+pub mod inbound_admission;
+pub mod peer_discovery;
+pub mod reliability;
+pub mod snapshot_sync;
+pub mod testing;
+pub mod transport;
+pub mod zero_copy;
+
+pub use access_control::{AccessDecision, DenyReason, PeerAccessControl};
+pub use bandwidth::{BandwidthQuota, NetworkStats, PeerBandwidthTracker, QuotaExceeded};
+pub use bounded_queue::{BoundedMessageQueue, PushOutcome, QueueStats};
+pub use catch_up::{CatchUpCoordinator, CatchUpRequest};
+pub use channel_network::{ChannelNetwork, ChannelNetworkHub, NetworkInterface, SendError};
+pub use chunking::{split_into_chunks, Chunk, ChunkReassembler, ChunkReassemblyError};
+pub use compression::{Algorithm, Codec, CompressionMetrics, RunLengthCodec, TransportCompressor};
+pub use connection_supervisor::{ConnectionState, PeerConnection, XorShiftJitter};
+pub use dedup::DedupCache;
+pub use envelope::{admit, downgrade_payload, Envelope, EnvelopeError, CURRENT_VERSION, MIN_COMPATIBLE_VERSION};
+pub use fault_classification::{classify_and_record, NetworkError, NetworkErrorKind, Retryability};
+pub use fault_detector::{ModeTransition, NetworkFaultDetector, NetworkMode};
+pub use heartbeat::{
+    build_network_health_check, AttestationVerifier, HeartbeatAttestation, InvalidAttestation, LivenessTracker,
+    NetworkHealthCheck, PeerLagReport, PeerLivenessStatus,
+};
+pub use handshake::{make_claim, verify_handshake, HandshakeClaim, HandshakeError, PeerConfig};
+pub use inbound_admission::{ConnectionTicket, InboundAdmissionController, InboundLimits, RejectionReason};
+pub use peer_discovery::{sign_peer_advertisement, verify_peer_advertisement, DiscoveryError, PeerAdvertisement};
+pub use reliability::{PeerScore, ReputationTracker};
+pub use snapshot_sync::{hash_snapshot, prepare_snapshot, SnapshotManifest, SnapshotReceiver, SnapshotSyncError};
+pub use testing::{
+    three_region_wan, AdversarialNetworkConditions, ByzantinePattern, ClusterBuilder, LatencyTopology, Region,
+    SimulatedCluster, TransportKind,
+};
+pub use transport::{
+    MessageClass, MultiplexedChannelHub, MultiplexedChannelTransport, ResumeToken, SessionResumeCache, Transport,
+};
+pub use zero_copy::{decode_vote_view, encode_vote, DecodeError, SharedBytes, VoteView};
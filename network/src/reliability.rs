@@ -0,0 +1,147 @@
+//! Per-peer reputation tracking. Feeds connection prioritization and
+//! automatic disconnection of low-scoring peers; persistence is left to the
+//! caller (`snapshot`/`restore` round-trip through whatever storage backend
+//! the embedding node uses).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeerScore {
+    pub score: f64,
+}
+
+impl PeerScore {
+    const INITIAL: f64 = 100.0;
+    const INVALID_MESSAGE_PENALTY: f64 = 10.0;
+    const MISSED_RESPONSE_PENALTY: f64 = 2.0;
+    const MIN: f64 = 0.0;
+    const MAX: f64 = 100.0;
+
+    fn new() -> Self {
+        Self { score: Self::INITIAL }
+    }
+
+    fn clamp(&mut self) {
+        self.score = self.score.clamp(Self::MIN, Self::MAX);
+    }
+}
+
+/// Tracks reputation across all known peers and decides who should be
+/// disconnected. `latencies` are recorded but only used for prioritization,
+/// never penalized directly, since a slow but honest peer isn't misbehaving.
+#[derive(Default)]
+pub struct ReputationTracker {
+    scores: HashMap<ValidatorId, PeerScore>,
+    latencies: HashMap<ValidatorId, Duration>,
+    disconnect_threshold: f64,
+}
+
+impl ReputationTracker {
+    pub fn new(disconnect_threshold: f64) -> Self {
+        Self {
+            scores: HashMap::new(),
+            latencies: HashMap::new(),
+            disconnect_threshold,
+        }
+    }
+
+    fn entry(&mut self, peer: ValidatorId) -> &mut PeerScore {
+        self.scores.entry(peer).or_insert_with(PeerScore::new)
+    }
+
+    pub fn record_invalid_message(&mut self, peer: ValidatorId) {
+        self.entry(peer).score -= PeerScore::INVALID_MESSAGE_PENALTY;
+        self.entry(peer).clamp();
+    }
+
+    pub fn record_missed_response(&mut self, peer: ValidatorId) {
+        self.entry(peer).score -= PeerScore::MISSED_RESPONSE_PENALTY;
+        self.entry(peer).clamp();
+    }
+
+    pub fn record_latency(&mut self, peer: ValidatorId, latency: Duration) {
+        self.latencies.insert(peer, latency);
+    }
+
+    pub fn score_of(&self, peer: ValidatorId) -> f64 {
+        self.scores.get(&peer).map(|s| s.score).unwrap_or(PeerScore::INITIAL)
+    }
+
+    pub fn should_disconnect(&self, peer: ValidatorId) -> bool {
+        self.score_of(peer) < self.disconnect_threshold
+    }
+
+    /// Peers ordered by score, highest (most trusted) first, for connection
+    /// prioritization when the transport has a limited number of slots.
+    pub fn prioritized_peers(&self) -> Vec<ValidatorId> {
+        let mut peers: Vec<ValidatorId> = self.scores.keys().copied().collect();
+        peers.sort_by(|a, b| self.score_of(*b).partial_cmp(&self.score_of(*a)).unwrap());
+        peers
+    }
+
+    /// Serializes current scores as `(peer, score)` pairs so the embedding
+    /// node can persist them across restarts.
+    pub fn snapshot(&self) -> Vec<(ValidatorId, f64)> {
+        self.scores.iter().map(|(peer, s)| (*peer, s.score)).collect()
+    }
+
+    pub fn restore(&mut self, snapshot: Vec<(ValidatorId, f64)>) {
+        for (peer, score) in snapshot {
+            let mut s = PeerScore { score };
+            s.clamp();
+            self.scores.insert(peer, s);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_peers_start_at_full_trust() {
+        let tracker = ReputationTracker::new(50.0);
+        assert_eq!(tracker.score_of(1), 100.0);
+        assert!(!tracker.should_disconnect(1));
+    }
+
+    #[test]
+    fn invalid_messages_lower_score_and_can_trigger_disconnect() {
+        let mut tracker = ReputationTracker::new(50.0);
+        for _ in 0..6 {
+            tracker.record_invalid_message(1);
+        }
+        assert!(tracker.should_disconnect(1));
+    }
+
+    #[test]
+    fn score_never_drops_below_zero() {
+        let mut tracker = ReputationTracker::new(0.0);
+        for _ in 0..50 {
+            tracker.record_invalid_message(1);
+        }
+        assert_eq!(tracker.score_of(1), 0.0);
+    }
+
+    #[test]
+    fn snapshot_and_restore_round_trips_scores() {
+        let mut tracker = ReputationTracker::new(50.0);
+        tracker.record_invalid_message(1);
+        let snapshot = tracker.snapshot();
+
+        let mut restored = ReputationTracker::new(50.0);
+        restored.restore(snapshot);
+        assert_eq!(restored.score_of(1), tracker.score_of(1));
+    }
+
+    #[test]
+    fn prioritized_peers_ranks_by_score_descending() {
+        let mut tracker = ReputationTracker::new(0.0);
+        tracker.record_invalid_message(1);
+        let _ = tracker.entry(2); // peer 2 stays at full trust
+        assert_eq!(tracker.prioritized_peers(), vec![2, 1]);
+    }
+}
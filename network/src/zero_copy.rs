@@ -0,0 +1,170 @@
+//! Vote-heavy profiles showed the receive path allocating and copying a
+//! fresh `Vec` per field for every incoming vote, dominating time spent
+//! actually processing the vote. The request asks for `bytes::Bytes` and
+//! `rkyv`; neither is available in this workspace (no external crates), so
+//! this hand-rolls the two properties that actually matter: an `Arc`-backed
+//! `SharedBytes` that slices in O(1) without copying (the same value
+//! `Bytes` provides), and `decode_vote_view`, which borrows its fields
+//! directly out of the wire buffer instead of allocating owned copies (the
+//! same value zero-copy/borrow-based deserialization provides). This
+//! covers the vote hot path the request calls out; it is not a general
+//! replacement for `Envelope<M>`'s owned, allocating decode used elsewhere.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use hotstuff2_types::{Hash, ValidatorId};
+
+/// A reference-counted byte buffer that slices without copying: cloning a
+/// `SharedBytes` or taking a sub-`slice` of one only bumps the `Arc`'s
+/// refcount, unlike `Vec<u8>::clone`, which copies the bytes.
+#[derive(Debug, Clone)]
+pub struct SharedBytes {
+    data: Arc<[u8]>,
+    start: usize,
+    end: usize,
+}
+
+impl SharedBytes {
+    pub fn from_vec(data: Vec<u8>) -> Self {
+        let data: Arc<[u8]> = data.into();
+        let end = data.len();
+        Self { data, start: 0, end }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data[self.start..self.end]
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes a sub-range of this buffer in O(1): no bytes are copied, only
+    /// the `Arc` is cloned and the window narrowed.
+    ///
+    /// # Panics
+    /// If `range` is out of bounds of the current window.
+    pub fn slice(&self, range: Range<usize>) -> SharedBytes {
+        assert!(range.end <= self.len(), "slice out of bounds");
+        SharedBytes { data: Arc::clone(&self.data), start: self.start + range.start, end: self.start + range.end }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    TooShort { expected: usize, got: usize },
+}
+
+const VOTE_RECORD_LEN: usize = 8 + 32 + 8;
+
+/// Borrows its fields directly out of the buffer `decode_vote_view` was
+/// called on; decoding a vote allocates nothing.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VoteView<'a> {
+    pub view: u64,
+    pub block_hash: &'a Hash,
+    pub voter: ValidatorId,
+}
+
+/// Encodes a vote into the fixed layout `decode_vote_view` reads: `view`
+/// (u64 LE), `block_hash` (32 bytes), `voter` (u64 LE).
+pub fn encode_vote(view: u64, block_hash: &Hash, voter: ValidatorId) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(VOTE_RECORD_LEN);
+    buf.extend_from_slice(&view.to_le_bytes());
+    buf.extend_from_slice(block_hash);
+    buf.extend_from_slice(&voter.to_le_bytes());
+    buf
+}
+
+/// Decodes a vote from `data` without allocating: `view` and `voter` are
+/// copied out of the buffer (they're plain `u64`s), but `block_hash`
+/// borrows directly from `data` for the lifetime of the returned
+/// `VoteView`.
+pub fn decode_vote_view(data: &[u8]) -> Result<VoteView<'_>, DecodeError> {
+    if data.len() < VOTE_RECORD_LEN {
+        return Err(DecodeError::TooShort { expected: VOTE_RECORD_LEN, got: data.len() });
+    }
+    let view = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let block_hash: &Hash = data[8..40].try_into().unwrap();
+    let voter = u64::from_le_bytes(data[40..48].try_into().unwrap());
+    Ok(VoteView { view, block_hash, voter })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_sliced_shared_bytes_sees_only_its_own_window() {
+        let shared = SharedBytes::from_vec(vec![1, 2, 3, 4, 5]);
+        let middle = shared.slice(1..4);
+        assert_eq!(middle.as_slice(), &[2, 3, 4]);
+    }
+
+    #[test]
+    fn slicing_out_of_bounds_panics() {
+        let shared = SharedBytes::from_vec(vec![1, 2, 3]);
+        let result = std::panic::catch_unwind(|| shared.slice(0..10));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn a_vote_round_trips_through_encode_and_decode() {
+        let hash = [9u8; 32];
+        let encoded = encode_vote(42, &hash, 7);
+        let view = decode_vote_view(&encoded).unwrap();
+        assert_eq!(view.view, 42);
+        assert_eq!(view.block_hash, &hash);
+        assert_eq!(view.voter, 7);
+    }
+
+    #[test]
+    fn a_truncated_buffer_is_rejected_instead_of_reading_out_of_bounds() {
+        let encoded = encode_vote(1, &[0u8; 32], 1);
+        let truncated = &encoded[..encoded.len() - 1];
+        assert_eq!(
+            decode_vote_view(truncated),
+            Err(DecodeError::TooShort { expected: VOTE_RECORD_LEN, got: truncated.len() })
+        );
+    }
+
+    /// No `bincode`/`serde`/protobuf crate is available in this workspace,
+    /// so `encode_vote`/`decode_vote_view` are this format's hand-rolled
+    /// equivalent; this is that format's round-trip/adversarial-input
+    /// property test: every truncation length either round-trips
+    /// correctly (lengths at or beyond the fixed record size, since
+    /// trailing bytes are legitimately ignored for batched messages) or is
+    /// rejected outright, and never panics either way.
+    #[test]
+    fn every_truncation_length_round_trips_or_is_rejected_without_panicking() {
+        let hash = [7u8; 32];
+        let encoded = encode_vote(11, &hash, 22);
+        for len in 0..VOTE_RECORD_LEN {
+            assert_eq!(
+                decode_vote_view(&encoded[..len]),
+                Err(DecodeError::TooShort { expected: VOTE_RECORD_LEN, got: len }),
+                "truncation to {len} bytes should be rejected"
+            );
+        }
+        let view = decode_vote_view(&encoded).unwrap();
+        assert_eq!(view.view, 11);
+        assert_eq!(view.block_hash, &hash);
+        assert_eq!(view.voter, 22);
+    }
+
+    #[test]
+    fn decoding_from_a_shared_bytes_slice_still_borrows_zero_copy() {
+        let hash = [3u8; 32];
+        let mut encoded = encode_vote(1, &hash, 5);
+        encoded.extend_from_slice(&[0xAA; 10]); // trailing bytes from a batched message
+        let shared = SharedBytes::from_vec(encoded);
+        let view_bytes = shared.slice(0..VOTE_RECORD_LEN);
+        let view = decode_vote_view(view_bytes.as_slice()).unwrap();
+        assert_eq!(view.block_hash, &hash);
+    }
+}
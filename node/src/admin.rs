@@ -0,0 +1,234 @@
+//! Admin interface to flip optimistic-mode settings at runtime, so operators
+//! can disable the fast path during an incident without restarting the node.
+//! Changes are reflected immediately in `PerformanceStatistics`.
+//!
+//! `fast_commit_threshold` used to be an arbitrary positive count an operator
+//! could set below the provable safety bound, silently weakening the fast
+//! path to something a Byzantine minority could exploit. `check_fast_commit_threshold`
+//! enforces the real bound in code — `ValidatorSet::fast_commit_quorum`
+//! (all-but-`f`) — and only treats the configured value as advisory above
+//! that floor, rejecting anything lower with `AdminError::BelowSafeFastCommitQuorum`.
+
+use hotstuff2_core::{PipelineDepthTuner, PipelineObservation};
+use hotstuff2_types::ValidatorSet;
+
+use crate::latency_breakdown::{LatencyBreakdown, LatencyStage, LatencyTracker};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponsivenessMode {
+    Synchronous,
+    Asynchronous,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OptimisticModeConfig {
+    pub fast_path_enabled: bool,
+    pub fast_commit_threshold: u32,
+    pub forced_mode: Option<ResponsivenessMode>,
+}
+
+impl Default for OptimisticModeConfig {
+    fn default() -> Self {
+        Self {
+            fast_path_enabled: true,
+            fast_commit_threshold: 1,
+            forced_mode: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PerformanceStatistics {
+    pub fast_path_enabled: bool,
+    pub fast_commit_threshold: u32,
+    pub effective_pipeline_depth: u32,
+    pub latency_breakdown: LatencyBreakdown,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AdminError {
+    ThresholdMustBePositive,
+    /// The requested threshold is below the provable all-but-`f` fast-commit
+    /// quorum for the current validator set; `required` is the minimum safe
+    /// value.
+    BelowSafeFastCommitQuorum { required: u32 },
+}
+
+/// Enforces the real fast-commit safety bound instead of trusting a
+/// hand-configured value: `threshold` is only safe if it meets or exceeds
+/// `validators.fast_commit_quorum()` (all-but-`f`). A `threshold` above that
+/// bound is accepted as-is — the config value is advisory only once it
+/// clears the floor.
+pub fn check_fast_commit_threshold(threshold: u32, validators: &ValidatorSet) -> Result<u32, AdminError> {
+    if threshold == 0 {
+        return Err(AdminError::ThresholdMustBePositive);
+    }
+    let required = validators.fast_commit_quorum() as u32;
+    if threshold < required {
+        return Err(AdminError::BelowSafeFastCommitQuorum { required });
+    }
+    Ok(threshold)
+}
+
+/// Default pipeline depth bounds and latency budget for nodes that don't
+/// override them; matches the depths exercised in `benches/commit_latency`.
+const DEFAULT_MIN_PIPELINE_DEPTH: u32 = 1;
+const DEFAULT_MAX_PIPELINE_DEPTH: u32 = 8;
+const DEFAULT_LATENCY_BUDGET_MS: u64 = 200;
+
+/// Live-updatable admin surface over `OptimisticModeConfig`. Every setter
+/// takes effect immediately and is visible in the next `stats()` call.
+pub struct AdminApi {
+    config: OptimisticModeConfig,
+    pipeline_tuner: PipelineDepthTuner,
+    validators: ValidatorSet,
+    latency: LatencyTracker,
+}
+
+impl Default for AdminApi {
+    fn default() -> Self {
+        Self::new(OptimisticModeConfig::default())
+    }
+}
+
+impl AdminApi {
+    pub fn new(config: OptimisticModeConfig) -> Self {
+        Self {
+            config,
+            pipeline_tuner: PipelineDepthTuner::new(
+                DEFAULT_MIN_PIPELINE_DEPTH,
+                DEFAULT_MAX_PIPELINE_DEPTH,
+                DEFAULT_LATENCY_BUDGET_MS,
+            ),
+            validators: ValidatorSet { validators: vec![1, 2, 3, 4] },
+            latency: LatencyTracker::new(),
+        }
+    }
+
+    /// Overrides the validator set `check_fast_commit_threshold` validates
+    /// against; defaults to a 4-validator set (tolerating `f = 1`) so a node
+    /// wired up without an explicit set still gets a real safety bound.
+    pub fn set_validators(&mut self, validators: ValidatorSet) {
+        self.validators = validators;
+    }
+
+    /// Feeds one round's commit latency and view-change outcome into the
+    /// pipeline depth auto-tuner.
+    pub fn record_pipeline_observation(&mut self, observation: PipelineObservation) {
+        self.pipeline_tuner.observe(observation);
+    }
+
+    /// Pins the pipeline depth regardless of observations; `None` returns
+    /// control to the auto-tuner.
+    pub fn set_pipeline_depth_override(&mut self, depth: Option<u32>) {
+        self.pipeline_tuner.set_override(depth);
+    }
+
+    pub fn set_fast_path_enabled(&mut self, enabled: bool) {
+        self.config.fast_path_enabled = enabled;
+    }
+
+    pub fn set_fast_commit_threshold(&mut self, threshold: u32) -> Result<(), AdminError> {
+        self.config.fast_commit_threshold = check_fast_commit_threshold(threshold, &self.validators)?;
+        Ok(())
+    }
+
+    pub fn force_mode(&mut self, mode: Option<ResponsivenessMode>) {
+        self.config.forced_mode = mode;
+    }
+
+    /// Records one stage's elapsed time for this write, folded into that
+    /// stage's running average in the next `stats()` call.
+    pub fn record_stage_latency(&mut self, stage: LatencyStage, elapsed_ms: u64) {
+        self.latency.record(stage, elapsed_ms);
+    }
+
+    pub fn config(&self) -> OptimisticModeConfig {
+        self.config
+    }
+
+    pub fn stats(&self) -> PerformanceStatistics {
+        PerformanceStatistics {
+            fast_path_enabled: self.config.fast_path_enabled,
+            fast_commit_threshold: self.config.fast_commit_threshold,
+            effective_pipeline_depth: self.pipeline_tuner.effective_depth(),
+            latency_breakdown: self.latency.breakdown(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabling_fast_path_is_reflected_in_stats() {
+        let mut admin = AdminApi::default();
+        admin.set_fast_path_enabled(false);
+        assert!(!admin.stats().fast_path_enabled);
+    }
+
+    #[test]
+    fn rejects_a_zero_fast_commit_threshold() {
+        let mut admin = AdminApi::default();
+        assert_eq!(admin.set_fast_commit_threshold(0), Err(AdminError::ThresholdMustBePositive));
+    }
+
+    #[test]
+    fn rejects_a_threshold_below_the_all_but_f_fast_commit_quorum() {
+        // 4 validators tolerate f = 1, so the fast-commit quorum is 3.
+        let mut admin = AdminApi::default();
+        assert_eq!(admin.set_fast_commit_threshold(2), Err(AdminError::BelowSafeFastCommitQuorum { required: 3 }));
+    }
+
+    #[test]
+    fn accepts_a_threshold_at_or_above_the_fast_commit_quorum_as_advisory() {
+        let mut admin = AdminApi::default();
+        admin.set_fast_commit_threshold(3).unwrap();
+        assert_eq!(admin.config().fast_commit_threshold, 3);
+        admin.set_fast_commit_threshold(4).unwrap();
+        assert_eq!(admin.config().fast_commit_threshold, 4);
+    }
+
+    #[test]
+    fn a_smaller_validator_set_lowers_the_required_fast_commit_quorum() {
+        let mut admin = AdminApi::default();
+        admin.set_validators(ValidatorSet { validators: vec![1] });
+        admin.set_fast_commit_threshold(1).unwrap();
+        assert_eq!(admin.config().fast_commit_threshold, 1);
+    }
+
+    #[test]
+    fn force_mode_overrides_the_detected_mode() {
+        let mut admin = AdminApi::default();
+        admin.force_mode(Some(ResponsivenessMode::Asynchronous));
+        assert_eq!(admin.config().forced_mode, Some(ResponsivenessMode::Asynchronous));
+    }
+
+    #[test]
+    fn a_view_change_shrinks_the_effective_pipeline_depth() {
+        let mut admin = AdminApi::default();
+        let before = admin.stats().effective_pipeline_depth;
+        admin.record_pipeline_observation(PipelineObservation { commit_latency_ms: 50, view_changed: true });
+        assert!(admin.stats().effective_pipeline_depth < before);
+    }
+
+    #[test]
+    fn an_admin_override_pins_the_pipeline_depth() {
+        let mut admin = AdminApi::default();
+        admin.set_pipeline_depth_override(Some(3));
+        admin.record_pipeline_observation(PipelineObservation { commit_latency_ms: 50, view_changed: true });
+        assert_eq!(admin.stats().effective_pipeline_depth, 3);
+    }
+
+    #[test]
+    fn stats_reports_the_recorded_per_stage_latency_breakdown() {
+        let mut admin = AdminApi::default();
+        admin.record_stage_latency(LatencyStage::MempoolWait, 12);
+        admin.record_stage_latency(LatencyStage::Execution, 30);
+        let breakdown = admin.stats().latency_breakdown;
+        assert_eq!(breakdown.mempool_wait_ms, 12);
+        assert_eq!(breakdown.execution_ms, 30);
+        assert_eq!(breakdown.storage_flush_ms, 0);
+    }
+}
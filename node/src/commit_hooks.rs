@@ -0,0 +1,150 @@
+//! There is no `HotStuff2` protocol-driver struct in this workspace (the
+//! commit path lives across `consensus::execution`/`consensus::state` and
+//! whatever glues them together in a deployment), so the registration API
+//! the request describes — `HotStuff2::on_commit(Box<dyn CommitHook>)` —
+//! is implemented here as a standalone `CommitHookRegistry` that the commit
+//! path calls into at the point a block finalizes. Hooks run sequentially
+//! in commit order (never concurrently, never reordered) so an embedder
+//! relying on "hook sees height N before height N+1" can depend on that. A
+//! panicking hook is caught and reported instead of taking the node down or
+//! skipping the remaining hooks for that commit.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use hotstuff2_types::Hash;
+
+/// What a hook is told about a just-committed block. Kept to primitives
+/// (no `hotstuff2-consensus` types such as receipts); an embedder that
+/// needs full receipts can look them up from `ReceiptStore` by
+/// `block_hash` itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitInfo {
+    pub height: u64,
+    pub block_hash: Hash,
+    pub state_root: Hash,
+}
+
+pub trait CommitHook {
+    fn on_commit(&mut self, info: CommitInfo);
+}
+
+/// Records which registered hook (by registration index) panicked and what
+/// its panic payload stringified to, so the caller can surface it (log,
+/// metric, alert) without the panic itself propagating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookPanic {
+    pub hook_index: usize,
+    pub message: String,
+}
+
+/// Runs registered `CommitHook`s sequentially, in registration order, once
+/// per commit.
+#[derive(Default)]
+pub struct CommitHookRegistry {
+    hooks: Vec<Box<dyn CommitHook>>,
+}
+
+impl CommitHookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, hook: Box<dyn CommitHook>) {
+        self.hooks.push(hook);
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Calls every hook's `on_commit` in registration order, catching a
+    /// panic from any single hook so it can't stop the rest from running
+    /// (or, worse, take the commit path down). Returns one `HookPanic` per
+    /// hook that panicked, in the order they occurred.
+    pub fn dispatch(&mut self, info: CommitInfo) -> Vec<HookPanic> {
+        let mut panics = Vec::new();
+        for (index, hook) in self.hooks.iter_mut().enumerate() {
+            let result = catch_unwind(AssertUnwindSafe(|| hook.on_commit(info)));
+            if let Err(payload) = result {
+                panics.push(HookPanic { hook_index: index, message: panic_message(&*payload) });
+            }
+        }
+        panics
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(height: u64) -> CommitInfo {
+        CommitInfo { height, block_hash: [height as u8; 32], state_root: [0u8; 32] }
+    }
+
+    struct RecordingHook {
+        seen: std::sync::Arc<std::sync::Mutex<Vec<u64>>>,
+    }
+
+    impl CommitHook for RecordingHook {
+        fn on_commit(&mut self, info: CommitInfo) {
+            self.seen.lock().unwrap().push(info.height);
+        }
+    }
+
+    struct PanickingHook;
+
+    impl CommitHook for PanickingHook {
+        fn on_commit(&mut self, _info: CommitInfo) {
+            panic!("boom");
+        }
+    }
+
+    #[test]
+    fn hooks_run_in_registration_order_for_each_commit() {
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut registry = CommitHookRegistry::new();
+        registry.register(Box::new(RecordingHook { seen: seen.clone() }));
+        registry.register(Box::new(RecordingHook { seen: seen.clone() }));
+
+        registry.dispatch(info(1));
+        registry.dispatch(info(2));
+
+        assert_eq!(*seen.lock().unwrap(), vec![1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn a_panicking_hook_is_isolated_and_reported() {
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut registry = CommitHookRegistry::new();
+        registry.register(Box::new(PanickingHook));
+        registry.register(Box::new(RecordingHook { seen: seen.clone() }));
+
+        let panics = registry.dispatch(info(1));
+
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].hook_index, 0);
+        assert_eq!(panics[0].message, "boom");
+        // The second hook still ran despite the first panicking.
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn dispatch_with_no_hooks_reports_no_panics() {
+        let mut registry = CommitHookRegistry::new();
+        assert!(registry.dispatch(info(1)).is_empty());
+    }
+}
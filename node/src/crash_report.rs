@@ -0,0 +1,163 @@
+//! A validator that panics used to just die with a backtrace on stderr:
+//! whatever `MetricSnapshot`, `ChainState`, and pipeline depths it held in
+//! memory at that instant were lost, leaving an operator nothing to go on
+//! besides "it restarted". `CrashReporter::install` installs a panic hook
+//! that writes the most recently published `CrashContext` to a report file
+//! before falling through to whatever hook was previously installed (so the
+//! default backtrace-to-stderr behavior is preserved, not replaced).
+//!
+//! There's no single `Node::start` in this workspace to hook this into
+//! directly — the embedder's run loop is expected to call
+//! `CrashReporter::publish` once per view (or on whatever cadence it
+//! already gathers `MetricSnapshot`/`ChainState`/pipeline sizes) and to
+//! call `install` once at startup, the same caller-supplies-the-facts
+//! boundary `watchdog` and `health` already draw.
+
+use std::fs::File;
+use std::io::Write;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use hotstuff2_core::ChainState;
+use hotstuff2_metrics::MetricSnapshot;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineSizes {
+    pub mempool_pending: u64,
+    pub execution_queue_depth: u64,
+    pub vote_pipeline_depth: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrashContext {
+    pub metrics: MetricSnapshot,
+    pub chain_state: ChainState,
+    pub pipeline_sizes: PipelineSizes,
+}
+
+#[derive(Clone, Default)]
+pub struct CrashReporter {
+    latest: Arc<Mutex<Option<CrashContext>>>,
+}
+
+impl CrashReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publishes the latest known state, overwriting whatever was published
+    /// before. Cheap enough to call once per view.
+    pub fn publish(&self, context: CrashContext) {
+        *self.latest.lock().unwrap() = Some(context);
+    }
+
+    pub fn latest(&self) -> Option<CrashContext> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    /// Installs a panic hook that dumps `self.latest()` (if anything has
+    /// been published yet) plus the panic message to `report_path`, then
+    /// calls the hook that was previously installed. Returns a guard that
+    /// restores the previous hook when dropped.
+    pub fn install(&self, report_path: PathBuf) -> CrashReporterGuard {
+        let reporter = self.clone();
+        let previous = Arc::new(panic::take_hook());
+        let chained = Arc::clone(&previous);
+        panic::set_hook(Box::new(move |info| {
+            if let Some(context) = reporter.latest() {
+                if let Ok(mut file) = File::create(&report_path) {
+                    let _ = writeln!(file, "panic: {info}");
+                    let _ = writeln!(file, "{context:#?}");
+                }
+            }
+            (*chained)(info);
+        }));
+        CrashReporterGuard { restore: Some(Box::new(move || panic::set_hook(Box::new(move |info| (*previous)(info))))) }
+    }
+}
+
+/// Restores the previously installed panic hook on drop.
+pub struct CrashReporterGuard {
+    restore: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl Drop for CrashReporterGuard {
+    fn drop(&mut self) {
+        if let Some(restore) = self.restore.take() {
+            restore();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex as StdMutex;
+
+    // Panic hooks are process-global; serialize the tests that install one
+    // so they don't race with each other's assertions.
+    static HOOK_LOCK: StdMutex<()> = StdMutex::new(());
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_path(name: &str) -> PathBuf {
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("hotstuff2_crash_report_{name}_{unique}.txt"))
+    }
+
+    fn context() -> CrashContext {
+        CrashContext {
+            metrics: MetricSnapshot {
+                timestamp_ms: 1,
+                current_view: 7,
+                committed_height: 6,
+                mempool_depth: 3,
+                commit_latency_ms: 42,
+            },
+            chain_state: ChainState { current_view: 7, committed_height: 6, locked_view: 6 },
+            pipeline_sizes: PipelineSizes { mempool_pending: 3, execution_queue_depth: 1, vote_pipeline_depth: 0 },
+        }
+    }
+
+    #[test]
+    fn a_panic_after_publishing_writes_the_context_to_the_report_file() {
+        let _guard = HOOK_LOCK.lock().unwrap();
+        let path = temp_path("dumps");
+        let reporter = CrashReporter::new();
+        reporter.publish(context());
+        let install_guard = reporter.install(path.clone());
+
+        let result = panic::catch_unwind(|| panic!("synthetic crash for the test"));
+        assert!(result.is_err());
+        drop(install_guard);
+
+        let report = std::fs::read_to_string(&path).unwrap();
+        assert!(report.contains("synthetic crash for the test"));
+        assert!(report.contains("current_view: 7"));
+        assert!(report.contains("mempool_pending: 3"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn no_report_is_written_if_nothing_was_ever_published() {
+        let _guard = HOOK_LOCK.lock().unwrap();
+        let path = temp_path("no_publish");
+        let reporter = CrashReporter::new();
+        let install_guard = reporter.install(path.clone());
+
+        let result = panic::catch_unwind(|| panic!("synthetic crash with no published context"));
+        assert!(result.is_err());
+        drop(install_guard);
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn latest_reflects_the_most_recently_published_context() {
+        let reporter = CrashReporter::new();
+        assert!(reporter.latest().is_none());
+        reporter.publish(context());
+        assert_eq!(reporter.latest().unwrap().pipeline_sizes.mempool_pending, 3);
+    }
+}
@@ -0,0 +1,118 @@
+//! Aggregated operator dashboard status document: a single struct pulling
+//! together the handful of things an operator dashboard (Grafana JSON
+//! datasource, or a small web UI) needs on one call, instead of hitting
+//! several separate endpoints and reassembling them client-side.
+
+use hotstuff2_types::ValidatorId;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerHealthSummary {
+    pub peer: ValidatorId,
+    pub connected: bool,
+    pub score: f64,
+}
+
+/// Bounded ring of the most recent commit latencies, in milliseconds, so the
+/// dashboard document doesn't grow with node uptime.
+pub struct CommitLatencyHistory {
+    capacity: usize,
+    samples: std::collections::VecDeque<u64>,
+}
+
+impl CommitLatencyHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, samples: std::collections::VecDeque::new() }
+    }
+
+    pub fn record(&mut self, latency_ms: u64) {
+        if self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ms);
+    }
+
+    pub fn recent(&self) -> Vec<u64> {
+        self.samples.iter().copied().collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashboardStatus {
+    pub current_height: u64,
+    pub current_view: u64,
+    pub leader: ValidatorId,
+    pub peers: Vec<PeerHealthSummary>,
+    pub mempool_depth: usize,
+    pub recent_commit_latencies_ms: Vec<u64>,
+    pub storage_bytes_used: u64,
+    /// Total transactions committed since genesis, used by `status_cli` to
+    /// derive a TPS figure across two polls of this document.
+    pub committed_tx_count: u64,
+}
+
+/// Assembles the dashboard document from the pieces each subsystem already
+/// tracks. Kept as a plain function (rather than a method on some `Node`
+/// god-object that doesn't exist in this workspace) so it composes with
+/// whatever data the embedding node has on hand.
+#[allow(clippy::too_many_arguments)]
+pub fn build_dashboard_status(
+    current_height: u64,
+    current_view: u64,
+    leader: ValidatorId,
+    peers: Vec<PeerHealthSummary>,
+    mempool_depth: usize,
+    commit_latencies: &CommitLatencyHistory,
+    storage_bytes_used: u64,
+    committed_tx_count: u64,
+) -> DashboardStatus {
+    DashboardStatus {
+        current_height,
+        current_view,
+        leader,
+        peers,
+        mempool_depth,
+        recent_commit_latencies_ms: commit_latencies.recent(),
+        storage_bytes_used,
+        committed_tx_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_history_keeps_only_the_most_recent_samples() {
+        let mut history = CommitLatencyHistory::new(2);
+        history.record(10);
+        history.record(20);
+        history.record(30);
+        assert_eq!(history.recent(), vec![20, 30]);
+    }
+
+    #[test]
+    fn dashboard_status_aggregates_every_subsystem() {
+        let mut history = CommitLatencyHistory::new(100);
+        history.record(15);
+
+        let status = build_dashboard_status(
+            100,
+            42,
+            7,
+            vec![PeerHealthSummary { peer: 1, connected: true, score: 95.0 }],
+            3,
+            &history,
+            1024,
+            500,
+        );
+
+        assert_eq!(status.current_height, 100);
+        assert_eq!(status.current_view, 42);
+        assert_eq!(status.leader, 7);
+        assert_eq!(status.peers.len(), 1);
+        assert_eq!(status.mempool_depth, 3);
+        assert_eq!(status.recent_commit_latencies_ms, vec![15]);
+        assert_eq!(status.storage_bytes_used, 1024);
+        assert_eq!(status.committed_tx_count, 500);
+    }
+}
@@ -0,0 +1,114 @@
+//! Trying out application logic against the real state machine and receipt
+//! path meant standing up a `ValidatorSet` of at least four validators, a
+//! network stack, and driving three-chain commit — none of which an
+//! application developer iterating on their own state machine cares about.
+//! `SingleNodeDevRunner` collapses that to the degenerate but still-real
+//! case: a `ValidatorSet` of one, whose `quorum_size()` is already `1`
+//! (`(2*1)/3 + 1 == 1`, no special-casing needed), so the node's own single
+//! vote forms a QC immediately and every submitted block commits in the
+//! same call with no view-change machinery and no `hotstuff2-network`
+//! dependency at all.
+
+use hotstuff2_consensus::{execute_and_record, KVStateMachine, ReceiptStore};
+use hotstuff2_types::{Block, Hash, QuorumCertificate, Transaction, ValidatorId, ValidatorSet};
+
+const DEV_VALIDATOR: ValidatorId = 1;
+
+/// Runs a single-validator chain entirely in-process: `submit` executes and
+/// commits a block of transactions immediately, with no network round trip.
+pub struct SingleNodeDevRunner {
+    machine: KVStateMachine,
+    receipts: ReceiptStore,
+    validator_set: ValidatorSet,
+    height: u64,
+    parent_hash: Hash,
+}
+
+impl Default for SingleNodeDevRunner {
+    fn default() -> Self {
+        Self {
+            machine: KVStateMachine::new(),
+            receipts: ReceiptStore::new(),
+            validator_set: ValidatorSet { validators: vec![DEV_VALIDATOR] },
+            height: 0,
+            parent_hash: [0u8; 32],
+        }
+    }
+}
+
+impl SingleNodeDevRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    pub fn state_hash(&self) -> Hash {
+        self.machine.state_hash()
+    }
+
+    /// Builds a block over `transactions` at the next height, self-votes it
+    /// (the only validator in `validator_set`), and executes it against the
+    /// state machine — all synchronously, since a one-validator quorum needs
+    /// no other votes to certify.
+    pub fn submit(&mut self, transactions: Vec<Transaction>) -> QuorumCertificate {
+        self.height += 1;
+        let block = Block { parent_hash: self.parent_hash, height: self.height, view: self.height, transactions };
+        let block_hash = block.hash();
+        let qc = QuorumCertificate { block_hash, view: block.view, signers: vec![DEV_VALIDATOR] };
+        assert!(self.validator_set.verify_threshold(&qc), "a single-validator set must always reach its own quorum");
+        execute_and_record(&mut self.machine, &mut self.receipts, &block);
+        self.parent_hash = block_hash;
+        qc
+    }
+
+    pub fn receipt_for(&self, tx_id: Hash) -> Option<&hotstuff2_consensus::TxReceipt> {
+        self.receipts.receipt_for(tx_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(id: u8) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction { id: hash, payload: vec![id], weight: 1, valid_until: None }
+    }
+
+    #[test]
+    fn a_fresh_runner_starts_at_height_zero() {
+        let runner = SingleNodeDevRunner::new();
+        assert_eq!(runner.height(), 0);
+    }
+
+    #[test]
+    fn submitting_a_block_commits_immediately_and_advances_height() {
+        let mut runner = SingleNodeDevRunner::new();
+        let qc = runner.submit(vec![tx(1)]);
+        assert_eq!(runner.height(), 1);
+        assert_eq!(qc.signers, vec![DEV_VALIDATOR]);
+        assert!(runner.receipt_for(tx(1).id).is_some());
+    }
+
+    #[test]
+    fn successive_submissions_chain_off_the_prior_block() {
+        let mut runner = SingleNodeDevRunner::new();
+        runner.submit(vec![tx(1)]);
+        runner.submit(vec![tx(2)]);
+        assert_eq!(runner.height(), 2);
+        assert!(runner.receipt_for(tx(1).id).is_some());
+        assert!(runner.receipt_for(tx(2).id).is_some());
+    }
+
+    #[test]
+    fn the_state_hash_changes_as_transactions_are_committed() {
+        let mut runner = SingleNodeDevRunner::new();
+        let before = runner.state_hash();
+        runner.submit(vec![tx(1)]);
+        assert_ne!(before, runner.state_hash());
+    }
+}
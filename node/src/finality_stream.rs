@@ -0,0 +1,106 @@
+//! Downstream services (indexers, explorers, settlement bridges) want to
+//! consume finalized transactions as they land instead of polling
+//! `AdminApi::stats()` for height changes. The request asks for
+//! `Node::finalized_transactions() -> impl Stream<Item = FinalizedTx>`
+//! backed by `tokio-stream`, but this workspace has no async runtime
+//! dependency (see the same disclaimer in `mempool::admission` and
+//! `storage::write_behind`). `FinalizedTxStream` is the synchronous,
+//! bounded-queue analogue: the consensus layer `push`es as blocks commit,
+//! a consumer pulls via the standard `Iterator` trait, and `push` reports
+//! backpressure instead of an async task suspending on a full channel.
+//! Swapping this for a real `impl Stream` is a mechanical wrapper once
+//! `tokio`/`tokio-stream` are available as dependencies.
+
+use std::collections::VecDeque;
+
+use hotstuff2_types::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinalizedTx {
+    pub height: u64,
+    pub tx_id: Hash,
+    /// Identifies who submitted the transaction, so subscribers can filter
+    /// by sender (see `result_subscription`) without decoding the payload.
+    pub sender: Hash,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StreamFull;
+
+/// Bounded queue of finalized transactions awaiting consumption. Bounded so
+/// a slow or absent consumer can't let this grow without limit for the
+/// lifetime of a long-running node.
+pub struct FinalizedTxStream {
+    queue: VecDeque<FinalizedTx>,
+    capacity: usize,
+}
+
+impl FinalizedTxStream {
+    pub fn new(capacity: usize) -> Self {
+        Self { queue: VecDeque::new(), capacity }
+    }
+
+    /// Called by the finality-notification path as each block commits.
+    /// Rejects with `StreamFull` once the consumer has fallen `capacity`
+    /// transactions behind, giving the caller a backpressure signal instead
+    /// of growing unbounded.
+    pub fn push(&mut self, tx: FinalizedTx) -> Result<(), StreamFull> {
+        if self.queue.len() >= self.capacity {
+            return Err(StreamFull);
+        }
+        self.queue.push_back(tx);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+impl Iterator for FinalizedTxStream {
+    type Item = FinalizedTx;
+
+    fn next(&mut self) -> Option<FinalizedTx> {
+        self.queue.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(height: u64, id: u8) -> FinalizedTx {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        FinalizedTx { height, tx_id: hash, sender: [0u8; 32] }
+    }
+
+    #[test]
+    fn consuming_returns_transactions_in_push_order() {
+        let mut stream = FinalizedTxStream::new(4);
+        stream.push(tx(1, 1)).unwrap();
+        stream.push(tx(2, 2)).unwrap();
+        assert_eq!(stream.next(), Some(tx(1, 1)));
+        assert_eq!(stream.next(), Some(tx(2, 2)));
+        assert_eq!(stream.next(), None);
+    }
+
+    #[test]
+    fn pushing_past_capacity_reports_backpressure() {
+        let mut stream = FinalizedTxStream::new(1);
+        stream.push(tx(1, 1)).unwrap();
+        assert_eq!(stream.push(tx(2, 2)), Err(StreamFull));
+    }
+
+    #[test]
+    fn draining_the_queue_makes_room_for_more_pushes() {
+        let mut stream = FinalizedTxStream::new(1);
+        stream.push(tx(1, 1)).unwrap();
+        assert_eq!(stream.next(), Some(tx(1, 1)));
+        assert!(stream.push(tx(2, 2)).is_ok());
+    }
+}
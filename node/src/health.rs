@@ -0,0 +1,109 @@
+//! `/healthz` and `/readyz` probe logic, framework-agnostic: this module
+//! computes the status and body; wiring it to an HTTP server is left to the
+//! embedder since this workspace has no HTTP dependency.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeStatus {
+    Ok,
+    Unhealthy,
+}
+
+impl ProbeStatus {
+    pub fn http_status_code(&self) -> u16 {
+        match self {
+            ProbeStatus::Ok => 200,
+            ProbeStatus::Unhealthy => 503,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NodeVitals {
+    pub last_committed_height: u64,
+    pub previous_committed_height: u64,
+    pub connected_peers: usize,
+    pub min_peers_for_ready: usize,
+    pub storage_healthy: bool,
+    pub view_changes_in_last_window: u32,
+    pub view_change_storm_threshold: u32,
+}
+
+/// `/healthz`: is the process alive and not wedged? Height must have moved
+/// since the last check and storage must be reachable; peer count doesn't
+/// gate liveness (a partitioned node is still alive).
+pub fn healthz(vitals: &NodeVitals) -> ProbeStatus {
+    if !vitals.storage_healthy {
+        return ProbeStatus::Unhealthy;
+    }
+    if vitals.previous_committed_height > 0 && vitals.last_committed_height <= vitals.previous_committed_height {
+        return ProbeStatus::Unhealthy;
+    }
+    ProbeStatus::Ok
+}
+
+/// `/readyz`: is the node ready to serve traffic? Stricter than `/healthz`:
+/// also requires enough peers and no ongoing view-change storm.
+pub fn readyz(vitals: &NodeVitals) -> ProbeStatus {
+    if healthz(vitals) == ProbeStatus::Unhealthy {
+        return ProbeStatus::Unhealthy;
+    }
+    if vitals.connected_peers < vitals.min_peers_for_ready {
+        return ProbeStatus::Unhealthy;
+    }
+    if vitals.view_changes_in_last_window >= vitals.view_change_storm_threshold {
+        return ProbeStatus::Unhealthy;
+    }
+    ProbeStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy_vitals() -> NodeVitals {
+        NodeVitals {
+            last_committed_height: 10,
+            previous_committed_height: 5,
+            connected_peers: 3,
+            min_peers_for_ready: 2,
+            storage_healthy: true,
+            view_changes_in_last_window: 0,
+            view_change_storm_threshold: 5,
+        }
+    }
+
+    #[test]
+    fn healthy_node_passes_both_probes() {
+        let vitals = healthy_vitals();
+        assert_eq!(healthz(&vitals), ProbeStatus::Ok);
+        assert_eq!(readyz(&vitals), ProbeStatus::Ok);
+    }
+
+    #[test]
+    fn stalled_height_fails_healthz() {
+        let mut vitals = healthy_vitals();
+        vitals.last_committed_height = vitals.previous_committed_height;
+        assert_eq!(healthz(&vitals), ProbeStatus::Unhealthy);
+    }
+
+    #[test]
+    fn insufficient_peers_fails_readyz_but_not_healthz() {
+        let mut vitals = healthy_vitals();
+        vitals.connected_peers = 0;
+        assert_eq!(healthz(&vitals), ProbeStatus::Ok);
+        assert_eq!(readyz(&vitals), ProbeStatus::Unhealthy);
+    }
+
+    #[test]
+    fn view_change_storm_fails_readyz() {
+        let mut vitals = healthy_vitals();
+        vitals.view_changes_in_last_window = 5;
+        assert_eq!(readyz(&vitals), ProbeStatus::Unhealthy);
+    }
+
+    #[test]
+    fn status_codes_match_probe_status() {
+        assert_eq!(ProbeStatus::Ok.http_status_code(), 200);
+        assert_eq!(ProbeStatus::Unhealthy.http_status_code(), 503);
+    }
+}
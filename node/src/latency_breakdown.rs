@@ -0,0 +1,133 @@
+//! `PerformanceStatistics` reported pipeline depth and fast-path config but
+//! nothing about where a commit's wall-clock time actually went, so "why is
+//! latency up" had no answer besides re-instrumenting ad hoc. `LatencyStage`
+//! names each stage of the write path; `LatencyBreakdown` accumulates a
+//! running average per stage from timer samples the caller records as each
+//! stage completes, so `AdminApi::stats()` can report it directly.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LatencyStage {
+    MempoolWait,
+    ProposalAssembly,
+    VoteCollection,
+    QcAggregation,
+    Execution,
+    StorageFlush,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct StageAccumulator {
+    total_ms: u64,
+    samples: u64,
+}
+
+/// Running per-stage average latency, in whole milliseconds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyBreakdown {
+    pub mempool_wait_ms: u64,
+    pub proposal_assembly_ms: u64,
+    pub vote_collection_ms: u64,
+    pub qc_aggregation_ms: u64,
+    pub execution_ms: u64,
+    pub storage_flush_ms: u64,
+}
+
+/// Accumulates timer samples per `LatencyStage` and produces the averaged
+/// `LatencyBreakdown` `AdminApi::stats()` reports.
+#[derive(Default)]
+pub struct LatencyTracker {
+    stages: HashMap<LatencyStage, StageAccumulator>,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, stage: LatencyStage, elapsed_ms: u64) {
+        let acc = self.stages.entry(stage).or_default();
+        acc.total_ms += elapsed_ms;
+        acc.samples += 1;
+    }
+
+    pub fn average_ms(&self, stage: LatencyStage) -> u64 {
+        match self.stages.get(&stage) {
+            Some(acc) if acc.samples > 0 => acc.total_ms / acc.samples,
+            _ => 0,
+        }
+    }
+
+    pub fn breakdown(&self) -> LatencyBreakdown {
+        LatencyBreakdown {
+            mempool_wait_ms: self.average_ms(LatencyStage::MempoolWait),
+            proposal_assembly_ms: self.average_ms(LatencyStage::ProposalAssembly),
+            vote_collection_ms: self.average_ms(LatencyStage::VoteCollection),
+            qc_aggregation_ms: self.average_ms(LatencyStage::QcAggregation),
+            execution_ms: self.average_ms(LatencyStage::Execution),
+            storage_flush_ms: self.average_ms(LatencyStage::StorageFlush),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_unrecorded_stage_averages_to_zero() {
+        let tracker = LatencyTracker::new();
+        assert_eq!(tracker.average_ms(LatencyStage::Execution), 0);
+    }
+
+    #[test]
+    fn a_single_sample_is_its_own_average() {
+        let mut tracker = LatencyTracker::new();
+        tracker.record(LatencyStage::MempoolWait, 40);
+        assert_eq!(tracker.average_ms(LatencyStage::MempoolWait), 40);
+    }
+
+    #[test]
+    fn repeated_samples_average_together() {
+        let mut tracker = LatencyTracker::new();
+        tracker.record(LatencyStage::VoteCollection, 10);
+        tracker.record(LatencyStage::VoteCollection, 30);
+        assert_eq!(tracker.average_ms(LatencyStage::VoteCollection), 20);
+    }
+
+    #[test]
+    fn stages_are_tracked_independently() {
+        let mut tracker = LatencyTracker::new();
+        tracker.record(LatencyStage::Execution, 100);
+        tracker.record(LatencyStage::StorageFlush, 5);
+        let breakdown = tracker.breakdown();
+        assert_eq!(breakdown.execution_ms, 100);
+        assert_eq!(breakdown.storage_flush_ms, 5);
+        assert_eq!(breakdown.mempool_wait_ms, 0);
+    }
+
+    const ALL_STAGES: [LatencyStage; 6] = [
+        LatencyStage::MempoolWait,
+        LatencyStage::ProposalAssembly,
+        LatencyStage::VoteCollection,
+        LatencyStage::QcAggregation,
+        LatencyStage::Execution,
+        LatencyStage::StorageFlush,
+    ];
+
+    #[test]
+    fn every_declared_stage_is_reachable_through_the_breakdown() {
+        let mut tracker = LatencyTracker::new();
+        for (i, stage) in ALL_STAGES.iter().enumerate() {
+            tracker.record(*stage, i as u64 + 1);
+        }
+        let breakdown = tracker.breakdown();
+        assert_eq!(breakdown.mempool_wait_ms, 1);
+        assert_eq!(breakdown.proposal_assembly_ms, 2);
+        assert_eq!(breakdown.vote_collection_ms, 3);
+        assert_eq!(breakdown.qc_aggregation_ms, 4);
+        assert_eq!(breakdown.execution_ms, 5);
+        assert_eq!(breakdown.storage_flush_ms, 6);
+    }
+}
@@ -0,0 +1,31 @@
+pub mod admin;
+pub mod commit_hooks;
+pub mod crash_report;
+pub mod dashboard;
+pub mod dev_mode;
+pub mod finality_stream;
+pub mod health;
+pub mod journal;
+pub mod latency_breakdown;
+pub mod lifecycle;
+pub mod multi_tenant;
+pub mod result_subscription;
+pub mod status_cli;
+pub mod watchdog;
+
+pub use admin::{
+    check_fast_commit_threshold, AdminApi, AdminError, OptimisticModeConfig, PerformanceStatistics, ResponsivenessMode,
+};
+pub use commit_hooks::{CommitHook, CommitHookRegistry, CommitInfo, HookPanic};
+pub use crash_report::{CrashContext, CrashReporter, CrashReporterGuard, PipelineSizes};
+pub use dashboard::{build_dashboard_status, CommitLatencyHistory, DashboardStatus, PeerHealthSummary};
+pub use dev_mode::SingleNodeDevRunner;
+pub use finality_stream::{FinalizedTx, FinalizedTxStream, StreamFull};
+pub use health::{healthz, readyz, NodeVitals, ProbeStatus};
+pub use journal::{testing, JournalEntry, MessageJournal};
+pub use latency_breakdown::{LatencyBreakdown, LatencyStage, LatencyTracker};
+pub use lifecycle::{InvalidTransition, LifecycleState, LifecycleTransition, NodeLifecycle};
+pub use multi_tenant::{ChainId, ChainRegistry, ChainRegistryError};
+pub use result_subscription::{ResultFilter, ResultSubscriptionHub, SubscriptionId};
+pub use status_cli::{render_status_line, StatusWatcher};
+pub use watchdog::{ResourceSample, ResourceThresholds, ResourceWatchdog, WatchdogEvent};
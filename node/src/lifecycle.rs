@@ -0,0 +1,154 @@
+//! Node startup/shutdown used to be two ad-hoc methods with no way to tell
+//! an operator (or a health probe) which of "still catching up",
+//! "serving traffic", or "shutting down" the process was actually in.
+//! `NodeLifecycle` makes the states explicit and the transitions between
+//! them the only way to move between them, and `stop()` runs its shutdown
+//! work — draining in-flight proposals, flushing storage — while in the
+//! `Draining` state instead of tearing the process down underneath it.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleState {
+    Initializing,
+    Syncing,
+    Active,
+    Draining,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifecycleTransition {
+    pub from: LifecycleState,
+    pub to: LifecycleState,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: LifecycleState,
+    pub attempted: LifecycleState,
+}
+
+/// Every legal edge in the lifecycle graph; anything else is rejected by
+/// `transition_to`.
+fn is_legal(from: LifecycleState, to: LifecycleState) -> bool {
+    matches!(
+        (from, to),
+        (LifecycleState::Initializing, LifecycleState::Syncing)
+            | (LifecycleState::Syncing, LifecycleState::Active)
+            | (LifecycleState::Active, LifecycleState::Draining)
+            | (LifecycleState::Draining, LifecycleState::Stopped)
+    )
+}
+
+/// Tracks the node's lifecycle state and the history of transitions it has
+/// made, for the status API to report.
+pub struct NodeLifecycle {
+    state: LifecycleState,
+    history: Vec<LifecycleTransition>,
+}
+
+impl Default for NodeLifecycle {
+    fn default() -> Self {
+        Self { state: LifecycleState::Initializing, history: Vec::new() }
+    }
+}
+
+impl NodeLifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> LifecycleState {
+        self.state
+    }
+
+    pub fn history(&self) -> &[LifecycleTransition] {
+        &self.history
+    }
+
+    /// Moves to `to` if the edge from the current state is legal, recording
+    /// the transition; otherwise leaves the state untouched.
+    pub fn transition_to(&mut self, to: LifecycleState) -> Result<LifecycleTransition, InvalidTransition> {
+        if !is_legal(self.state, to) {
+            return Err(InvalidTransition { from: self.state, attempted: to });
+        }
+        let transition = LifecycleTransition { from: self.state, to };
+        self.state = to;
+        self.history.push(transition);
+        Ok(transition)
+    }
+
+    /// Drains in-flight work and flushes storage before completing shutdown:
+    /// moves `Active -> Draining`, runs `drain`, then `Draining -> Stopped`.
+    /// `drain` is the caller's hook for whatever in-flight-proposal draining
+    /// and storage flush it needs — this crate has no concrete storage
+    /// handle to call directly.
+    pub fn stop(&mut self, drain: impl FnOnce()) -> Result<(), InvalidTransition> {
+        self.transition_to(LifecycleState::Draining)?;
+        drain();
+        self.transition_to(LifecycleState::Stopped)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_in_initializing() {
+        let lifecycle = NodeLifecycle::new();
+        assert_eq!(lifecycle.state(), LifecycleState::Initializing);
+    }
+
+    #[test]
+    fn the_happy_path_walks_through_every_state_in_order() {
+        let mut lifecycle = NodeLifecycle::new();
+        lifecycle.transition_to(LifecycleState::Syncing).unwrap();
+        lifecycle.transition_to(LifecycleState::Active).unwrap();
+        assert_eq!(lifecycle.state(), LifecycleState::Active);
+        assert_eq!(lifecycle.history().len(), 2);
+        lifecycle.transition_to(LifecycleState::Draining).unwrap();
+        lifecycle.transition_to(LifecycleState::Stopped).unwrap();
+        assert_eq!(lifecycle.state(), LifecycleState::Stopped);
+        assert_eq!(lifecycle.history().len(), 4);
+    }
+
+    #[test]
+    fn skipping_a_state_is_rejected() {
+        let mut lifecycle = NodeLifecycle::new();
+        let result = lifecycle.transition_to(LifecycleState::Active);
+        assert_eq!(
+            result,
+            Err(InvalidTransition { from: LifecycleState::Initializing, attempted: LifecycleState::Active })
+        );
+        assert_eq!(lifecycle.state(), LifecycleState::Initializing);
+    }
+
+    #[test]
+    fn stop_drains_before_reaching_stopped() {
+        let mut lifecycle = NodeLifecycle::new();
+        lifecycle.transition_to(LifecycleState::Syncing).unwrap();
+        lifecycle.transition_to(LifecycleState::Active).unwrap();
+
+        let mut drained = false;
+        lifecycle.stop(|| drained = true).unwrap();
+
+        assert!(drained);
+        assert_eq!(lifecycle.state(), LifecycleState::Stopped);
+        assert_eq!(lifecycle.history().last(), Some(&LifecycleTransition {
+            from: LifecycleState::Draining,
+            to: LifecycleState::Stopped,
+        }));
+    }
+
+    #[test]
+    fn stopping_before_active_is_rejected_and_leaves_state_untouched() {
+        let mut lifecycle = NodeLifecycle::new();
+        let result = lifecycle.stop(|| {});
+        assert_eq!(
+            result,
+            Err(InvalidTransition { from: LifecycleState::Initializing, attempted: LifecycleState::Draining })
+        );
+        assert_eq!(lifecycle.state(), LifecycleState::Initializing);
+    }
+}
@@ -0,0 +1,88 @@
+//! Lets a single node host multiple independent consensus instances (shards
+//! or app-chains) multiplexed over one shared network stack, identified by a
+//! `chain_id` carried in the message envelope. Each chain owns its own
+//! opaque per-chain state (`T`); the registry only routes by id.
+
+use std::collections::HashMap;
+
+pub type ChainId = u64;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChainRegistryError {
+    AlreadyRegistered(ChainId),
+    UnknownChain(ChainId),
+}
+
+/// Routes inbound messages to the right per-chain consensus instance by
+/// `chain_id`. `T` is whatever bundle of `HotStuff2` + `BlockStore` + state
+/// machine the embedder wants to keep per chain; this type only multiplexes.
+#[derive(Default)]
+pub struct ChainRegistry<T> {
+    chains: HashMap<ChainId, T>,
+}
+
+impl<T> ChainRegistry<T> {
+    pub fn new() -> Self {
+        Self { chains: HashMap::new() }
+    }
+
+    pub fn register(&mut self, chain_id: ChainId, instance: T) -> Result<(), ChainRegistryError> {
+        if self.chains.contains_key(&chain_id) {
+            return Err(ChainRegistryError::AlreadyRegistered(chain_id));
+        }
+        self.chains.insert(chain_id, instance);
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, chain_id: ChainId) -> Option<T> {
+        self.chains.remove(&chain_id)
+    }
+
+    pub fn get(&self, chain_id: ChainId) -> Result<&T, ChainRegistryError> {
+        self.chains.get(&chain_id).ok_or(ChainRegistryError::UnknownChain(chain_id))
+    }
+
+    pub fn get_mut(&mut self, chain_id: ChainId) -> Result<&mut T, ChainRegistryError> {
+        self.chains.get_mut(&chain_id).ok_or(ChainRegistryError::UnknownChain(chain_id))
+    }
+
+    pub fn chain_ids(&self) -> Vec<ChainId> {
+        self.chains.keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_a_message_to_the_registered_chain() {
+        let mut registry = ChainRegistry::new();
+        registry.register(1, "shard-a").unwrap();
+        registry.register(2, "shard-b").unwrap();
+
+        assert_eq!(*registry.get(1).unwrap(), "shard-a");
+        assert_eq!(*registry.get(2).unwrap(), "shard-b");
+    }
+
+    #[test]
+    fn rejects_duplicate_registration() {
+        let mut registry = ChainRegistry::new();
+        registry.register(1, "shard-a").unwrap();
+        assert_eq!(registry.register(1, "shard-a-again"), Err(ChainRegistryError::AlreadyRegistered(1)));
+    }
+
+    #[test]
+    fn unknown_chain_lookup_is_an_error() {
+        let registry: ChainRegistry<&str> = ChainRegistry::new();
+        assert_eq!(registry.get(99), Err(ChainRegistryError::UnknownChain(99)));
+    }
+
+    #[test]
+    fn unregister_removes_a_chain_and_returns_its_instance() {
+        let mut registry = ChainRegistry::new();
+        registry.register(1, "shard-a").unwrap();
+        assert_eq!(registry.unregister(1), Some("shard-a"));
+        assert!(registry.get(1).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Resource exhaustion — memory growth from a leak, a runaway file
+//! descriptor count, or a data directory that's about to hit `ENOSPC` mid
+//! write — used to go unnoticed until the process was already unhealthy.
+//! `ResourceWatchdog` classifies a resource sample against configurable
+//! thresholds and, once disk usage is critical, says so explicitly enough
+//! that a caller can pause mempool admission before a write actually fails,
+//! rather than risking a torn write into `hotstuff2-storage`. Measuring RSS,
+//! open FDs, and disk usage themselves needs OS-specific syscalls (`statvfs`
+//! for free space has no `std` equivalent, and this workspace has no `libc`
+//! dependency to call it through) — this only classifies a sample the
+//! caller supplies, the same caller-supplies-the-facts boundary `health`
+//! draws around `NodeVitals`.
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceThresholds {
+    pub max_rss_bytes: u64,
+    pub max_open_fds: u64,
+    /// Disk usage fraction (`0.0..=1.0`) at or above which admission should
+    /// pause to avoid writing into an already-full data directory.
+    pub disk_pause_fraction: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceSample {
+    pub rss_bytes: u64,
+    pub open_fds: u64,
+    pub disk_used_fraction: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogEvent {
+    MemoryHigh { rss_bytes: u64, max_rss_bytes: u64 },
+    TooManyOpenFds { open_fds: u64, max_open_fds: u64 },
+    /// Disk usage at or above `disk_pause_fraction`: mempool admission
+    /// should pause until a compaction/GC pass brings usage back down.
+    DiskNearlyFull { used_permille: u32, pause_permille: u32 },
+}
+
+#[derive(Default)]
+pub struct ResourceWatchdog {
+    thresholds: Option<ResourceThresholds>,
+}
+
+impl ResourceWatchdog {
+    pub fn new(thresholds: ResourceThresholds) -> Self {
+        Self { thresholds: Some(thresholds) }
+    }
+
+    /// Classifies `sample` against the configured thresholds, returning one
+    /// event per threshold exceeded (order: memory, fds, disk).
+    pub fn check(&self, sample: ResourceSample) -> Vec<WatchdogEvent> {
+        let Some(thresholds) = self.thresholds else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+        if sample.rss_bytes > thresholds.max_rss_bytes {
+            events.push(WatchdogEvent::MemoryHigh { rss_bytes: sample.rss_bytes, max_rss_bytes: thresholds.max_rss_bytes });
+        }
+        if sample.open_fds > thresholds.max_open_fds {
+            events.push(WatchdogEvent::TooManyOpenFds { open_fds: sample.open_fds, max_open_fds: thresholds.max_open_fds });
+        }
+        if sample.disk_used_fraction >= thresholds.disk_pause_fraction {
+            events.push(WatchdogEvent::DiskNearlyFull {
+                used_permille: (sample.disk_used_fraction * 1000.0).round() as u32,
+                pause_permille: (thresholds.disk_pause_fraction * 1000.0).round() as u32,
+            });
+        }
+        events
+    }
+
+    /// `true` if mempool admission should pause given `events` — currently
+    /// only a near-full disk warrants it; memory/fd pressure is reported
+    /// but doesn't by itself stop the node from accepting transactions.
+    pub fn should_pause_admission(events: &[WatchdogEvent]) -> bool {
+        events.iter().any(|e| matches!(e, WatchdogEvent::DiskNearlyFull { .. }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> ResourceThresholds {
+        ResourceThresholds { max_rss_bytes: 1_000_000, max_open_fds: 100, disk_pause_fraction: 0.9 }
+    }
+
+    #[test]
+    fn a_sample_within_every_threshold_raises_no_events() {
+        let watchdog = ResourceWatchdog::new(thresholds());
+        let sample = ResourceSample { rss_bytes: 500_000, open_fds: 50, disk_used_fraction: 0.5 };
+        assert!(watchdog.check(sample).is_empty());
+    }
+
+    #[test]
+    fn excess_rss_is_reported() {
+        let watchdog = ResourceWatchdog::new(thresholds());
+        let sample = ResourceSample { rss_bytes: 2_000_000, open_fds: 50, disk_used_fraction: 0.5 };
+        assert_eq!(
+            watchdog.check(sample),
+            vec![WatchdogEvent::MemoryHigh { rss_bytes: 2_000_000, max_rss_bytes: 1_000_000 }]
+        );
+    }
+
+    #[test]
+    fn too_many_open_fds_is_reported() {
+        let watchdog = ResourceWatchdog::new(thresholds());
+        let sample = ResourceSample { rss_bytes: 500_000, open_fds: 200, disk_used_fraction: 0.5 };
+        assert_eq!(watchdog.check(sample), vec![WatchdogEvent::TooManyOpenFds { open_fds: 200, max_open_fds: 100 }]);
+    }
+
+    #[test]
+    fn a_nearly_full_disk_is_reported_and_pauses_admission() {
+        let watchdog = ResourceWatchdog::new(thresholds());
+        let sample = ResourceSample { rss_bytes: 500_000, open_fds: 50, disk_used_fraction: 0.95 };
+        let events = watchdog.check(sample);
+        assert_eq!(events, vec![WatchdogEvent::DiskNearlyFull { used_permille: 950, pause_permille: 900 }]);
+        assert!(ResourceWatchdog::should_pause_admission(&events));
+    }
+
+    #[test]
+    fn memory_pressure_alone_does_not_pause_admission() {
+        let watchdog = ResourceWatchdog::new(thresholds());
+        let sample = ResourceSample { rss_bytes: 2_000_000, open_fds: 50, disk_used_fraction: 0.1 };
+        let events = watchdog.check(sample);
+        assert!(!ResourceWatchdog::should_pause_admission(&events));
+    }
+
+    #[test]
+    fn multiple_thresholds_can_be_exceeded_at_once() {
+        let watchdog = ResourceWatchdog::new(thresholds());
+        let sample = ResourceSample { rss_bytes: 2_000_000, open_fds: 200, disk_used_fraction: 0.95 };
+        assert_eq!(watchdog.check(sample).len(), 3);
+    }
+}
@@ -0,0 +1,82 @@
+//! Archive storage mode: maintains secondary indexes (tx hash -> block,
+//! height -> block hash, sender -> tx list) on top of the primary block
+//! store, for the historical lookups an explorer needs. Pruning-mode nodes
+//! simply never construct one of these.
+
+use std::collections::HashMap;
+
+use hotstuff2_types::{Block, Hash, ValidatorId};
+
+#[derive(Default)]
+pub struct ArchiveIndex {
+    tx_to_block: HashMap<Hash, Hash>,
+    height_to_block: HashMap<u64, Hash>,
+    sender_to_txs: HashMap<ValidatorId, Vec<Hash>>,
+}
+
+impl ArchiveIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes a committed block. `tx_senders` supplies the sender for each
+    /// transaction in `block.transactions`, since `Transaction` itself
+    /// doesn't carry sender identity in this workspace's minimal wire types.
+    pub fn index_block(&mut self, block: &Block, tx_senders: &[ValidatorId]) {
+        let block_hash = block.hash();
+        self.height_to_block.insert(block.height, block_hash);
+        for (tx, sender) in block.transactions.iter().zip(tx_senders) {
+            self.tx_to_block.insert(tx.id, block_hash);
+            self.sender_to_txs.entry(*sender).or_default().push(tx.id);
+        }
+    }
+
+    pub fn block_for_tx(&self, tx_hash: Hash) -> Option<Hash> {
+        self.tx_to_block.get(&tx_hash).copied()
+    }
+
+    pub fn block_at_height(&self, height: u64) -> Option<Hash> {
+        self.height_to_block.get(&height).copied()
+    }
+
+    pub fn txs_by_sender(&self, sender: ValidatorId) -> &[Hash] {
+        self.sender_to_txs.get(&sender).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hotstuff2_types::Transaction;
+
+    fn tx(id: u8) -> Transaction {
+        let mut hash = [0u8; 32];
+        hash[0] = id;
+        Transaction { id: hash, payload: vec![], weight: 1, valid_until: None }
+    }
+
+    #[test]
+    fn indexes_block_by_height_and_tx_by_sender() {
+        let mut index = ArchiveIndex::new();
+        let block = Block {
+            parent_hash: [0u8; 32],
+            height: 5,
+            view: 1,
+            transactions: vec![tx(1), tx(2)],
+        };
+        index.index_block(&block, &[10, 20]);
+
+        assert_eq!(index.block_at_height(5), Some(block.hash()));
+        assert_eq!(index.block_for_tx(tx(1).id), Some(block.hash()));
+        assert_eq!(index.txs_by_sender(10), &[tx(1).id]);
+        assert_eq!(index.txs_by_sender(20), &[tx(2).id]);
+    }
+
+    #[test]
+    fn unknown_lookups_return_none_or_empty() {
+        let index = ArchiveIndex::new();
+        assert_eq!(index.block_for_tx([9u8; 32]), None);
+        assert_eq!(index.block_at_height(1), None);
+        assert!(index.txs_by_sender(1).is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+//! Persistent, deduplicated archive of every QC a node has seen, so a
+//! suspected safety violation can be investigated after the fact instead
+//! of only from in-memory state. No RocksDB dependency is available in
+//! this workspace, so `FileAuditLog` uses the same fixed-record on-disk
+//! approach as `metrics::history::OnDiskHistory`: each QC is a fixed-width
+//! binary record appended to a single file, deduplicated in memory by QC
+//! identity so re-observing the same QC from multiple peers doesn't grow
+//! the log. `export_proof_bundle` hands a third party everything needed to
+//! verify a view range offline against the validator set, the same
+//! quorum check (`ValidatorSet::verify_threshold`) this workspace already
+//! uses in place of real aggregated-signature verification.
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use hotstuff2_types::{Hash, QuorumCertificate, ValidatorId, ValidatorSet};
+
+/// Validator sets larger than this can't be recorded in the fixed-width
+/// record format; `append_qc` rejects them explicitly rather than
+/// truncating signers silently.
+const MAX_SIGNERS: usize = 32;
+const QC_RECORD_LEN: usize = 8 + 32 + 8 + MAX_SIGNERS * 8; // view, block_hash, signer_count, signers
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TooManySigners;
+
+/// Append-only, deduplicated on-disk log of QCs.
+pub struct FileAuditLog {
+    path: PathBuf,
+    seen: HashSet<Hash>,
+}
+
+impl FileAuditLog {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if !path.exists() {
+            File::create(&path)?;
+        }
+        let mut log = Self { path, seen: HashSet::new() };
+        for qc in log.read_all()? {
+            log.seen.insert(qc.hash());
+        }
+        Ok(log)
+    }
+
+    /// Appends `qc` unless a QC with the same identity (block + view +
+    /// signer set) was already recorded. Returns whether it was newly
+    /// appended.
+    pub fn append_qc(&mut self, qc: &QuorumCertificate) -> Result<bool, TooManySigners> {
+        if qc.signers.len() > MAX_SIGNERS {
+            return Err(TooManySigners);
+        }
+        let id = qc.hash();
+        if self.seen.contains(&id) {
+            return Ok(false);
+        }
+        let mut file = OpenOptions::new().append(true).open(&self.path).expect("audit log file must be openable");
+        file.write_all(&encode_qc(qc)).expect("audit log append must succeed");
+        self.seen.insert(id);
+        Ok(true)
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    fn read_all(&self) -> io::Result<Vec<QuorumCertificate>> {
+        let mut file = File::open(&self.path)?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        Ok(buf.chunks_exact(QC_RECORD_LEN).map(decode_qc).collect())
+    }
+
+    /// Every QC recorded with `from_view <= qc.view <= to_view`, for
+    /// building a proof bundle a third party can verify offline.
+    pub fn qcs_in_view_range(&self, from_view: u64, to_view: u64) -> io::Result<Vec<QuorumCertificate>> {
+        Ok(self.read_all()?.into_iter().filter(|qc| qc.view >= from_view && qc.view <= to_view).collect())
+    }
+}
+
+fn encode_qc(qc: &QuorumCertificate) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(QC_RECORD_LEN);
+    buf.extend_from_slice(&qc.view.to_le_bytes());
+    buf.extend_from_slice(&qc.block_hash);
+    buf.extend_from_slice(&(qc.signers.len() as u64).to_le_bytes());
+    for i in 0..MAX_SIGNERS {
+        let signer = qc.signers.get(i).copied().unwrap_or(0);
+        buf.extend_from_slice(&signer.to_le_bytes());
+    }
+    buf
+}
+
+fn decode_qc(buf: &[u8]) -> QuorumCertificate {
+    let read_u64 = |range: std::ops::Range<usize>| u64::from_le_bytes(buf[range].try_into().unwrap());
+    let view = read_u64(0..8);
+    let mut block_hash = [0u8; 32];
+    block_hash.copy_from_slice(&buf[8..40]);
+    let signer_count = read_u64(40..48) as usize;
+    let mut signers: Vec<ValidatorId> = Vec::with_capacity(signer_count);
+    for i in 0..signer_count {
+        let start = 48 + i * 8;
+        signers.push(read_u64(start..start + 8));
+    }
+    QuorumCertificate { block_hash, view, signers }
+}
+
+/// Portable bundle a third party can verify without access to this node's
+/// storage at all — just the validator set's public identities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofBundle {
+    pub from_view: u64,
+    pub to_view: u64,
+    pub qcs: Vec<QuorumCertificate>,
+}
+
+impl ProofBundle {
+    /// True only if every QC in the bundle independently meets quorum for
+    /// `validators` — a third party doesn't need to trust the exporting
+    /// node, only the validator set's identities.
+    pub fn verify_offline(&self, validators: &ValidatorSet) -> bool {
+        self.qcs.iter().all(|qc| validators.verify_threshold(qc))
+    }
+}
+
+pub fn export_proof_bundle(log: &FileAuditLog, from_view: u64, to_view: u64) -> io::Result<ProofBundle> {
+    Ok(ProofBundle { from_view, to_view, qcs: log.qcs_in_view_range(from_view, to_view)? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn temp_path(name: &str) -> PathBuf {
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("hotstuff2_audit_log_{name}_{unique}.bin"))
+    }
+
+    fn qc(view: u64, signers: Vec<ValidatorId>) -> QuorumCertificate {
+        QuorumCertificate { block_hash: [view as u8; 32], view, signers }
+    }
+
+    #[test]
+    fn appending_a_new_qc_persists_it() {
+        let path = temp_path("append");
+        let mut log = FileAuditLog::open(&path).unwrap();
+        assert!(log.append_qc(&qc(1, vec![1, 2, 3])).unwrap());
+        assert_eq!(log.len(), 1);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn appending_the_same_qc_twice_is_deduplicated() {
+        let path = temp_path("dedup");
+        let mut log = FileAuditLog::open(&path).unwrap();
+        assert!(log.append_qc(&qc(1, vec![1, 2, 3])).unwrap());
+        assert!(!log.append_qc(&qc(1, vec![1, 2, 3])).unwrap());
+        assert_eq!(log.len(), 1);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn reopening_an_existing_log_recovers_dedup_state() {
+        let path = temp_path("reopen");
+        {
+            let mut log = FileAuditLog::open(&path).unwrap();
+            log.append_qc(&qc(1, vec![1, 2, 3])).unwrap();
+        }
+        let mut log = FileAuditLog::open(&path).unwrap();
+        assert_eq!(log.len(), 1);
+        assert!(!log.append_qc(&qc(1, vec![1, 2, 3])).unwrap());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn a_qc_with_too_many_signers_is_rejected() {
+        let path = temp_path("too_many");
+        let mut log = FileAuditLog::open(&path).unwrap();
+        let too_many: Vec<ValidatorId> = (0..40).collect();
+        assert_eq!(log.append_qc(&qc(1, too_many)), Err(TooManySigners));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn a_proof_bundle_verifies_offline_against_the_validator_set() {
+        let path = temp_path("bundle");
+        let mut log = FileAuditLog::open(&path).unwrap();
+        log.append_qc(&qc(1, vec![1, 2, 3])).unwrap();
+        log.append_qc(&qc(2, vec![1, 2, 3])).unwrap();
+        log.append_qc(&qc(10, vec![1, 2, 3])).unwrap(); // outside the exported range
+
+        let bundle = export_proof_bundle(&log, 1, 2).unwrap();
+        assert_eq!(bundle.qcs.len(), 2);
+        let validators = ValidatorSet { validators: vec![1, 2, 3, 4] };
+        assert!(bundle.verify_offline(&validators));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn a_bundle_with_a_below_quorum_qc_fails_offline_verification() {
+        let path = temp_path("bad_bundle");
+        let mut log = FileAuditLog::open(&path).unwrap();
+        log.append_qc(&qc(1, vec![1])).unwrap(); // below quorum for 4 validators
+        let bundle = export_proof_bundle(&log, 1, 1).unwrap();
+        let validators = ValidatorSet { validators: vec![1, 2, 3, 4] };
+        assert!(!bundle.verify_offline(&validators));
+        std::fs::remove_file(&path).ok();
+    }
+}
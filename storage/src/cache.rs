@@ -0,0 +1,169 @@
+//! In-memory LRU cache for recent blocks and QCs, shared between the
+//! protocol's hot commit path (`commit_block_fast`) and the sync subsystem,
+//! so both stop hitting `BlockStore` directly for data that was just
+//! written or requested. Sized from `StorageConfig::memory_limit_mb` via a
+//! caller-supplied estimated entry size, since this cache doesn't know the
+//! real in-memory size of a `Block`/`QuorumCertificate`.
+
+use std::collections::{HashMap, VecDeque};
+
+use hotstuff2_types::{Block, Hash, QuorumCertificate};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheMetrics {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+struct LruMap<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    order: VecDeque<K>,
+}
+
+impl<K: Eq + std::hash::Hash + Clone, V> LruMap<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, entries: HashMap::new(), order: VecDeque::new() }
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if self.entries.contains_key(key) {
+            if let Some(pos) = self.order.iter().position(|k| k == key) {
+                self.order.remove(pos);
+                self.order.push_back(key.clone());
+            }
+        }
+        self.entries.get(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if !self.entries.contains_key(&key) {
+            if self.order.len() >= self.capacity {
+                if let Some(evicted) = self.order.pop_front() {
+                    self.entries.remove(&evicted);
+                }
+            }
+            self.order.push_back(key.clone());
+        }
+        self.entries.insert(key, value);
+    }
+}
+
+/// `memory_limit_mb` is converted to a block/QC entry count using
+/// `estimated_entry_bytes` as the per-entry size, since neither type
+/// reports its own heap footprint.
+pub struct HotPathCache {
+    blocks: LruMap<Hash, Block>,
+    qcs: LruMap<Hash, QuorumCertificate>,
+    block_metrics: CacheMetrics,
+    qc_metrics: CacheMetrics,
+}
+
+impl HotPathCache {
+    pub fn from_memory_limit(memory_limit_mb: u64, estimated_entry_bytes: u64) -> Self {
+        let capacity = ((memory_limit_mb * 1024 * 1024) / estimated_entry_bytes.max(1)).max(1) as usize;
+        Self {
+            blocks: LruMap::new(capacity),
+            qcs: LruMap::new(capacity),
+            block_metrics: CacheMetrics::default(),
+            qc_metrics: CacheMetrics::default(),
+        }
+    }
+
+    pub fn put_block(&mut self, block: Block) {
+        self.blocks.insert(block.hash(), block);
+    }
+
+    pub fn get_block(&mut self, hash: Hash) -> Option<&Block> {
+        let hit = self.blocks.get(&hash).is_some();
+        if hit {
+            self.block_metrics.hits += 1;
+        } else {
+            self.block_metrics.misses += 1;
+        }
+        self.blocks.get(&hash)
+    }
+
+    pub fn put_qc(&mut self, qc: QuorumCertificate) {
+        self.qcs.insert(qc.hash(), qc);
+    }
+
+    pub fn get_qc(&mut self, hash: Hash) -> Option<&QuorumCertificate> {
+        let hit = self.qcs.get(&hash).is_some();
+        if hit {
+            self.qc_metrics.hits += 1;
+        } else {
+            self.qc_metrics.misses += 1;
+        }
+        self.qcs.get(&hash)
+    }
+
+    pub fn block_metrics(&self) -> CacheMetrics {
+        self.block_metrics
+    }
+
+    pub fn qc_metrics(&self) -> CacheMetrics {
+        self.qc_metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(height: u64) -> Block {
+        Block { parent_hash: [0u8; 32], height, view: 1, transactions: vec![] }
+    }
+
+    #[test]
+    fn a_cached_block_is_a_hit_and_an_uncached_one_is_a_miss() {
+        let mut cache = HotPathCache::from_memory_limit(1, 1);
+        let b = block(1);
+        let hash = b.hash();
+        cache.put_block(b);
+
+        assert!(cache.get_block(hash).is_some());
+        assert!(cache.get_block([9u8; 32]).is_none());
+
+        let metrics = cache.block_metrics();
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(metrics.misses, 1);
+        assert_eq!(metrics.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_inserted_block_once_full() {
+        // 1 MB limit, 1 MB per entry => capacity of 1.
+        let mut cache = HotPathCache::from_memory_limit(1, 1024 * 1024);
+        let b1 = block(1);
+        let b2 = block(2);
+        let h1 = b1.hash();
+        let h2 = b2.hash();
+        cache.put_block(b1);
+        cache.put_block(b2);
+
+        assert!(cache.get_block(h1).is_none());
+        assert!(cache.get_block(h2).is_some());
+    }
+
+    #[test]
+    fn qcs_are_cached_independently_of_blocks() {
+        let mut cache = HotPathCache::from_memory_limit(1, 1);
+        let qc = QuorumCertificate { block_hash: [1u8; 32], view: 1, signers: vec![1, 2, 3] };
+        let hash = qc.hash();
+        cache.put_qc(qc);
+        assert!(cache.get_qc(hash).is_some());
+        assert_eq!(cache.qc_metrics().hits, 1);
+    }
+}
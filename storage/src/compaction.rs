@@ -0,0 +1,95 @@
+//! Storage engine statistics and compaction scheduling. This workspace has
+//! no RocksDB dependency, so `StorageStats` is populated by whatever engine
+//! the caller actually runs (a real deployment would read these off
+//! `rocksdb::DB::property_value`); `CompactionScheduler` only decides *when*
+//! to trigger a manual compaction, based on `compaction_interval_ms` and
+//! activity reported by the pacemaker, and calls back into the caller to
+//! actually run it.
+
+use std::time::Duration;
+
+/// Point-in-time storage engine statistics, exposed through
+/// `MetricsCollector`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StorageStats {
+    pub total_sst_bytes: u64,
+    pub compaction_backlog_bytes: u64,
+    pub cache_hit_rate: f64,
+}
+
+pub trait MetricsCollector {
+    fn record_storage_stats(&mut self, stats: StorageStats);
+}
+
+/// Fed a snapshot of recent consensus activity by the pacemaker; low
+/// activity is when a manual compaction is least disruptive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityLevel {
+    Idle,
+    Active,
+}
+
+pub struct CompactionScheduler {
+    compaction_interval: Duration,
+    elapsed_since_last: Duration,
+    compactions_run: u64,
+}
+
+impl CompactionScheduler {
+    pub fn new(compaction_interval_ms: u64) -> Self {
+        Self {
+            compaction_interval: Duration::from_millis(compaction_interval_ms),
+            elapsed_since_last: Duration::ZERO,
+            compactions_run: 0,
+        }
+    }
+
+    pub fn compactions_run(&self) -> u64 {
+        self.compactions_run
+    }
+
+    /// Called on every pacemaker tick. Returns `true` if the caller should
+    /// run a manual compaction now: the interval has elapsed *and* the
+    /// pacemaker reports the node is currently idle, so compaction I/O
+    /// doesn't compete with active consensus traffic.
+    pub fn on_tick(&mut self, elapsed: Duration, activity: ActivityLevel) -> bool {
+        self.elapsed_since_last += elapsed;
+        if self.elapsed_since_last < self.compaction_interval || activity != ActivityLevel::Idle {
+            return false;
+        }
+        self.elapsed_since_last = Duration::ZERO;
+        self.compactions_run += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_not_trigger_before_the_interval_elapses() {
+        let mut scheduler = CompactionScheduler::new(1000);
+        assert!(!scheduler.on_tick(Duration::from_millis(500), ActivityLevel::Idle));
+    }
+
+    #[test]
+    fn does_not_trigger_while_the_node_is_active_even_past_the_interval() {
+        let mut scheduler = CompactionScheduler::new(1000);
+        assert!(!scheduler.on_tick(Duration::from_millis(1500), ActivityLevel::Active));
+    }
+
+    #[test]
+    fn triggers_once_the_interval_elapses_during_idle_time() {
+        let mut scheduler = CompactionScheduler::new(1000);
+        assert!(scheduler.on_tick(Duration::from_millis(1500), ActivityLevel::Idle));
+        assert_eq!(scheduler.compactions_run(), 1);
+    }
+
+    #[test]
+    fn resets_the_timer_after_triggering() {
+        let mut scheduler = CompactionScheduler::new(1000);
+        scheduler.on_tick(Duration::from_millis(1000), ActivityLevel::Idle);
+        assert!(!scheduler.on_tick(Duration::from_millis(500), ActivityLevel::Idle));
+    }
+}
@@ -0,0 +1,15 @@
+pub mod archive;
+pub mod audit_log;
+pub mod backup;
+pub mod cache;
+pub mod compaction;
+pub mod qc_backfill;
+pub mod write_behind;
+
+pub use archive::ArchiveIndex;
+pub use audit_log::{export_proof_bundle, FileAuditLog, ProofBundle, TooManySigners};
+pub use backup::{create_backup, restore_from_backup, BackedUpFile, BackupManifest, RestoreError, MANIFEST_FILE_NAME};
+pub use cache::{CacheMetrics, HotPathCache};
+pub use compaction::{ActivityLevel, CompactionScheduler, MetricsCollector, StorageStats};
+pub use qc_backfill::{QcBackfillRequest, QcBackfillTracker};
+pub use write_behind::{BlockSink, StorageConfig, SyncPolicy, WriteBehindStore};
@@ -0,0 +1,125 @@
+//! A block committed via the optimistic fast path (see `fast_commit_quorum`
+//! in `hotstuff2-types`) can land in storage without the intermediate QCs
+//! an auditor's 3-chain check (`consensus::fork_audit`) expects at every
+//! height. `QcBackfillTracker` records which committed heights are missing
+//! their QC and decides what a background task should fetch next;
+//! `record_backfilled` is what that task calls once it has actually
+//! retrieved and verified one, at which point the height is marked fully
+//! auditable. This crate only tracks the gap and the request list — the
+//! actual network fetch belongs to whatever `NetworkMsg` the transport
+//! defines, mirroring the request/response split `network::catch_up`
+//! already draws between "decide what to ask for" and "how to ask".
+
+use std::collections::{BTreeSet, HashMap};
+
+use hotstuff2_types::QuorumCertificate;
+
+/// What a background backfill task should fetch next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QcBackfillRequest {
+    pub height: u64,
+}
+
+/// Tracks which committed heights are missing their QC and which have since
+/// been backfilled and verified.
+#[derive(Default)]
+pub struct QcBackfillTracker {
+    missing: BTreeSet<u64>,
+    qcs: HashMap<u64, QuorumCertificate>,
+}
+
+impl QcBackfillTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called when `height` commits via a path that didn't leave behind a
+    /// QC (e.g. the fast path). No-op if this height was already backfilled.
+    pub fn mark_committed_without_qc(&mut self, height: u64) {
+        if !self.qcs.contains_key(&height) {
+            self.missing.insert(height);
+        }
+    }
+
+    /// Every height still missing its QC, in ascending order — what a
+    /// background task should request next, oldest gap first.
+    pub fn pending_requests(&self) -> Vec<QcBackfillRequest> {
+        self.missing.iter().map(|&height| QcBackfillRequest { height }).collect()
+    }
+
+    /// Called once a background task has fetched and verified the QC for
+    /// `height`: persists it and marks the height fully auditable.
+    pub fn record_backfilled(&mut self, height: u64, qc: QuorumCertificate) {
+        self.missing.remove(&height);
+        self.qcs.insert(height, qc);
+    }
+
+    pub fn qc_for_height(&self, height: u64) -> Option<&QuorumCertificate> {
+        self.qcs.get(&height)
+    }
+
+    /// A height is fully auditable once it was never missing a QC to begin
+    /// with, or one has since been backfilled.
+    pub fn is_fully_auditable(&self, height: u64) -> bool {
+        self.qcs.contains_key(&height) || !self.missing.contains(&height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qc(height: u64) -> QuorumCertificate {
+        QuorumCertificate { block_hash: [height as u8; 32], view: height, signers: vec![1, 2, 3] }
+    }
+
+    #[test]
+    fn a_height_never_marked_missing_is_already_auditable() {
+        let tracker = QcBackfillTracker::new();
+        assert!(tracker.is_fully_auditable(5));
+    }
+
+    #[test]
+    fn a_height_missing_its_qc_is_not_auditable_until_backfilled() {
+        let mut tracker = QcBackfillTracker::new();
+        tracker.mark_committed_without_qc(5);
+        assert!(!tracker.is_fully_auditable(5));
+        assert_eq!(tracker.pending_requests(), vec![QcBackfillRequest { height: 5 }]);
+    }
+
+    #[test]
+    fn backfilling_marks_the_height_auditable_and_clears_the_request() {
+        let mut tracker = QcBackfillTracker::new();
+        tracker.mark_committed_without_qc(5);
+        tracker.record_backfilled(5, qc(5));
+        assert!(tracker.is_fully_auditable(5));
+        assert!(tracker.pending_requests().is_empty());
+        assert_eq!(tracker.qc_for_height(5), Some(&qc(5)));
+    }
+
+    #[test]
+    fn pending_requests_are_returned_oldest_gap_first() {
+        let mut tracker = QcBackfillTracker::new();
+        tracker.mark_committed_without_qc(9);
+        tracker.mark_committed_without_qc(3);
+        tracker.mark_committed_without_qc(6);
+        assert_eq!(
+            tracker.pending_requests(),
+            vec![
+                QcBackfillRequest { height: 3 },
+                QcBackfillRequest { height: 6 },
+                QcBackfillRequest { height: 9 },
+            ]
+        );
+    }
+
+    #[test]
+    fn marking_an_already_backfilled_height_missing_again_is_a_no_op() {
+        let mut tracker = QcBackfillTracker::new();
+        tracker.mark_committed_without_qc(5);
+        tracker.record_backfilled(5, qc(5));
+        tracker.mark_committed_without_qc(5);
+        assert!(tracker.is_fully_auditable(5));
+        assert!(tracker.pending_requests().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Write-behind layer over a `BlockStore`: instead of fsyncing every block
+//! synchronously on the write path, blocks are buffered and flushed in
+//! batches, so throughput isn't bounded by per-block fsyncs. The actual
+//! durability write (and any real disk fsync) is owned by the caller via
+//! `BlockSink`; this crate has no async runtime or filesystem dependency, so
+//! "async" here means "deferred to an explicit `flush()` call on a commit
+//! boundary" rather than a background task.
+
+use hotstuff2_types::Block;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// fsync (via `BlockSink::fsync`) after every flush, tied to
+    /// `StorageConfig::sync_writes = true`.
+    EveryFlush,
+    /// Never fsync explicitly; rely on the OS/backing store's own durability.
+    /// Tied to `StorageConfig::sync_writes = false`.
+    Never,
+}
+
+pub struct StorageConfig {
+    pub sync_writes: bool,
+    pub max_batch_size: usize,
+}
+
+impl StorageConfig {
+    fn sync_policy(&self) -> SyncPolicy {
+        if self.sync_writes {
+            SyncPolicy::EveryFlush
+        } else {
+            SyncPolicy::Never
+        }
+    }
+}
+
+/// The durability boundary this layer defers to. A real implementation
+/// writes to disk and, in `fsync`, calls the platform fsync/fdatasync.
+pub trait BlockSink {
+    fn write_batch(&mut self, blocks: &[Block]);
+    fn fsync(&mut self);
+}
+
+/// Buffers committed blocks and flushes them to a `BlockSink` in batches.
+/// `flush` is called explicitly on commit boundaries by the caller (e.g.
+/// after a QC forms), rather than blocking the write path on I/O per block.
+pub struct WriteBehindStore<S: BlockSink> {
+    sink: S,
+    policy: SyncPolicy,
+    max_batch_size: usize,
+    pending: Vec<Block>,
+    flushed_count: u64,
+}
+
+impl<S: BlockSink> WriteBehindStore<S> {
+    pub fn new(sink: S, config: &StorageConfig) -> Self {
+        Self {
+            sink,
+            policy: config.sync_policy(),
+            max_batch_size: config.max_batch_size,
+            pending: Vec::new(),
+            flushed_count: 0,
+        }
+    }
+
+    /// Buffers `block` for the next flush. Flushes immediately if the batch
+    /// has grown to `max_batch_size`, so an unbounded backlog can't build up
+    /// between explicit `flush()` calls.
+    pub fn append(&mut self, block: Block) {
+        self.pending.push(block);
+        if self.pending.len() >= self.max_batch_size {
+            self.flush();
+        }
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn flushed_count(&self) -> u64 {
+        self.flushed_count
+    }
+
+    /// Writes out whatever is buffered and, per `SyncPolicy`, fsyncs.
+    /// Called by the caller on commit boundaries.
+    pub fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        self.sink.write_batch(&self.pending);
+        self.flushed_count += self.pending.len() as u64;
+        self.pending.clear();
+        if self.policy == SyncPolicy::EveryFlush {
+            self.sink.fsync();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingSink {
+        written: Vec<Block>,
+        fsync_count: u32,
+    }
+
+    impl BlockSink for RecordingSink {
+        fn write_batch(&mut self, blocks: &[Block]) {
+            self.written.extend_from_slice(blocks);
+        }
+
+        fn fsync(&mut self) {
+            self.fsync_count += 1;
+        }
+    }
+
+    fn block(height: u64) -> Block {
+        Block { parent_hash: [0u8; 32], height, view: 1, transactions: vec![] }
+    }
+
+    #[test]
+    fn appended_blocks_stay_buffered_until_flush() {
+        let config = StorageConfig { sync_writes: true, max_batch_size: 100 };
+        let mut store = WriteBehindStore::new(RecordingSink::default(), &config);
+        store.append(block(1));
+        store.append(block(2));
+        assert_eq!(store.pending_count(), 2);
+        assert!(store.sink.written.is_empty());
+    }
+
+    #[test]
+    fn flush_writes_the_whole_batch_and_fsyncs_when_configured() {
+        let config = StorageConfig { sync_writes: true, max_batch_size: 100 };
+        let mut store = WriteBehindStore::new(RecordingSink::default(), &config);
+        store.append(block(1));
+        store.append(block(2));
+        store.flush();
+
+        assert_eq!(store.sink.written.len(), 2);
+        assert_eq!(store.sink.fsync_count, 1);
+        assert_eq!(store.pending_count(), 0);
+        assert_eq!(store.flushed_count(), 2);
+    }
+
+    #[test]
+    fn sync_writes_false_skips_fsync() {
+        let config = StorageConfig { sync_writes: false, max_batch_size: 100 };
+        let mut store = WriteBehindStore::new(RecordingSink::default(), &config);
+        store.append(block(1));
+        store.flush();
+        assert_eq!(store.sink.fsync_count, 0);
+    }
+
+    #[test]
+    fn reaching_max_batch_size_flushes_automatically() {
+        let config = StorageConfig { sync_writes: false, max_batch_size: 2 };
+        let mut store = WriteBehindStore::new(RecordingSink::default(), &config);
+        store.append(block(1));
+        store.append(block(2));
+        assert_eq!(store.pending_count(), 0);
+        assert_eq!(store.sink.written.len(), 2);
+    }
+}
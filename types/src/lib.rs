@@ -0,0 +1,123 @@
+//! Minimal wire types shared across the workspace's crates.
+
+pub type Hash = [u8; 32];
+pub type ValidatorId = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub id: Hash,
+    pub payload: Vec<u8>,
+    /// Gas/weight cost of including this transaction in a block, used to pack
+    /// blocks against `max_block_size` by cumulative weight rather than raw
+    /// transaction count.
+    pub weight: u64,
+    /// Block height beyond which this transaction is no longer valid, set by
+    /// the submitting client to bound how long a stale transfer may execute.
+    /// `None` means the transaction never expires.
+    pub valid_until: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub parent_hash: Hash,
+    pub height: u64,
+    pub view: u64,
+    pub transactions: Vec<Transaction>,
+}
+
+impl Block {
+    pub fn hash(&self) -> Hash {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash as _, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.parent_hash.hash(&mut hasher);
+        self.height.hash(&mut hasher);
+        self.view.hash(&mut hasher);
+        for tx in &self.transactions {
+            tx.id.hash(&mut hasher);
+        }
+        let digest = hasher.finish();
+
+        let mut out = [0u8; 32];
+        out[..8].copy_from_slice(&digest.to_le_bytes());
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuorumCertificate {
+    pub block_hash: Hash,
+    pub view: u64,
+    pub signers: Vec<ValidatorId>,
+}
+
+impl QuorumCertificate {
+    /// Identity for this exact QC: block + view + signer set. Two QCs over the
+    /// same block at different views (e.g. after a view-change re-proposal)
+    /// hash differently and are tracked independently by callers.
+    pub fn hash(&self) -> Hash {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash as _, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.block_hash.hash(&mut hasher);
+        self.view.hash(&mut hasher);
+        self.signers.hash(&mut hasher);
+        let digest = hasher.finish();
+
+        let mut out = [0u8; 32];
+        out[..8].copy_from_slice(&digest.to_le_bytes());
+        out
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidatorSet {
+    pub validators: Vec<ValidatorId>,
+}
+
+impl ValidatorSet {
+    pub fn quorum_size(&self) -> usize {
+        (2 * self.validators.len()) / 3 + 1
+    }
+
+    /// Checks that every signer is a known validator and that there are enough
+    /// of them to meet quorum. Standing in for real threshold-signature
+    /// verification until crypto/ wires an actual aggregated signature scheme.
+    pub fn verify_threshold(&self, qc: &QuorumCertificate) -> bool {
+        qc.signers.iter().all(|s| self.validators.contains(s)) && qc.signers.len() >= self.quorum_size()
+    }
+
+    /// The maximum number of Byzantine validators this set can tolerate
+    /// under the standard `n = 3f + 1` assumption.
+    pub fn max_faulty(&self) -> usize {
+        (self.validators.len().saturating_sub(1)) / 3
+    }
+
+    /// The quorum an optimistic fast-commit path needs to be provably safe
+    /// without falling back to a view-change: all-but-`f` validators. With
+    /// this set's integer arithmetic that equals `quorum_size()` for every
+    /// non-empty set.
+    pub fn fast_commit_quorum(&self) -> usize {
+        self.validators.len() - self.max_faulty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_hash_changes_with_height() {
+        let mut block = Block {
+            parent_hash: [0u8; 32],
+            height: 1,
+            view: 1,
+            transactions: vec![],
+        };
+        let h1 = block.hash();
+        block.height = 2;
+        assert_ne!(h1, block.hash());
+    }
+}